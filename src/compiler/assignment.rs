@@ -11,7 +11,67 @@ fn compile_assignment<T: LLVMCompiler>(
     expr: &crate::expression::Assignment,
     span: Span,
 ) -> CompilerResult<Value> {
-    let right = compiler.walk(&expr.right)?;
+    if let (Expression::Identifier(name), Expression::FuncDecl(e)) =
+        (&expr.left.expression, &expr.right.expression)
+    {
+        if let Some(prototype) = compiler.take_prototype(name) {
+            compiler.build_function(prototype, e, expr.right.span.clone())?;
+            return Ok(Value::Void);
+        }
+    }
+
+    // the type of a field or element is only known once the target is
+    // compiled, so the value is compiled along with it
+    if let Expression::FieldAccess(_) | Expression::Index(_) = &expr.left.expression {
+        let right = match &expr.left.expression {
+            Expression::FieldAccess(target) => compiler.assign_field(target, &expr.right, span)?,
+            Expression::Index(target) => compiler.assign_index(target, &expr.right, span)?,
+            _ => unreachable!(),
+        };
+        if let Expression::FuncDecl(e) = &expr.right.expression {
+            compiler.build_function(right, e, expr.right.span.clone())?
+        }
+        return Ok(Value::Void);
+    }
+
+    let right = match (&expr.left.expression, &expr.right.expression) {
+        (_, Expression::FuncDecl(e)) => compiler.declare_function(e),
+        (Expression::Identifier(name), _) => match compiler.get_var(name) {
+            Some(var) => compiler.walk_expecting(&expr.right, &var.get_type())?,
+            None => compiler.walk(&expr.right)?,
+        },
+        _ => compiler.walk(&expr.right)?,
+    };
+    let right = match (&expr.left.expression, right) {
+        (Expression::Identifier(name), right @ Value::Function { .. })
+            if matches!(compiler.get_var(name), Some(Variable::Closure { .. })) =>
+        {
+            compiler.build_closure(right)
+        }
+        (_, right) => right,
+    };
+
+    let right = match &expr.left.expression {
+        Expression::Identifier(name) => match compiler.get_var(name) {
+            Some(var) => {
+                if right.get_type() != var.get_type() {
+                    Err(CompilerError::TypeError {
+                        expected: var.get_type(),
+                        actual: right.get_type(),
+                        span: expr.right.span.clone(),
+                    })?
+                }
+                right
+            }
+            None => match right {
+                Value::Void | Value::Break(_) | Value::Continue(_) => {
+                    Err(CompilerError::VoidAssignment)?
+                }
+                right => right,
+            },
+        },
+        _ => right,
+    };
 
     let ptr = if let Expression::Identifier(name) = &expr.left.expression {
         match compiler.get_var(name) {
@@ -43,7 +103,7 @@ fn compile_assignment<T: LLVMCompiler>(
                             "",
                         );
                     }
-                    Variable::Vec(val) => {
+                    Variable::Vec { val, .. } => {
                         let release = compiler
                             .module()
                             .get_function("release_vec_reference")
@@ -59,58 +119,134 @@ fn compile_assignment<T: LLVMCompiler>(
                             "",
                         );
                     }
+                    Variable::Closure { val, .. } => {
+                        let release = compiler
+                            .module()
+                            .get_function("release_env_reference")
+                            .unwrap();
+                        compiler.builder().build_call(
+                            &release,
+                            &[compiler.builder().build_load(
+                                &var.llvm_type(compiler.context()),
+                                &val,
+                                "",
+                            )],
+                            "",
+                        );
+                    }
+                    Variable::Struct { val, .. } | Variable::Enum { val, .. } => {
+                        let release = compiler
+                            .module()
+                            .get_function("release_struct_reference")
+                            .unwrap();
+                        compiler.builder().build_call(
+                            &release,
+                            &[compiler.builder().build_load(
+                                &var.llvm_type(compiler.context()),
+                                &val,
+                                "",
+                            )],
+                            "",
+                        );
+                    }
                     Variable::Numeric(_)
+                    | Variable::Int(_)
                     | Variable::Bool(_)
                     | Variable::Function { .. }
                     | Variable::Ptr(_) => {}
                 }
-                compiler.builder().create_store(right.into(), &ptr);
+                compiler.builder().create_store((&right).into(), &ptr);
                 var.set_value(ptr);
             }
             None => {
                 let var = match right {
                     Value::String(_) => Variable::String(ptr),
                     Value::Numeric(_) => Variable::Numeric(ptr),
+                    Value::Int(_) => Variable::Int(ptr),
                     Value::Bool(_) => Variable::Bool(ptr),
                     Value::Function {
-                        return_type,
+                        ref return_type,
                         typ,
                         val,
+                        ref params,
+                        env,
                     } => Variable::Function {
                         val,
                         typ,
-                        return_type,
+                        return_type: return_type.clone(),
+                        params: params.clone(),
+                        env,
+                    },
+                    Value::Closure {
+                        ref return_type,
+                        ref params,
+                        ..
+                    } => Variable::Closure {
+                        val: ptr,
+                        return_type: return_type.clone(),
+                        params: params.clone(),
+                    },
+                    Value::Vec { ref element, .. } => Variable::Vec {
+                        val: ptr,
+                        element: element.clone(),
+                    },
+                    Value::Struct { ref name, .. } => Variable::Struct {
+                        val: ptr,
+                        name: name.clone(),
+                    },
+                    Value::Enum { ref name, .. } => Variable::Enum {
+                        val: ptr,
+                        name: name.clone(),
                     },
-                    Value::Vec(_) => Variable::Vec(ptr),
                     Value::Ptr(_) => Variable::Ptr(ptr),
-                    Value::Void | Value::Break => Err(CompilerError::VoidAssignment)?,
+                    Value::Void | Value::Break(_) | Value::Continue(_) => {
+                        Err(CompilerError::VoidAssignment)?
+                    }
                     Value::CString(_) => todo!(),
                 };
 
-                compiler.builder().create_store(right.into(), &ptr);
+                compiler.builder().create_store((&right).into(), &ptr);
                 compiler.set_var(name, var);
             }
         }
 
-        match right {
+        match &right {
             Value::String(val) => {
                 let release = compiler
                     .module()
                     .get_function("inc_string_reference")
                     .unwrap();
 
-                compiler.builder().build_call(&release, &[val], "");
+                compiler.builder().build_call(&release, &[*val], "");
             }
             Value::Numeric(_) => {}
+            Value::Int(_) => {}
             Value::Bool(_) => {}
+            Value::Function { env: Some(env), .. } if !env.is_null() => {
+                let inc = compiler.module().get_function("inc_env_reference").unwrap();
+                compiler.builder().build_call(&inc, &[*env], "");
+            }
             Value::Function { .. } => {}
-            Value::Vec(val) => {
+            Value::Closure { val, .. } => {
+                let inc = compiler.module().get_function("inc_env_reference").unwrap();
+                compiler.builder().build_call(&inc, &[*val], "");
+            }
+            Value::Vec { val, .. } => {
                 let release = compiler.module().get_function("inc_vec_reference").unwrap();
 
-                compiler.builder().build_call(&release, &[val], "");
+                compiler.builder().build_call(&release, &[*val], "");
+            }
+            Value::Struct { val, .. } | Value::Enum { val, .. } => {
+                let inc = compiler
+                    .module()
+                    .get_function("inc_struct_reference")
+                    .unwrap();
+                compiler.builder().build_call(&inc, &[*val], "");
             }
             Value::Ptr(_) => {}
-            Value::Void | Value::Break => Err(CompilerError::VoidAssignment)?,
+            Value::Void | Value::Break(_) | Value::Continue(_) => {
+                Err(CompilerError::VoidAssignment)?
+            }
             Value::CString(_) => todo!(),
         };
     } else {
@@ -118,7 +254,7 @@ fn compile_assignment<T: LLVMCompiler>(
     }
 
     if let expression::Expression::FuncDecl(e) = &expr.right.expression {
-        compiler.build_function(right, e)?
+        compiler.build_function(right, e, expr.right.span.clone())?
     }
 
     Ok(Value::Void)
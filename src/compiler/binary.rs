@@ -1,35 +1,298 @@
 use crate::{
     expression::{self},
-    parser::Span,
+    llvm,
+    parser::{self, Span},
     visitor::BinaryVisitor,
 };
 
-use super::{value::Value, Compiler, CompilerError, CompilerResult, LLVMCompiler};
+use super::{
+    utils::int_literal, value::Value, Compiler, CompilerError, CompilerResult, LLVMCompiler,
+};
 
-fn compile_binary<T: LLVMCompiler>(
+fn compile_logical<T: LLVMCompiler>(
     compiler: &mut T,
     expr: &expression::Binary,
     span: Span,
 ) -> CompilerResult<Value> {
     let lhs = match compiler.walk(&expr.left)? {
-        Value::Numeric(n) => n,
+        Value::Bool(b) => b,
         expr => Err(CompilerError::TypeError {
-            expected: crate::parser::Type::Numeric,
+            expected: crate::parser::Type::Bool,
             actual: expr.get_type(),
             span: span.clone(),
         })?,
     };
 
+    let lhs_block = compiler.builder().get_insert_block();
+    let fun = lhs_block.get_parent();
+
+    let rhs_block = compiler.context().append_basic_block(&fun, "logicrhs");
+    let after_block = compiler.context().append_basic_block(&fun, "afterlogic");
+
+    match expr.operator {
+        expression::Operator::And => {
+            compiler
+                .builder()
+                .build_cond_br(&lhs, &rhs_block, &after_block);
+        }
+        _ => {
+            compiler
+                .builder()
+                .build_cond_br(&lhs, &after_block, &rhs_block);
+        }
+    };
+
+    compiler.builder().position_builder_at_end(&rhs_block);
+
+    let orphaned_len = compiler.maybe_orphaned_len();
     let rhs = match compiler.walk(&expr.right)? {
-        Value::Numeric(n) => n,
+        Value::Bool(b) => b,
         expr => Err(CompilerError::TypeError {
-            expected: crate::parser::Type::Numeric,
+            expected: crate::parser::Type::Bool,
             actual: expr.get_type(),
             span,
         })?,
     };
+    compiler.release_maybe_orphaned_since(orphaned_len);
 
-    match expr.operator {
+    let rhs_block = compiler.builder().get_insert_block();
+    compiler.builder().create_br(&after_block);
+
+    compiler.builder().position_builder_at_end(&after_block);
+    let phi = compiler
+        .builder()
+        .build_phi(compiler.context().i1_type(), "");
+    phi.add_incoming(&[(lhs, lhs_block), (rhs, rhs_block)]);
+
+    Ok(Value::Bool(phi))
+}
+
+fn compile_binary<T: LLVMCompiler>(
+    compiler: &mut T,
+    expr: &expression::Binary,
+    span: Span,
+) -> CompilerResult<Value> {
+    if let expression::Operator::And | expression::Operator::Or = expr.operator {
+        return compile_logical(compiler, expr, span);
+    }
+
+    // integer literals take the type of the other operand, so they are
+    // compiled after it, and are numbers when both operands are literals
+    let (lhs, rhs) = match expr.operator {
+        expression::Operator::BitAnd
+        | expression::Operator::BitOr
+        | expression::Operator::BitXor
+        | expression::Operator::ShiftLeft
+        | expression::Operator::ShiftRight => (
+            compiler.walk_expecting(&expr.left, &parser::Type::Int)?,
+            compiler.walk_expecting(&expr.right, &parser::Type::Int)?,
+        ),
+        _ if int_literal(&expr.left).is_some() && int_literal(&expr.right).is_none() => {
+            let rhs = compiler.walk(&expr.right)?;
+            (compiler.walk_expecting(&expr.left, &rhs.get_type())?, rhs)
+        }
+        _ => {
+            let lhs = compiler.walk(&expr.left)?;
+            (
+                lhs.clone(),
+                compiler.walk_expecting(&expr.right, &lhs.get_type())?,
+            )
+        }
+    };
+
+    compile_binary_values(compiler, &expr.operator, lhs, rhs, span)
+}
+
+/// Applies a non-logical operator to already compiled operands.
+pub(super) fn compile_binary_values<T: LLVMCompiler>(
+    compiler: &mut T,
+    operator: &expression::Operator,
+    lhs: Value,
+    rhs: Value,
+    span: Span,
+) -> CompilerResult<Value> {
+    if let expression::Operator::BitAnd
+    | expression::Operator::BitOr
+    | expression::Operator::BitXor
+    | expression::Operator::ShiftLeft
+    | expression::Operator::ShiftRight = *operator
+    {
+        return match (lhs, rhs) {
+            (Value::Int(lhs), Value::Int(rhs)) => {
+                compile_int_binary(compiler, operator, lhs, rhs, span)
+            }
+            (Value::Int(_), val) | (val, _) => Err(CompilerError::TypeError {
+                expected: crate::parser::Type::Int,
+                actual: val.get_type(),
+                span,
+            }),
+        };
+    }
+
+    match (lhs, rhs) {
+        (Value::Numeric(lhs), Value::Numeric(rhs)) => {
+            compile_numeric_binary(compiler, operator, lhs, rhs)
+        }
+        (Value::Int(lhs), Value::Int(rhs)) => {
+            compile_int_binary(compiler, operator, lhs, rhs, span)
+        }
+        // converting between ints and numbers is left to `int` and `number`
+        (lhs @ Value::Int(_), rhs @ Value::Numeric(_))
+        | (lhs @ Value::Numeric(_), rhs @ Value::Int(_)) => Err(CompilerError::TypeError {
+            expected: lhs.get_type(),
+            actual: rhs.get_type(),
+            span,
+        }),
+        (Value::Bool(lhs), Value::Bool(rhs)) => match *operator {
+            expression::Operator::Equal => Ok(Value::Bool(compiler.builder().build_icmp(
+                lhs,
+                rhs,
+                crate::llvm::Cmp::Equal,
+                "",
+            ))),
+            expression::Operator::NotEqual => Ok(Value::Bool(compiler.builder().build_icmp(
+                lhs,
+                rhs,
+                crate::llvm::Cmp::NotEqual,
+                "",
+            ))),
+            _ => Err(CompilerError::TypeError {
+                expected: crate::parser::Type::Numeric,
+                actual: crate::parser::Type::Bool,
+                span,
+            }),
+        },
+        (Value::String(lhs), Value::String(rhs)) => match *operator {
+            expression::Operator::Plus => {
+                let string_concat = compiler.module().get_function("string_concat").unwrap();
+                let val = Value::String(compiler.builder().build_call(
+                    &string_concat,
+                    &[lhs, rhs],
+                    "",
+                ));
+                compiler.track_maybe_orphaned(val.clone());
+                Ok(val)
+            }
+            expression::Operator::Equal => {
+                let string_equal = compiler.module().get_function("string_equal").unwrap();
+                Ok(Value::Bool(compiler.builder().build_call(
+                    &string_equal,
+                    &[lhs, rhs],
+                    "",
+                )))
+            }
+            expression::Operator::NotEqual => {
+                let string_equal = compiler.module().get_function("string_equal").unwrap();
+                let equal = compiler
+                    .builder()
+                    .build_call(&string_equal, &[lhs, rhs], "");
+                Ok(Value::Bool(compiler.builder().build_not(equal, "")))
+            }
+            // ordered by comparing the result of the stdlib comparison to 0
+            expression::Operator::Less
+            | expression::Operator::Greater
+            | expression::Operator::LessOrEqual
+            | expression::Operator::GreaterOrEqual => {
+                let string_compare = compiler.module().get_function("string_compare").unwrap();
+                let ordering = compiler
+                    .builder()
+                    .build_call(&string_compare, &[lhs, rhs], "");
+                let zero = compiler.context().const_i64(0);
+                compile_int_binary(compiler, operator, ordering, zero, span)
+            }
+            _ => Err(CompilerError::TypeError {
+                expected: crate::parser::Type::Numeric,
+                actual: crate::parser::Type::String,
+                span,
+            }),
+        },
+        (lhs, rhs) if std::mem::discriminant(&lhs) != std::mem::discriminant(&rhs) => {
+            Err(CompilerError::TypeError {
+                expected: lhs.get_type(),
+                actual: rhs.get_type(),
+                span,
+            })
+        }
+        (lhs, _) => Err(CompilerError::TypeError {
+            expected: crate::parser::Type::Numeric,
+            actual: lhs.get_type(),
+            span,
+        }),
+    }
+}
+
+fn compile_int_binary<T: LLVMCompiler>(
+    compiler: &mut T,
+    operator: &expression::Operator,
+    lhs: llvm::Value,
+    rhs: llvm::Value,
+    span: Span,
+) -> CompilerResult<Value> {
+    let builder = compiler.builder();
+    let val = match operator {
+        expression::Operator::Plus => Value::Int(builder.build_add(lhs, rhs, "")),
+        expression::Operator::Minus => Value::Int(builder.build_sub(lhs, rhs, "")),
+        expression::Operator::Asterisk => Value::Int(builder.build_mul(lhs, rhs, "")),
+        expression::Operator::Slash | expression::Operator::Mod => {
+            // dividing by zero or overflowing the quotient is undefined in
+            // llvm, so it aborts like an out of bounds index
+            let check = compiler.module().get_function("int_check_div").unwrap();
+            let line = compiler.context().const_i64(span.line.into());
+            let column = compiler.context().const_i64(span.column.into());
+            builder.build_call(&check, &[lhs, rhs, line, column], "");
+            match operator {
+                expression::Operator::Slash => Value::Int(builder.build_sdiv(lhs, rhs, "")),
+                _ => Value::Int(builder.build_srem(lhs, rhs, "")),
+            }
+        }
+        expression::Operator::Equal => {
+            Value::Bool(builder.build_icmp(lhs, rhs, llvm::Cmp::Equal, ""))
+        }
+        expression::Operator::NotEqual => {
+            Value::Bool(builder.build_icmp(lhs, rhs, llvm::Cmp::NotEqual, ""))
+        }
+        expression::Operator::Less => {
+            Value::Bool(builder.build_icmp(lhs, rhs, llvm::Cmp::Less, ""))
+        }
+        expression::Operator::Greater => {
+            Value::Bool(builder.build_icmp(lhs, rhs, llvm::Cmp::Greater, ""))
+        }
+        expression::Operator::LessOrEqual => {
+            Value::Bool(builder.build_icmp(lhs, rhs, llvm::Cmp::LessOrEqual, ""))
+        }
+        expression::Operator::GreaterOrEqual => {
+            Value::Bool(builder.build_icmp(lhs, rhs, llvm::Cmp::GreaterOrEqual, ""))
+        }
+        expression::Operator::BitAnd => Value::Int(builder.build_and(lhs, rhs, "")),
+        expression::Operator::BitOr => Value::Int(builder.build_or(lhs, rhs, "")),
+        expression::Operator::BitXor => Value::Int(builder.build_xor(lhs, rhs, "")),
+        expression::Operator::ShiftLeft | expression::Operator::ShiftRight => {
+            // shifting by the bit width or more is undefined in llvm, so the
+            // amount wraps like it does on x86
+            let mask = compiler.context().const_i64(63);
+            let rhs = builder.build_and(rhs, mask, "");
+            match operator {
+                expression::Operator::ShiftLeft => Value::Int(builder.build_shl(lhs, rhs, "")),
+                _ => Value::Int(builder.build_ashr(lhs, rhs, "")),
+            }
+        }
+        expression::Operator::And
+        | expression::Operator::Or
+        | expression::Operator::Not
+        | expression::Operator::BitNot => {
+            unreachable!()
+        }
+    };
+    Ok(val)
+}
+
+fn compile_numeric_binary<T: LLVMCompiler>(
+    compiler: &mut T,
+    operator: &expression::Operator,
+    lhs: llvm::Value,
+    rhs: llvm::Value,
+) -> CompilerResult<Value> {
+    match operator {
         expression::Operator::Plus => {
             Ok(Value::Numeric(compiler.builder().build_fadd(lhs, rhs, "")))
         }
@@ -81,6 +344,17 @@ fn compile_binary<T: LLVMCompiler>(
             crate::llvm::Cmp::GreaterOrEqual,
             "",
         ))),
+        expression::Operator::And
+        | expression::Operator::Or
+        | expression::Operator::Not
+        | expression::Operator::BitAnd
+        | expression::Operator::BitOr
+        | expression::Operator::BitXor
+        | expression::Operator::BitNot
+        | expression::Operator::ShiftLeft
+        | expression::Operator::ShiftRight => {
+            unreachable!()
+        }
     }
 }
 
@@ -91,6 +365,7 @@ impl BinaryVisitor<CompilerResult<Value>> for Compiler {
 }
 
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod test {
     use mockall::{mock, predicate::*};
 
@@ -119,6 +394,9 @@ mod test {
         compiler.expect_builder().return_const(builder);
 
         let const_double = Value::Numeric(compiler.context().const_double(3.0));
+        compiler
+            .expect_walk_expecting()
+            .return_const_st(Ok(const_double.clone()));
         compiler.expect_walk().return_const_st(Ok(const_double));
 
         let val: Value;
@@ -159,6 +437,46 @@ mod test {
         Ok((module.to_string(), val))
     }
 
+    fn test_logical_operation(
+        operator: expression::Operator,
+    ) -> Result<(String, Value), CompilerError> {
+        let context = Context::new();
+        let module = context.create_module("main");
+        let builder = context.create_builder();
+        let mut compiler = MockCompiler::new();
+        compiler.expect_context().return_const(context);
+        compiler.expect_builder().return_const(builder);
+        compiler.expect_maybe_orphaned_len().return_const(0usize);
+        compiler
+            .expect_release_maybe_orphaned_since()
+            .with(eq(0))
+            .return_const(());
+
+        let const_bool = Value::Bool(compiler.context().const_bool(true));
+        compiler.expect_walk().return_const_st(Ok(const_bool));
+
+        let val: Value;
+        in_main_function!(compiler.context(), module, compiler.builder(), {
+            val = compile_binary(
+                &mut compiler,
+                &expression::Binary {
+                    left: Box::new(Node {
+                        expression: expression::Expression::Bool(true),
+                        span: Default::default(),
+                    }),
+                    operator,
+                    right: Box::new(Node {
+                        expression: expression::Expression::Bool(true),
+                        span: Default::default(),
+                    }),
+                },
+                Span::default(),
+            )?;
+        });
+
+        Ok((module.to_string(), val))
+    }
+
     #[test]
     fn test_addition() -> Result<(), CompilerError> {
         let (ir, val) = test_binary_operation(expression::Operator::Plus)?;
@@ -356,4 +674,50 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_and() -> Result<(), CompilerError> {
+        let (ir, val) = test_logical_operation(expression::Operator::And)?;
+        assert!(matches!(val, Value::Bool(_)));
+        assert_eq_ir!(
+            ir,
+            r#"
+
+            define void @main() {
+              br i1 true, label %logicrhs, label %afterlogic
+
+            logicrhs:                                         ; preds = %0
+              br label %afterlogic
+
+            afterlogic:                                       ; preds = %logicrhs, %0
+              %1 = phi i1 [ true, %0 ], [ true, %logicrhs ]
+              ret void
+            }
+            "#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_or() -> Result<(), CompilerError> {
+        let (ir, val) = test_logical_operation(expression::Operator::Or)?;
+        assert!(matches!(val, Value::Bool(_)));
+        assert_eq_ir!(
+            ir,
+            r#"
+
+            define void @main() {
+              br i1 true, label %afterlogic, label %logicrhs
+
+            logicrhs:                                         ; preds = %0
+              br label %afterlogic
+
+            afterlogic:                                       ; preds = %logicrhs, %0
+              %1 = phi i1 [ true, %0 ], [ true, %logicrhs ]
+              ret void
+            }
+            "#
+        );
+        Ok(())
+    }
 }
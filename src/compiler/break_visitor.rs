@@ -1,9 +1,10 @@
+use crate::parser::Span;
 use crate::visitor::BreakVisitor;
 
 use super::{Compiler, CompilerResult, Value};
 
 impl BreakVisitor<CompilerResult<Value>> for Compiler {
-    fn visit_break(&mut self) -> CompilerResult<Value> {
-        Ok(Value::Break)
+    fn visit_break(&mut self, label: &Option<String>, span: Span) -> CompilerResult<Value> {
+        Ok(Value::Break(self.resolve_loop(label, span)?))
     }
 }
@@ -0,0 +1,418 @@
+use crate::expression::{Expression, FuncDecl, Iterable, Node, Pattern};
+use crate::llvm;
+use crate::parser;
+use crate::visitor::IdentifierVisitor;
+
+use super::{utils::get_llvm_function_type, variable::Variable, Compiler, LLVMCompiler, Value};
+
+/// Collects the identifiers `nodes` read before assigning them into `names`.
+/// Names in `bound` are locals of the body, which shadow outer variables.
+fn collect_identifiers(nodes: &[Node], names: &mut Vec<String>, bound: &mut Vec<String>) {
+    for node in nodes {
+        collect_node_identifiers(node, names, bound);
+    }
+}
+
+/// Locals of a nested block, which go out of scope at its end.
+fn block_bound(bound: &[String], locals: &[String]) -> Vec<String> {
+    bound.iter().chain(locals).cloned().collect()
+}
+
+fn collect_node_identifiers(node: &Node, names: &mut Vec<String>, bound: &mut Vec<String>) {
+    match &node.expression {
+        Expression::Identifier(name) => {
+            if !names.contains(name) && !bound.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        Expression::Return(Some(expr)) => collect_node_identifiers(expr, names, bound),
+        Expression::Conditional(expr) => {
+            collect_node_identifiers(&expr.predicate, names, bound);
+            collect_identifiers(&expr.body, names, &mut block_bound(bound, &[]));
+            collect_identifiers(&expr.else_body, names, &mut block_bound(bound, &[]));
+        }
+        Expression::Assignment(expr) => {
+            collect_node_identifiers(&expr.right, names, bound);
+            match &expr.left.expression {
+                // assigning a name that wasn't read yet declares a local
+                Expression::Identifier(name) => {
+                    if !names.contains(name) && !bound.contains(name) {
+                        bound.push(name.clone());
+                    }
+                }
+                _ => collect_node_identifiers(&expr.left, names, bound),
+            }
+        }
+        Expression::Binary(expr) => {
+            collect_node_identifiers(&expr.left, names, bound);
+            collect_node_identifiers(&expr.right, names, bound);
+        }
+        Expression::While(expr) => {
+            collect_node_identifiers(&expr.predicate, names, bound);
+            collect_identifiers(&expr.body, names, &mut block_bound(bound, &[]));
+        }
+        Expression::For(expr) => {
+            match &expr.iterable {
+                Iterable::Range { start, end } => {
+                    collect_node_identifiers(start, names, bound);
+                    collect_node_identifiers(end, names, bound);
+                }
+                Iterable::Collection(expr) => collect_node_identifiers(expr, names, bound),
+            }
+            let mut bound = block_bound(bound, std::slice::from_ref(&expr.variable));
+            collect_identifiers(&expr.body, names, &mut bound);
+        }
+        Expression::Match(expr) => {
+            collect_node_identifiers(&expr.subject, names, bound);
+            for arm in &expr.arms {
+                let mut arm_bound = match &arm.pattern {
+                    Pattern::Literal(expr) => {
+                        collect_node_identifiers(expr, names, bound);
+                        block_bound(bound, &[])
+                    }
+                    Pattern::Range { start, end } => {
+                        collect_node_identifiers(start, names, bound);
+                        collect_node_identifiers(end, names, bound);
+                        block_bound(bound, &[])
+                    }
+                    Pattern::Variant { bindings, .. } => block_bound(bound, bindings),
+                    Pattern::Wildcard => block_bound(bound, &[]),
+                };
+                if let Some(guard) = &arm.guard {
+                    collect_node_identifiers(guard, names, &mut arm_bound);
+                }
+                collect_identifiers(&arm.body, names, &mut arm_bound);
+            }
+        }
+        Expression::StructLiteral(expr) => {
+            for field in &expr.fields {
+                collect_node_identifiers(&field.value, names, bound);
+            }
+        }
+        Expression::FieldAccess(expr) => collect_node_identifiers(&expr.object, names, bound),
+        Expression::Index(expr) => {
+            collect_node_identifiers(&expr.object, names, bound);
+            collect_node_identifiers(&expr.index, names, bound);
+        }
+        Expression::VecLiteral(expr) => collect_identifiers(&expr.elements, names, bound),
+        Expression::Interpolation(expr) => collect_identifiers(&expr.parts, names, bound),
+        Expression::EnumVariant(expr) => collect_identifiers(&expr.args, names, bound),
+        Expression::Unary(expr) => collect_node_identifiers(&expr.right, names, bound),
+        Expression::Grouping(expr) => collect_node_identifiers(&expr.0, names, bound),
+        Expression::FuncCall(expr) => {
+            collect_node_identifiers(&expr.calee, names, bound);
+            collect_identifiers(&expr.args, names, bound);
+        }
+        Expression::FuncDecl(expr) => {
+            for name in free_identifiers(expr) {
+                if !names.contains(&name) && !bound.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        Expression::Break(_)
+        | Expression::Continue(_)
+        | Expression::Return(None)
+        | Expression::Bool(_)
+        | Expression::String(_)
+        | Expression::Numeric(_)
+        | Expression::Integer(_)
+        | Expression::Load(_)
+        | Expression::Extern(_)
+        | Expression::StructDecl(_)
+        | Expression::EnumDecl(_) => {}
+    }
+}
+
+/// Identifiers the function body reads that are neither its own parameters
+/// nor locals it assigns before reading them.
+pub fn free_identifiers(expr: &FuncDecl) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut bound = expr.params.iter().map(|param| param.name.clone()).collect();
+    collect_identifiers(&expr.body, &mut names, &mut bound);
+    names
+}
+
+fn is_closure(env: &Option<llvm::Value>) -> bool {
+    matches!(env, Some(env) if !env.is_null())
+}
+
+impl Compiler {
+    fn capture(&mut self, name: &str) -> Option<Value> {
+        match self.get_var(name) {
+            Some(Variable::Function {
+                val,
+                typ,
+                return_type,
+                params,
+                env,
+            }) => is_closure(&env).then_some(Value::Function {
+                val,
+                typ,
+                return_type,
+                params,
+                env,
+            }),
+            Some(_) => self.visit_identifier(name).ok(),
+            None => match self.get_param(name)? {
+                Value::Function { env, .. } if !is_closure(&env) => None,
+                val => Some(val),
+            },
+        }
+    }
+
+    /// Copies the current values of outer variables referenced by `expr` into a
+    /// new environment, returning it along with the captured names. Captures
+    /// are by value: later assignments to the outer variables aren't seen by
+    /// the closure, nor are the closure's own assignments seen outside of it.
+    pub(super) fn build_env(
+        &mut self,
+        fun: llvm::Function,
+        expr: &FuncDecl,
+    ) -> (llvm::Value, Vec<(String, Value)>) {
+        let captures: Vec<(String, Value)> = free_identifiers(expr)
+            .into_iter()
+            .filter_map(|name| self.capture(&name).map(|val| (name, val)))
+            .collect();
+
+        if captures.is_empty() {
+            let env = self
+                .context
+                .const_null(self.context.void_type().pointer_type(0));
+            return (env, captures);
+        }
+
+        let env = self.build_env_new(fun);
+
+        for (i, (_, val)) in captures.iter().enumerate() {
+            let (setter, val) = match val {
+                Value::Numeric(v) => ("env_set_numeric", *v),
+                Value::Int(v) => ("env_set_int", *v),
+                Value::Bool(v) => ("env_set_bool", *v),
+                Value::String(v) => ("env_set_string", *v),
+                Value::Vec { val: v, .. } => ("env_set_vec", *v),
+                Value::Struct { val, .. } | Value::Enum { val, .. } => ("env_set_struct", *val),
+                Value::Ptr(v) => ("env_set_ptr", *v),
+                Value::Function { env, .. } => ("env_set_env", env.unwrap()),
+                Value::Closure { val, .. } => ("env_set_env", *val),
+                Value::Void | Value::Break(_) | Value::Continue(_) | Value::CString(_) => {
+                    unreachable!()
+                }
+            };
+            let setter = self.module.get_function(setter).unwrap();
+            let idx = self.context.const_double(i as f64);
+            self.builder.build_call(&setter, &[env, idx, val], "");
+        }
+
+        (env, captures)
+    }
+
+    /// Binds captured values from `env` as locals of the current scope.
+    pub(super) fn load_captures(
+        &mut self,
+        env: llvm::Value,
+        captures: &[(String, Value)],
+    ) -> Vec<Variable> {
+        let mut vars = Vec::new();
+        for (i, (name, val)) in captures.iter().enumerate() {
+            let idx = self.context.const_double(i as f64);
+            let getter = match val {
+                Value::Numeric(_) => "env_get_numeric",
+                Value::Int(_) => "env_get_int",
+                Value::Bool(_) => "env_get_bool",
+                _ => "env_get_ptr",
+            };
+            let getter = self.module.get_function(getter).unwrap();
+            let loaded = self.builder.build_call(&getter, &[env, idx], "");
+
+            let var = match val {
+                Value::Function {
+                    val,
+                    typ,
+                    return_type,
+                    params,
+                    ..
+                } => {
+                    let inc = self.module.get_function("inc_env_reference").unwrap();
+                    self.builder.build_call(&inc, &[loaded], "");
+                    Variable::Function {
+                        val: *val,
+                        typ: *typ,
+                        return_type: return_type.clone(),
+                        params: params.clone(),
+                        env: Some(loaded),
+                    }
+                }
+                val => {
+                    let ptr = self.builder.build_alloca(val.llvm_type(&self.context), "");
+                    self.builder.create_store(loaded, &ptr);
+                    match val {
+                        Value::Numeric(_) => Variable::Numeric(ptr),
+                        Value::Int(_) => Variable::Int(ptr),
+                        Value::Bool(_) => Variable::Bool(ptr),
+                        Value::String(_) => {
+                            let inc = self.module.get_function("inc_string_reference").unwrap();
+                            self.builder.build_call(&inc, &[loaded], "");
+                            Variable::String(ptr)
+                        }
+                        Value::Vec { element, .. } => {
+                            let inc = self.module.get_function("inc_vec_reference").unwrap();
+                            self.builder.build_call(&inc, &[loaded], "");
+                            Variable::Vec {
+                                val: ptr,
+                                element: element.clone(),
+                            }
+                        }
+                        Value::Struct { name, .. } => {
+                            let inc = self.module.get_function("inc_struct_reference").unwrap();
+                            self.builder.build_call(&inc, &[loaded], "");
+                            Variable::Struct {
+                                val: ptr,
+                                name: name.clone(),
+                            }
+                        }
+                        Value::Enum { name, .. } => {
+                            let inc = self.module.get_function("inc_struct_reference").unwrap();
+                            self.builder.build_call(&inc, &[loaded], "");
+                            Variable::Enum {
+                                val: ptr,
+                                name: name.clone(),
+                            }
+                        }
+                        Value::Ptr(_) => Variable::Ptr(ptr),
+                        Value::Closure {
+                            return_type,
+                            params,
+                            ..
+                        } => {
+                            let inc = self.module.get_function("inc_env_reference").unwrap();
+                            self.builder.build_call(&inc, &[loaded], "");
+                            Variable::Closure {
+                                val: ptr,
+                                return_type: return_type.clone(),
+                                params: params.clone(),
+                            }
+                        }
+                        Value::Function { .. }
+                        | Value::Void
+                        | Value::Break(_)
+                        | Value::Continue(_)
+                        | Value::CString(_) => {
+                            unreachable!()
+                        }
+                    }
+                }
+            };
+            self.set_var(name, var.clone());
+            vars.push(var);
+        }
+        vars
+    }
+
+    /// Writes captured locals of the current function back to its environment,
+    /// so that changes are visible in the next call.
+    pub(super) fn store_captures(&self) {
+        let frame = self.frames.last().unwrap();
+        let env = match frame.fun {
+            Some(fun) if !frame.captures.is_empty() => fun.get_param(0),
+            _ => return,
+        };
+
+        for (i, var) in frame.captures.iter().enumerate() {
+            let setter = match var {
+                Variable::Numeric(_) => "env_set_numeric",
+                Variable::Int(_) => "env_set_int",
+                Variable::Bool(_) => "env_set_bool",
+                Variable::String(_) => "env_set_string",
+                Variable::Vec { .. } => "env_set_vec",
+                Variable::Struct { .. } | Variable::Enum { .. } => "env_set_struct",
+                Variable::Ptr(_) => "env_set_ptr",
+                Variable::Closure { .. } => "env_set_env",
+                Variable::Function { .. } => continue,
+            };
+            let setter = self.module.get_function(setter).unwrap();
+            let idx = self.context.const_double(i as f64);
+            let val = self
+                .builder
+                .build_load(&var.llvm_type(&self.context), &var.into(), "");
+            self.builder.build_call(&setter, &[env, idx, val], "");
+        }
+    }
+
+    fn build_env_new(&mut self, fun: llvm::Function) -> llvm::Value {
+        let env_new = self.module.get_function("env_new").unwrap();
+        let fun = self.builder.build_bitcast(
+            &llvm::Value(fun.0),
+            self.context.void_type().pointer_type(0),
+            "",
+        );
+        self.builder.build_call(&env_new, &[fun], "")
+    }
+
+    /// Wraps an external function, so that it can be called with an environment
+    /// like functions declared in rock.
+    fn build_trampoline(
+        &mut self,
+        fun: llvm::Function,
+        params: &[parser::Type],
+        return_type: &parser::Type,
+    ) -> llvm::Function {
+        let llvm_params: Vec<_> = std::iter::once(parser::Type::Ptr)
+            .chain(params.iter().cloned())
+            .collect();
+        let typ = get_llvm_function_type(&self.context, &llvm_params, return_type);
+        let trampoline = self.module.add_function("", typ);
+
+        let curr = self.builder.get_insert_block();
+        let block = self.context.append_basic_block(&trampoline, "entry");
+        self.builder.position_builder_at_end(&block);
+
+        let args: Vec<_> = (1..=params.len())
+            .map(|i| trampoline.get_param(i.try_into().unwrap()))
+            .collect();
+        let ret = self.builder.build_call(&fun, &args, "");
+        match return_type {
+            parser::Type::Void => self.builder.build_ret_void(),
+            _ => self.builder.build_ret(ret),
+        };
+
+        self.builder.position_builder_at_end(&curr);
+        trampoline
+    }
+
+    /// Turns a function into a value that can be passed around at runtime.
+    pub(super) fn make_closure(&mut self, val: Value) -> Value {
+        match val {
+            Value::Function {
+                val,
+                return_type,
+                params,
+                env,
+                ..
+            } => {
+                let env = match env {
+                    Some(env) if !env.is_null() => env,
+                    _ => {
+                        let fun = match env {
+                            Some(_) => val,
+                            None => self.build_trampoline(val, &params, &return_type),
+                        };
+                        let env = self.build_env_new(fun);
+                        self.track_maybe_orphaned(Value::Closure {
+                            val: env,
+                            return_type: return_type.clone(),
+                            params: params.clone(),
+                        });
+                        env
+                    }
+                };
+                Value::Closure {
+                    val: env,
+                    return_type,
+                    params,
+                }
+            }
+            val => val,
+        }
+    }
+}
@@ -1,25 +1,247 @@
 use crate::{
-    expression,
+    expression, llvm,
     parser::{self, Span},
     visitor::ConditionalVisitor,
 };
 
-use super::{Compiler, CompilerError, CompilerResult, LLVMCompiler, Value};
+use super::{
+    return_visitor::always_returns, variable::Variable, Compiler, CompilerError, CompilerResult,
+    LLVMCompiler, Value,
+};
+
+/// Suffix of the stdlib functions counting references of a heap value.
+pub(super) fn reference_kind(val: &Value) -> Option<&'static str> {
+    match val {
+        Value::String(_) => Some("string"),
+        Value::Vec { .. } => Some("vec"),
+        Value::Closure { .. } => Some("env"),
+        Value::Struct { .. } | Value::Enum { .. } => Some("struct"),
+        _ => None,
+    }
+}
+
+pub(super) fn build_reference_call<T: LLVMCompiler>(compiler: &T, action: &str, val: &Value) {
+    if let Some(kind) = reference_kind(val) {
+        let fun = compiler
+            .module()
+            .get_function(&format!("{}_{}_reference", action, kind))
+            .unwrap();
+        compiler.builder().build_call(&fun, &[val.into()], "");
+    }
+}
+
+/// Binds `val` to a new variable of the current scope, holding a reference to
+/// heap values.
+pub(super) fn bind_value<T: LLVMCompiler>(compiler: &mut T, name: &str, val: Value) {
+    let ptr = compiler
+        .builder()
+        .build_alloca(val.llvm_type(compiler.context()), "");
+    store_binding(compiler, name, val, ptr);
+}
+
+/// Stores `val` in the variable `name` allocated at `ptr`, holding a reference
+/// to heap values.
+pub(super) fn store_binding<T: LLVMCompiler>(
+    compiler: &mut T,
+    name: &str,
+    val: Value,
+    ptr: llvm::Value,
+) {
+    compiler.builder().create_store((&val).into(), &ptr);
+    build_reference_call(compiler, "inc", &val);
+
+    let var = match val {
+        Value::Numeric(_) => Variable::Numeric(ptr),
+        Value::Int(_) => Variable::Int(ptr),
+        Value::Bool(_) => Variable::Bool(ptr),
+        Value::String(_) => Variable::String(ptr),
+        Value::Vec { element, .. } => Variable::Vec { val: ptr, element },
+        Value::Ptr(_) => Variable::Ptr(ptr),
+        Value::Struct { name, .. } => Variable::Struct { val: ptr, name },
+        Value::Enum { name, .. } => Variable::Enum { val: ptr, name },
+        Value::Closure {
+            return_type,
+            params,
+            ..
+        } => Variable::Closure {
+            val: ptr,
+            return_type,
+            params,
+        },
+        Value::CString(_) => todo!(),
+        Value::Void | Value::Function { .. } | Value::Break(_) | Value::Continue(_) => {
+            unreachable!()
+        }
+    };
+    compiler.set_var(name, var);
+}
+
+/// Compiles the body of a branch in its own scope, with `bindings` as its
+/// first variables, and leaves it towards `after_block`. Unless the branch
+/// leaves with `break`, `continue` or `return`, returns the value of its last
+/// statement, compiled as a value of `expected` when given, with the block it
+/// flows from. A reference is held to heap values, which flow from a block
+/// left open for `merge_branches` to finish.
+pub(super) fn compile_branch<T: LLVMCompiler>(
+    compiler: &mut T,
+    body: &[expression::Node],
+    bindings: Vec<(String, Value)>,
+    after_block: &llvm::BasicBlock,
+    expected: Option<&parser::Type>,
+) -> CompilerResult<Option<(Value, llvm::BasicBlock)>> {
+    compiler.enter_scope();
+    for (name, val) in bindings {
+        bind_value(compiler, &name, val);
+    }
+
+    let mut last = Value::Void;
+    for (i, stmt) in body.iter().enumerate() {
+        compiler.release_maybe_orphaned();
+        let val = match expected {
+            Some(typ) if i + 1 == body.len() => compiler.walk_expecting(stmt, typ)?,
+            _ => compiler.walk(stmt)?,
+        };
+        match val {
+            jump @ (Value::Break(_) | Value::Continue(_)) => {
+                compiler.exit_scope().unwrap();
+                let block = compiler.build_loop_exit(&jump)?;
+                compiler.builder().create_br(&block);
+                return Ok(None);
+            }
+            _ if always_returns(std::slice::from_ref(stmt)) => {
+                // the rest of the branch is never reached
+                compiler.exit_scope()?;
+                compiler.builder().build_unreachable();
+                return Ok(None);
+            }
+            val => last = val,
+        }
+    }
+
+    let last = match last {
+        val @ Value::Function { .. } => compiler.build_closure(val),
+        val => val,
+    };
+    build_reference_call(compiler, "inc", &last);
+    compiler.exit_scope().unwrap();
+
+    let block = match reference_kind(&last) {
+        Some(_) => {
+            let fun = compiler.builder().get_insert_block().get_parent();
+            let end_block = compiler.context().append_basic_block(&fun, "branchend");
+            compiler.builder().create_br(&end_block);
+            end_block
+        }
+        None => {
+            let block = compiler.builder().get_insert_block();
+            compiler.builder().create_br(after_block);
+            block
+        }
+    };
+    Ok(Some((last, block)))
+}
+
+/// Joins the values of the branches reaching `after_block`. They only make a
+/// value when the branches are `exhaustive` and none of them is void, in which
+/// case their types have to agree.
+pub(super) fn merge_branches<T: LLVMCompiler>(
+    compiler: &mut T,
+    branches: Vec<(Value, llvm::BasicBlock)>,
+    exhaustive: bool,
+    after_block: &llvm::BasicBlock,
+    span: Span,
+) -> CompilerResult<Value> {
+    let values: Vec<_> = branches
+        .iter()
+        .map(|(val, _)| val)
+        .filter(|val| !matches!(val, Value::Void))
+        .collect();
+    if let Some(val) = values
+        .iter()
+        .find(|val| val.get_type() != values[0].get_type())
+    {
+        Err(CompilerError::TypeError {
+            expected: values[0].get_type(),
+            actual: val.get_type(),
+            span,
+        })?
+    }
+
+    let produces_value = exhaustive && !branches.is_empty() && values.len() == branches.len();
+
+    for (val, block) in &branches {
+        if reference_kind(val).is_some() {
+            compiler.builder().position_builder_at_end(block);
+            if !produces_value {
+                build_reference_call(compiler, "release", val);
+            }
+            compiler.builder().create_br(after_block);
+        }
+    }
+    compiler.builder().position_builder_at_end(after_block);
+
+    if !produces_value {
+        return Ok(Value::Void);
+    }
+
+    let (val, _) = &branches[0];
+    let phi = compiler
+        .builder()
+        .build_phi(val.llvm_type(compiler.context()), "");
+    let incoming: Vec<_> = branches
+        .iter()
+        .map(|(val, block)| (val.into(), *block))
+        .collect();
+    phi.add_incoming(&incoming);
+
+    let val = match val.clone() {
+        Value::Numeric(_) => Value::Numeric(phi),
+        Value::Int(_) => Value::Int(phi),
+        Value::Bool(_) => Value::Bool(phi),
+        Value::String(_) => Value::String(phi),
+        Value::Vec { element, .. } => Value::Vec { val: phi, element },
+        Value::Struct { name, .. } => Value::Struct { val: phi, name },
+        Value::Enum { name, .. } => Value::Enum { val: phi, name },
+        Value::Ptr(_) => Value::Ptr(phi),
+        Value::CString(_) => Value::CString(phi),
+        Value::Closure {
+            return_type,
+            params,
+            ..
+        } => Value::Closure {
+            val: phi,
+            return_type,
+            params,
+        },
+        Value::Void | Value::Function { .. } | Value::Break(_) | Value::Continue(_) => {
+            unreachable!()
+        }
+    };
+    if reference_kind(&val).is_some() {
+        compiler.track_maybe_orphaned(val.clone());
+    }
+
+    Ok(val)
+}
 
-fn compile_conditional<T: LLVMCompiler>(
+pub(super) fn compile_conditional<T: LLVMCompiler>(
     compiler: &mut T,
     expr: &expression::Conditional,
     span: Span,
+    expected: Option<&parser::Type>,
 ) -> CompilerResult<Value> {
     let predicate = match compiler.walk(&expr.predicate)? {
         Value::Bool(b) => b,
         expr => Err(CompilerError::TypeError {
             expected: parser::Type::Bool,
             actual: expr.get_type(),
-            span,
+            span: span.clone(),
         })?,
     };
 
+    // temporaries of the enclosing expression must outlive both branches
+    compiler.suspend_maybe_orphaned();
+
     let fun = compiler.builder().get_insert_block().get_parent();
 
     let then_block = compiler.context().append_basic_block(&fun, "then");
@@ -31,48 +253,27 @@ fn compile_conditional<T: LLVMCompiler>(
         .build_cond_br(&predicate, &then_block, &else_block);
 
     compiler.builder().position_builder_at_end(&then_block);
-    compiler.enter_scope();
-
-    let mut is_break = false;
-    for stmt in &expr.body {
-        compiler.release_maybe_orphaned();
-        if let Value::Break = compiler.walk(stmt)? {
-            is_break = true;
-            break;
-        }
-    }
-    compiler.exit_scope().unwrap();
-
-    if is_break {
-        compiler
-            .builder()
-            .create_br(compiler.after_loop_blocks().last().unwrap());
-    } else {
-        compiler.builder().create_br(&after_if_block);
-    }
+    let then_branch = compile_branch(compiler, &expr.body, Vec::new(), &after_if_block, expected)?;
 
     compiler.builder().position_builder_at_end(&else_block);
-    compiler.enter_scope();
+    let else_branch = compile_branch(
+        compiler,
+        &expr.else_body,
+        Vec::new(),
+        &after_if_block,
+        expected,
+    )?;
 
-    let mut is_break = false;
-    for stmt in &expr.else_body {
-        compiler.release_maybe_orphaned();
-        if let Value::Break = compiler.walk(stmt)? {
-            is_break = true;
-            break;
-        }
-    }
-    compiler.exit_scope().unwrap();
-    if is_break {
-        compiler
-            .builder()
-            .create_br(compiler.after_loop_blocks().last().unwrap());
-    } else {
-        compiler.builder().create_br(&after_if_block);
-    }
-    compiler.builder().position_builder_at_end(&after_if_block);
+    compiler.resume_maybe_orphaned();
 
-    Ok(Value::Void)
+    let branches = then_branch.into_iter().chain(else_branch).collect();
+    merge_branches(
+        compiler,
+        branches,
+        !expr.else_body.is_empty(),
+        &after_if_block,
+        span,
+    )
 }
 
 impl ConditionalVisitor<CompilerResult<Value>> for Compiler {
@@ -81,6 +282,6 @@ impl ConditionalVisitor<CompilerResult<Value>> for Compiler {
         expr: &crate::expression::Conditional,
         span: Span,
     ) -> CompilerResult<Value> {
-        compile_conditional(self, expr, span)
+        compile_conditional(self, expr, span, None)
     }
 }
@@ -0,0 +1,10 @@
+use crate::parser::Span;
+use crate::visitor::ContinueVisitor;
+
+use super::{Compiler, CompilerResult, Value};
+
+impl ContinueVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_continue(&mut self, label: &Option<String>, span: Span) -> CompilerResult<Value> {
+        Ok(Value::Continue(self.resolve_loop(label, span)?))
+    }
+}
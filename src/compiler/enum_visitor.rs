@@ -0,0 +1,177 @@
+use crate::expression::{self, Pattern, VariantDecl};
+use crate::llvm::{self, Cmp};
+use crate::parser::{self, Span};
+use crate::visitor::{EnumDeclVisitor, EnumVariantVisitor};
+
+use super::{Compiler, CompilerError, CompilerResult, LLVMCompiler, Value};
+
+// Enums are stored like structs, with the tag of the variant in the first slot
+// followed by its payload.
+const TAG_SLOT: usize = 0;
+
+impl Compiler {
+    pub(super) fn enum_variants(&self, name: &str) -> CompilerResult<Vec<VariantDecl>> {
+        self.enums
+            .get(name)
+            .cloned()
+            .ok_or_else(|| CompilerError::UndefinedIdentifier(name.to_string()))
+    }
+
+    /// Tag of `variant` of the enum `name` along with the types of its payload.
+    fn lookup_variant(
+        &self,
+        name: &str,
+        variant: &str,
+        span: Span,
+    ) -> CompilerResult<(usize, Vec<parser::Type>)> {
+        self.enum_variants(name)?
+            .into_iter()
+            .enumerate()
+            .find(|(_, decl)| decl.name == variant)
+            .map(|(tag, decl)| (tag, decl.fields))
+            .ok_or_else(|| CompilerError::UndefinedVariant {
+                typ: parser::Type::Enum(name.to_string()),
+                variant: variant.to_string(),
+                span,
+            })
+    }
+
+    /// Checks that a variant pattern applies to `subject`, returning the
+    /// pointer to the subject, the tag of the variant and its payload types.
+    fn lookup_pattern_variant(
+        &self,
+        subject: &Value,
+        pattern: &Pattern,
+        span: Span,
+    ) -> CompilerResult<(llvm::Value, usize, Vec<parser::Type>)> {
+        let (name, variant) = match pattern {
+            Pattern::Variant { name, variant, .. } => (name, variant),
+            _ => unreachable!(),
+        };
+        match subject {
+            Value::Enum { val, name: typ } if typ == name => {
+                let (tag, fields) = self.lookup_variant(name, variant, span)?;
+                Ok((*val, tag, fields))
+            }
+            _ => Err(CompilerError::TypeError {
+                expected: subject.get_type(),
+                actual: parser::Type::Enum(name.clone()),
+                span,
+            }),
+        }
+    }
+
+    pub(super) fn build_enum_tag(&self, ptr: llvm::Value) -> Value {
+        self.build_slot_load(ptr, TAG_SLOT, parser::Type::Int)
+    }
+
+    /// Tag of the variant matched by `pattern`.
+    pub(super) fn variant_case(
+        &self,
+        subject: &Value,
+        pattern: &Pattern,
+        span: Span,
+    ) -> CompilerResult<i64> {
+        let (_, tag, _) = self.lookup_pattern_variant(subject, pattern, span)?;
+        Ok(tag as i64)
+    }
+
+    pub(super) fn build_variant_test(
+        &self,
+        subject: &Value,
+        pattern: &Pattern,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        let (ptr, tag, _) = self.lookup_pattern_variant(subject, pattern, span)?;
+        let actual = self.build_enum_tag(ptr);
+        let expected = self.context.const_i64(tag as i64);
+        Ok(Value::Bool(self.builder.build_icmp(
+            actual.into(),
+            expected,
+            Cmp::Equal,
+            "",
+        )))
+    }
+
+    /// Loads the payload of the variant matched by `pattern` for the names it
+    /// binds. Must only be called once the variant of `subject` is known.
+    pub(super) fn build_pattern_bindings(
+        &self,
+        subject: &Value,
+        pattern: &Pattern,
+        span: Span,
+    ) -> CompilerResult<Vec<(String, Value)>> {
+        let bindings = match pattern {
+            Pattern::Variant { bindings, .. } => bindings,
+            _ => return Ok(Vec::new()),
+        };
+        let (ptr, _, fields) = self.lookup_pattern_variant(subject, pattern, span.clone())?;
+        if bindings.len() != fields.len() {
+            Err(CompilerError::WrongArgumentCount {
+                expected: fields.len(),
+                actual: bindings.len(),
+                span,
+            })?
+        }
+
+        Ok(bindings
+            .iter()
+            .zip(fields)
+            .enumerate()
+            .filter(|(_, (name, _))| *name != "_")
+            .map(|(idx, (name, typ))| (name.clone(), self.build_slot_load(ptr, idx + 1, typ)))
+            .collect())
+    }
+}
+
+impl EnumDeclVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_enum_decl(&mut self, expr: &expression::EnumDecl) -> CompilerResult<Value> {
+        if expr
+            .variants
+            .iter()
+            .any(|variant| variant.fields.contains(&parser::Type::Void))
+        {
+            Err(CompilerError::VoidAssignment)?
+        }
+        self.enums.insert(expr.name.clone(), expr.variants.clone());
+        Ok(Value::Void)
+    }
+}
+
+impl EnumVariantVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_enum_variant(
+        &mut self,
+        expr: &expression::EnumVariant,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        let (tag, fields) = self.lookup_variant(&expr.name, &expr.variant, span.clone())?;
+        if expr.args.len() != fields.len() {
+            Err(CompilerError::WrongArgumentCount {
+                expected: fields.len(),
+                actual: expr.args.len(),
+                span,
+            })?
+        }
+
+        let mut payload = Vec::new();
+        for (arg, typ) in expr.args.iter().zip(&fields) {
+            let val = self.walk_expecting(arg, typ)?;
+            payload.push(self.slot_value(val, typ, arg.span.clone())?);
+        }
+
+        let struct_new = self.module.get_function("struct_new").unwrap();
+        let ptr = self.builder.build_call(&struct_new, &[], "");
+        let tag = Value::Int(self.context.const_i64(tag as i64));
+        self.build_slot_store(ptr, TAG_SLOT, &tag);
+        for (idx, val) in payload.iter().enumerate() {
+            self.build_slot_store(ptr, idx + 1, val);
+        }
+
+        let val = Value::Enum {
+            val: ptr,
+            name: expr.name.clone(),
+        };
+        self.track_maybe_orphaned(val.clone());
+        Ok(val)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::expression::{self, Iterable, Node};
+use crate::parser::{self, Span};
+use crate::visitor::ForVisitor;
+use crate::{llvm, llvm::Cmp};
+
+use super::{
+    conditional::store_binding,
+    utils::{get_llvm_type, int_literal, typed_value},
+    variable::Variable,
+    Compiler, CompilerError, CompilerResult, Value,
+};
+use crate::compiler::LLVMCompiler;
+use crate::visitor::Visitor;
+
+impl Compiler {
+    fn build_for_condition(
+        &self,
+        counter: &Variable,
+        end: Option<llvm::Value>,
+        vec: Option<llvm::Value>,
+    ) -> llvm::Value {
+        let idx = self
+            .builder
+            .build_load(&counter.llvm_type(&self.context), &counter.into(), "");
+        match (counter, end, vec) {
+            (Variable::Int(_), Some(end), _) => self.builder.build_icmp(idx, end, Cmp::Less, ""),
+            (_, Some(end), _) => self.builder.build_fcmp(idx, end, Cmp::Less, ""),
+            (_, None, Some(vec)) => {
+                let len = self.build_vec_len(vec);
+                self.builder.build_fcmp(idx, len, Cmp::Less, "")
+            }
+            (_, None, None) => unreachable!(),
+        }
+    }
+
+    /// Bounds of a range, where an integer literal takes the type of the other
+    /// bound.
+    fn walk_range(&mut self, start: &Node, end: &Node) -> CompilerResult<(Value, Value)> {
+        if int_literal(start).is_some() && int_literal(end).is_none() {
+            let end = self.walk(end)?;
+            Ok((self.walk_expecting(start, &end.get_type())?, end))
+        } else {
+            let start = self.walk(start)?;
+            let end = self.walk_expecting(end, &start.get_type())?;
+            Ok((start, end))
+        }
+    }
+}
+
+impl ForVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_for(&mut self, expr: &expression::For, span: Span) -> CompilerResult<Value> {
+        let (start, end, vec, element) = match &expr.iterable {
+            Iterable::Range { start, end } => match self.walk_range(start, end)? {
+                (Value::Int(start), Value::Int(end)) => {
+                    (Value::Int(start), Some(end), None, parser::Type::Int)
+                }
+                (start @ Value::Numeric(_), Value::Numeric(end)) => {
+                    (start, Some(end), None, parser::Type::Numeric)
+                }
+                (start @ (Value::Numeric(_) | Value::Int(_)), end) => {
+                    Err(CompilerError::TypeError {
+                        expected: start.get_type(),
+                        actual: end.get_type(),
+                        span,
+                    })?
+                }
+                (start, _) => Err(CompilerError::TypeError {
+                    expected: parser::Type::Numeric,
+                    actual: start.get_type(),
+                    span,
+                })?,
+            },
+            Iterable::Collection(node) => match self.walk(node)? {
+                Value::Vec { val: vec, element } => {
+                    // keep the vector alive while iterating, even if the body
+                    // reassigns the variable it came from
+                    let inc = self.module.get_function("inc_vec_reference").unwrap();
+                    self.builder.build_call(&inc, &[vec], "");
+                    (
+                        Value::Numeric(self.context.const_double(0.0)),
+                        None,
+                        Some(vec),
+                        element,
+                    )
+                }
+                val => Err(CompilerError::TypeError {
+                    expected: parser::Type::Vector(Box::new(parser::Type::Numeric)),
+                    actual: val.get_type(),
+                    span,
+                })?,
+            },
+        };
+
+        // temporaries of the header must not be released on every iteration
+        self.release_maybe_orphaned();
+
+        let typ = start.llvm_type(&self.context);
+        let counter_ptr = self.builder.build_alloca(typ, "");
+        self.builder
+            .create_store(start.clone().into(), &counter_ptr);
+        let var_ptr = self
+            .builder
+            .build_alloca(get_llvm_type(&self.context, &element), "");
+        let counter = match start {
+            Value::Int(_) => Variable::Int(counter_ptr),
+            _ => Variable::Numeric(counter_ptr),
+        };
+
+        let fun = self.builder().get_insert_block().get_parent();
+
+        let loop_block = self.context().append_basic_block(&fun, "loop");
+        let inc_block = self.context().append_basic_block(&fun, "loopinc");
+        let after_loop_block = self.context().append_basic_block(&fun, "afterloop");
+        self.push_loop(&expr.label, inc_block, after_loop_block, vec);
+
+        let predicate = self.build_for_condition(&counter, end, vec);
+        self.builder
+            .build_cond_br(&predicate, &loop_block, &after_loop_block);
+
+        self.builder.position_builder_at_end(&loop_block);
+
+        self.enter_scope();
+
+        let idx = self.builder.build_load(&typ, &counter_ptr, "");
+        let element = match vec {
+            Some(vec) => self.build_vec_get(vec, idx, element),
+            None => typed_value(idx, element),
+        };
+        store_binding(self, &expr.variable, element, var_ptr);
+
+        let mut jump = None;
+        for stmt in &expr.body {
+            self.release_maybe_orphaned();
+            if let val @ (Value::Break(_) | Value::Continue(_)) = self.walk(stmt)? {
+                jump = Some(val);
+                break;
+            }
+        }
+        self.exit_scope().unwrap();
+        let exit = match jump {
+            Some(jump) => self.build_loop_exit(&jump)?,
+            None => inc_block,
+        };
+        self.builder.build_br(&exit);
+
+        self.builder.position_builder_at_end(&inc_block);
+        let idx = self.builder.build_load(&typ, &counter_ptr, "");
+        let next = match counter {
+            Variable::Int(_) => self.builder.build_add(idx, self.context.const_i64(1), ""),
+            _ => self
+                .builder
+                .build_fadd(idx, self.context.const_double(1.0), ""),
+        };
+        self.builder.create_store(next, &counter_ptr);
+        let predicate = self.build_for_condition(&counter, end, vec);
+        self.builder
+            .build_cond_br(&predicate, &loop_block, &after_loop_block);
+
+        self.builder.position_builder_at_end(&after_loop_block);
+        self.pop_loop();
+
+        if let Some(vec) = vec {
+            let release = self.module.get_function("release_vec_reference").unwrap();
+            self.builder.build_call(&release, &[vec], "");
+        }
+
+        Ok(Value::Void)
+    }
+}
@@ -5,22 +5,42 @@ use crate::{
     visitor::FuncCallVisitor,
 };
 
-use super::{variable::Variable, Compiler, CompilerError, CompilerResult, LLVMCompiler, Value};
+use super::{
+    utils::get_llvm_function_type, variable::Variable, vector::VEC_FUNCTIONS, Compiler,
+    CompilerError, CompilerResult, LLVMCompiler, Value,
+};
 
 fn compile_args<T: LLVMCompiler>(
     compiler: &mut T,
     args: &[Node],
+    params: &[parser::Type],
 ) -> CompilerResult<Vec<llvm::Value>> {
     args.iter()
-        .map(|arg| {
-            let val = match compiler.walk(arg)? {
-                Value::Void | Value::Break => Err(CompilerError::VoidAssignment)?,
+        .zip(params)
+        .map(|(arg, param)| {
+            let val = compiler.walk_expecting(arg, param)?;
+
+            if val.get_type() != *param {
+                Err(CompilerError::TypeError {
+                    expected: param.clone(),
+                    actual: val.get_type(),
+                    span: arg.span.clone(),
+                })?
+            }
+
+            let val = match val {
+                Value::Void | Value::Break(_) | Value::Continue(_) => {
+                    Err(CompilerError::VoidAssignment)?
+                }
                 Value::String(n) => n,
                 Value::Numeric(n) => n,
+                Value::Int(n) => n,
                 Value::Bool(n) => n,
-                Value::Vec(n) => n,
+                Value::Vec { val: n, .. } => n,
+                Value::Struct { val, .. } | Value::Enum { val, .. } => val,
                 Value::Ptr(n) => n,
-                Value::Function { val, .. } => llvm::Value(val.0),
+                val @ Value::Function { .. } => compiler.build_closure(val).into(),
+                Value::Closure { val, .. } => val,
                 Value::CString(n) => n,
             };
 
@@ -29,62 +49,162 @@ fn compile_args<T: LLVMCompiler>(
         .collect()
 }
 
+fn build_call<T: LLVMCompiler>(
+    compiler: &mut T,
+    fun: &llvm::Function,
+    args: &[llvm::Value],
+    return_type: parser::Type,
+) -> Value {
+    let llvm_value = compiler.builder().build_call(fun, args, "");
+    match return_type {
+        parser::Type::Numeric => Value::Numeric(llvm_value),
+        parser::Type::Int => Value::Int(llvm_value),
+        parser::Type::Vector(element) => {
+            let value = Value::Vec {
+                val: llvm_value,
+                element: *element,
+            };
+            compiler.track_maybe_orphaned(value.clone());
+            value
+        }
+        parser::Type::Void => Value::Void,
+        parser::Type::Function {
+            params,
+            return_type,
+        } => {
+            let value = Value::Closure {
+                val: llvm_value,
+                return_type: *return_type,
+                params,
+            };
+            compiler.track_maybe_orphaned(value.clone());
+            value
+        }
+        parser::Type::Ptr => Value::Ptr(llvm_value),
+        parser::Type::Bool => Value::Bool(llvm_value),
+        parser::Type::String => {
+            let value = Value::String(llvm_value);
+            compiler.track_maybe_orphaned(value.clone());
+            value
+        }
+        parser::Type::CString => Value::CString(llvm_value),
+        parser::Type::Struct(name) => {
+            let value = Value::Struct {
+                val: llvm_value,
+                name,
+            };
+            compiler.track_maybe_orphaned(value.clone());
+            value
+        }
+        parser::Type::Enum(name) => {
+            let value = Value::Enum {
+                val: llvm_value,
+                name,
+            };
+            compiler.track_maybe_orphaned(value.clone());
+            value
+        }
+    }
+}
+
 fn compile_func_call<T: LLVMCompiler>(
     compiler: &mut T,
     expr: &expression::FuncCall,
     span: Span,
 ) -> CompilerResult<Value> {
-    let name = match expr.calee.expression {
-        expression::Expression::Identifier(ref name) => Ok(name.clone()),
-        _ => unreachable!(),
-    }?;
-
-    let builtin = compiler.get_builtin(&name);
+    if let expression::Expression::Identifier(name) = &expr.calee.expression {
+        if VEC_FUNCTIONS.contains(&name.as_str()) {
+            return compiler.compile_vec_call(name, expr, span);
+        }
+    }
+    if !expr.type_args.is_empty() {
+        Err(CompilerError::WrongTypeArgumentCount {
+            expected: 0,
+            actual: expr.type_args.len(),
+            span: span.clone(),
+        })?
+    }
 
-    let var = match builtin {
-        Some(b) => b,
-        None => compiler
-            .get_var(&name)
-            .ok_or(CompilerError::UndefinedIdentifier(name))?,
+    let calee = match &expr.calee.expression {
+        expression::Expression::Identifier(name) => {
+            match compiler
+                .get_builtin(name)
+                .or_else(|| compiler.get_var(name))
+            {
+                Some(Variable::Function {
+                    val,
+                    typ,
+                    return_type,
+                    params,
+                    env,
+                }) => Value::Function {
+                    val,
+                    typ,
+                    return_type,
+                    params,
+                    env,
+                },
+                _ => compiler.walk(&expr.calee)?,
+            }
+        }
+        _ => compiler.walk(&expr.calee)?,
     };
 
-    let args = compile_args(compiler, &expr.args)?;
-
-    let builder = compiler.builder();
-
-    match var {
-        Variable::Function {
-            return_type, val, ..
-        } => {
-            let llvm_value = builder.build_call(&val, &args, "");
-
-            let val = match return_type {
-                parser::Type::Numeric => Value::Numeric(llvm_value),
-                parser::Type::Vector => {
-                    let value = Value::Vec(llvm_value);
-                    compiler.track_maybe_orphaned(value);
-                    value
-                }
-                parser::Type::Void => Value::Void,
-                parser::Type::Function => todo!(),
-                parser::Type::Ptr => Value::Ptr(llvm_value),
-                parser::Type::Bool => Value::Bool(llvm_value),
-                parser::Type::String => {
-                    let value = Value::String(llvm_value);
-                    compiler.track_maybe_orphaned(value);
-                    value
-                }
-                parser::Type::CString => Value::CString(llvm_value),
-            };
-
-            Ok(val)
+    let (return_type, params) = match &calee {
+        Value::Function {
+            return_type,
+            params,
+            ..
         }
+        | Value::Closure {
+            return_type,
+            params,
+            ..
+        } => (return_type.clone(), params.clone()),
         val => Err(CompilerError::TypeError {
-            expected: parser::Type::Function,
+            expected: parser::Type::Function {
+                params: vec![],
+                return_type: Box::new(parser::Type::Void),
+            },
             actual: val.get_type(),
+            span: span.clone(),
+        })?,
+    };
+
+    if expr.args.len() != params.len() {
+        Err(CompilerError::WrongArgumentCount {
+            expected: params.len(),
+            actual: expr.args.len(),
             span,
-        }),
+        })?
     }
+
+    let mut args = compile_args(compiler, &expr.args, &params)?;
+
+    let fun = match calee {
+        Value::Function { val, env, .. } => {
+            if let Some(env) = env {
+                args.insert(0, env);
+            }
+            val
+        }
+        Value::Closure { val, .. } => {
+            let llvm_params: Vec<_> = std::iter::once(parser::Type::Ptr)
+                .chain(params.iter().cloned())
+                .collect();
+            let typ = get_llvm_function_type(compiler.context(), &llvm_params, &return_type);
+            let env_function = compiler.module().get_function("env_function").unwrap();
+            let ptr = compiler.builder().build_call(&env_function, &[val], "");
+            let fun = compiler
+                .builder()
+                .build_bitcast(&ptr, typ.pointer_type(0), "");
+            args.insert(0, val);
+            llvm::Function(fun.0)
+        }
+        _ => unreachable!(),
+    };
+
+    Ok(build_call(compiler, &fun, &args, return_type))
 }
 
 impl FuncCallVisitor<CompilerResult<Value>> for Compiler {
@@ -98,6 +218,7 @@ impl FuncCallVisitor<CompilerResult<Value>> for Compiler {
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod test {
     use super::*;
     use crate::compiler::{utils::get_llvm_type, MAIN_FUNCTION};
@@ -142,6 +263,8 @@ mod test {
                 return_type: $return_type,
                 typ: fun_type,
                 val: fun,
+                params: $arg_types.to_vec(),
+                env: None,
             };
 
             compiler
@@ -153,9 +276,9 @@ mod test {
             let const_double = Value::Numeric(compiler.context().const_double(3.));
 
             compiler
-                .expect_walk()
-                .returning_st(move |x| match x.expression {
-                    Expression::Numeric(_) => Ok(const_double),
+                .expect_walk_expecting()
+                .returning_st(move |x, _| match x.expression {
+                    Expression::Numeric(_) => Ok(const_double.clone()),
                     _ => todo!(),
                 });
 
@@ -163,6 +286,7 @@ mod test {
             in_main_function!(compiler.context(), compiler.module(), compiler.builder(), {
                 let func_call = FuncCall {
                     calee: boxed_node!(Expression::Identifier("test_fun".to_string())),
+                    type_args: vec![],
                     args: $args,
                 };
                 val = compile_func_call(&mut compiler, &func_call, Span::default())?;
@@ -4,6 +4,6 @@ use super::{Compiler, CompilerResult, Value};
 
 impl GroupingVisitor<CompilerResult<Value>> for Compiler {
     fn visit_grouping(&mut self, expr: &crate::expression::Grouping) -> CompilerResult<Value> {
-        self.walk(&*expr.0)
+        self.walk(&expr.0)
     }
 }
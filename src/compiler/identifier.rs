@@ -4,31 +4,53 @@ use super::{variable::Variable, Compiler, CompilerError, CompilerResult, LLVMCom
 
 impl IdentifierVisitor<CompilerResult<Value>> for Compiler {
     fn visit_identifier(&mut self, expr: &str) -> CompilerResult<Value> {
-        let var = self.get_var(expr);
+        let var = self.get_var(expr).or_else(|| match self.get_builtin(expr) {
+            Some(builtin) if self.get_param(expr).is_none() => {
+                Some(self.builtin_value(expr, builtin))
+            }
+            _ => None,
+        });
 
         let val = match var {
+            Some(Variable::Function {
+                typ,
+                return_type,
+                val,
+                params,
+                env,
+            }) => Some(Value::Function {
+                val,
+                typ,
+                return_type,
+                params,
+                env,
+            }),
             Some(var) => {
-                let val = self
-                    .builder
-                    .build_load(&var.llvm_type(&self.context), &var.into(), "");
+                let val =
+                    self.builder
+                        .build_load(&var.llvm_type(&self.context), &(&var).into(), "");
                 Some(match var {
                     Variable::String(_) => Value::String(val),
                     Variable::Numeric(_) => Value::Numeric(val),
+                    Variable::Int(_) => Value::Int(val),
                     Variable::Bool(_) => Value::Bool(val),
-                    Variable::Function {
-                        typ,
+                    Variable::Closure {
                         return_type,
+                        params,
+                        ..
+                    } => Value::Closure {
                         val,
-                    } => Value::Function {
-                        val,
-                        typ,
                         return_type,
+                        params,
                     },
-                    Variable::Vec(_) => Value::Vec(val),
+                    Variable::Vec { element, .. } => Value::Vec { val, element },
+                    Variable::Struct { name, .. } => Value::Struct { val, name },
+                    Variable::Enum { name, .. } => Value::Enum { val, name },
                     Variable::Ptr(_) => Value::Ptr(val),
+                    Variable::Function { .. } => unreachable!(),
                 })
             }
-            None => self.get_param(expr),
+            None => self.get_param(expr).or_else(|| self.math_constant(expr)),
         }
         .ok_or_else(|| CompilerError::UndefinedIdentifier(expr.to_string()))?;
 
@@ -0,0 +1,191 @@
+use crate::expression::{self, Expression, Node};
+use crate::llvm;
+use crate::parser::{self, Span};
+use crate::visitor::{IndexVisitor, Visitor};
+
+use super::{
+    conditional::{bind_value, build_reference_call},
+    variable::Variable,
+    Compiler, CompilerError, CompilerResult, LLVMCompiler, Value,
+};
+
+/// Where the object of an index assignment is held. Strings are copied when
+/// they are shared, the copy is then stored back there.
+enum Place {
+    Variable(Variable),
+    Param(String, Value),
+    Field {
+        ptr: llvm::Value,
+        idx: usize,
+        typ: parser::Type,
+    },
+    Element {
+        vec: llvm::Value,
+        idx: llvm::Value,
+        element: parser::Type,
+        span: Span,
+    },
+    Temporary(Value),
+}
+
+impl Place {
+    fn get_type(&self) -> parser::Type {
+        match self {
+            Place::Variable(var) => var.get_type(),
+            Place::Param(_, val) | Place::Temporary(val) => val.get_type(),
+            Place::Field { typ, .. } => typ.clone(),
+            Place::Element { element, .. } => element.clone(),
+        }
+    }
+}
+
+impl Compiler {
+    /// Compiles all of `node` but the load of the value it refers to, so that
+    /// the value assigned can't replace it before it is used.
+    fn compile_place(&mut self, node: &Node) -> CompilerResult<Place> {
+        Ok(match &node.expression {
+            Expression::Identifier(name) => match (self.get_var(name), self.get_param(name)) {
+                (Some(var @ Variable::String(_)), _) => Place::Variable(var),
+                (None, Some(val @ Value::String(_))) => Place::Param(name.clone(), val),
+                _ => Place::Temporary(self.walk(node)?),
+            },
+            Expression::FieldAccess(expr) => {
+                let object = self.walk(&expr.object)?;
+                let (ptr, idx, typ) = self.lookup_field(&object, &expr.field, node.span.clone())?;
+                Place::Field { ptr, idx, typ }
+            }
+            Expression::Index(expr) => {
+                let object = self.walk(&expr.object)?;
+                let idx = self.index_arg(&expr.index)?;
+                match object {
+                    Value::Vec { val, element } => Place::Element {
+                        vec: val,
+                        idx,
+                        element,
+                        span: expr.index.span.clone(),
+                    },
+                    object => Place::Temporary(self.build_index(object, idx, expr)?),
+                }
+            }
+            _ => Place::Temporary(self.walk(node)?),
+        })
+    }
+
+    /// Loads the value held at `place`, without taking a reference to it.
+    fn load_place(&mut self, place: &Place) -> Value {
+        match place {
+            Place::Variable(var) => Value::String(self.builder.build_load(
+                &var.llvm_type(&self.context),
+                &var.into(),
+                "",
+            )),
+            Place::Param(_, val) | Place::Temporary(val) => val.clone(),
+            Place::Field { ptr, idx, typ } => self.build_slot_load(*ptr, *idx, typ.clone()),
+            Place::Element {
+                vec,
+                idx,
+                element,
+                span,
+            } => {
+                self.build_index_check("vec_check_get", *vec, *idx, span);
+                self.build_vec_get(*vec, *idx, element.clone())
+            }
+        }
+    }
+
+    /// Stores `val` at `place`, in place of the value held there.
+    fn store_place(&mut self, place: Place, val: &Value) {
+        match place {
+            Place::Variable(var) => {
+                let old = self.load_place(&Place::Variable(var.clone()));
+                self.builder.create_store(val.into(), &(&var).into());
+                build_reference_call(self, "inc", val);
+                build_reference_call(self, "release", &old);
+            }
+            // parameters are shadowed by a variable, like when assigned to
+            Place::Param(name, _) => bind_value(self, &name, val.clone()),
+            Place::Field { ptr, idx, .. } => self.build_slot_store(ptr, idx, val),
+            Place::Element { vec, idx, .. } => self.build_vec_set(vec, idx, val),
+            Place::Temporary(_) => {}
+        }
+    }
+
+    pub(super) fn build_index_assignment(
+        &mut self,
+        expr: &expression::Index,
+        right: &Node,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        let place = self.compile_place(&expr.object)?;
+        let idx = self.index_arg(&expr.index)?;
+        let value = match place.get_type() {
+            parser::Type::Vector(element) => self.assigned_value(right, &element)?,
+            _ => self.assigned_value(right, &parser::Type::String)?,
+        };
+        match self.load_place(&place) {
+            Value::Vec { val: vec, element } => {
+                let val = self.slot_value(value.clone(), &element, span)?;
+                self.build_index_check("vec_check_set", vec, idx, &expr.index.span);
+                self.build_vec_set(vec, idx, &val);
+            }
+            // a string holding the change, which is a copy unless the string
+            // is only held at `place`
+            Value::String(string) => {
+                let val = self.slot_value(value.clone(), &parser::Type::String, span)?;
+                self.build_index_check("string_check_set", string, idx, &expr.index.span);
+                let string_set = self.module.get_function("string_set").unwrap();
+                let line = self.context.const_i64(right.span.line.into());
+                let column = self.context.const_i64(right.span.column.into());
+                let string = Value::String(self.builder.build_call(
+                    &string_set,
+                    &[string, idx, val.into(), line, column],
+                    "",
+                ));
+                self.track_maybe_orphaned(string.clone());
+                self.store_place(place, &string);
+            }
+            object => Err(CompilerError::TypeError {
+                expected: parser::Type::Vector(Box::new(parser::Type::Numeric)),
+                actual: object.get_type(),
+                span: expr.object.span.clone(),
+            })?,
+        }
+        Ok(value)
+    }
+
+    /// Element or character at `idx` of `object`.
+    fn build_index(
+        &mut self,
+        object: Value,
+        idx: llvm::Value,
+        expr: &expression::Index,
+    ) -> CompilerResult<Value> {
+        match object {
+            Value::Vec { val, element } => {
+                self.build_index_check("vec_check_get", val, idx, &expr.index.span);
+                Ok(self.build_vec_element(val, idx, element))
+            }
+            // a new string holding the character
+            Value::String(string) => {
+                self.build_index_check("string_check_get", string, idx, &expr.index.span);
+                let string_get = self.module.get_function("string_get").unwrap();
+                let val = Value::String(self.builder.build_call(&string_get, &[string, idx], ""));
+                self.track_maybe_orphaned(val.clone());
+                Ok(val)
+            }
+            object => Err(CompilerError::TypeError {
+                expected: parser::Type::Vector(Box::new(parser::Type::Numeric)),
+                actual: object.get_type(),
+                span: expr.object.span.clone(),
+            }),
+        }
+    }
+}
+
+impl IndexVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_index(&mut self, expr: &expression::Index, _span: Span) -> CompilerResult<Value> {
+        let object = self.walk(&expr.object)?;
+        let idx = self.index_arg(&expr.index)?;
+        self.build_index(object, idx, expr)
+    }
+}
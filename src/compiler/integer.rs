@@ -0,0 +1,11 @@
+use crate::visitor::IntegerVisitor;
+
+use super::{Compiler, CompilerResult, Value};
+
+impl IntegerVisitor<CompilerResult<Value>> for Compiler {
+    /// Integer literals are numbers, unless they are compiled where an int is
+    /// expected by `walk_expecting`.
+    fn visit_integer(&mut self, expr: &i64) -> CompilerResult<Value> {
+        Ok(Value::Numeric(self.context.const_double(*expr as f64)))
+    }
+}
@@ -0,0 +1,49 @@
+use crate::expression;
+use crate::parser::{self, Span};
+use crate::visitor::{InterpolationVisitor, Visitor};
+
+use super::{Compiler, CompilerError, CompilerResult, LLVMCompiler, Value};
+
+impl Compiler {
+    /// Converts `val` to a string, for the types that have a textual form.
+    pub(super) fn build_to_string(&mut self, val: Value, span: Span) -> CompilerResult<Value> {
+        let conversion = match &val {
+            Value::String(_) => return Ok(val),
+            Value::Numeric(_) => "string",
+            Value::Int(_) => "string_from_int",
+            Value::Bool(_) => "string_from_bool",
+            Value::CString(_) => "string_from_c_string",
+            val => Err(CompilerError::TypeError {
+                expected: parser::Type::String,
+                actual: val.get_type(),
+                span,
+            })?,
+        };
+        let conversion = self.module.get_function(conversion).unwrap();
+        let string = Value::String(self.builder.build_call(&conversion, &[val.into()], ""));
+        self.track_maybe_orphaned(string.clone());
+        Ok(string)
+    }
+}
+
+impl InterpolationVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_interpolation(
+        &mut self,
+        expr: &expression::Interpolation,
+        _span: Span,
+    ) -> CompilerResult<Value> {
+        let string_new = self.module.get_function("string_new").unwrap();
+        let string = Value::String(self.builder.build_call(&string_new, &[], ""));
+        self.track_maybe_orphaned(string.clone());
+
+        let string_append = self.module.get_function("string_append").unwrap();
+        for part in &expr.parts {
+            let val = self.walk(part)?;
+            let val = self.build_to_string(val, part.span.clone())?;
+            self.builder
+                .build_call(&string_append, &[(&string).into(), val.into()], "");
+        }
+
+        Ok(string)
+    }
+}
@@ -0,0 +1,314 @@
+use crate::expression::{self, Expression, MatchArm, Node, Operator, Pattern};
+use crate::parser::{self, Span};
+use crate::visitor::MatchVisitor;
+use crate::{llvm, visitor::Visitor};
+
+use super::{
+    binary::compile_binary_values,
+    conditional::{bind_value, compile_branch, merge_branches},
+    utils::int_literal,
+    Compiler, CompilerError, CompilerResult, LLVMCompiler, Value,
+};
+
+/// Value of a literal pattern usable as a case of a `switch`.
+fn case_value(node: &Node) -> Option<i64> {
+    match &node.expression {
+        Expression::Bool(b) => Some(*b as i64),
+        _ => int_literal(node),
+    }
+}
+
+fn matches_anything(arm: &MatchArm) -> bool {
+    matches!(arm.pattern, Pattern::Wildcard) && arm.guard.is_none()
+}
+
+/// Arms up to the first one matching any value, the rest are never taken.
+fn reachable_arms(arms: &[MatchArm]) -> &[MatchArm] {
+    match arms.iter().position(matches_anything) {
+        Some(idx) => &arms[..=idx],
+        None => arms,
+    }
+}
+
+fn covers_variant(arms: &[MatchArm], name: &str) -> bool {
+    arms.iter().any(|arm| {
+        arm.guard.is_none()
+            && matches!(&arm.pattern, Pattern::Variant { variant, .. } if variant == name)
+    })
+}
+
+fn covers_bool(arms: &[MatchArm], val: bool) -> bool {
+    arms.iter().any(|arm| {
+        matches_anything(arm)
+            || arm.guard.is_none()
+                && matches!(&arm.pattern, Pattern::Literal(node)
+                    if matches!(node.expression, Expression::Bool(b) if b == val))
+    })
+}
+
+impl Compiler {
+    fn build_pattern_test(
+        &mut self,
+        subject: &Value,
+        pattern: &Pattern,
+        span: Span,
+    ) -> CompilerResult<Option<llvm::Value>> {
+        let test = match pattern {
+            Pattern::Wildcard => return Ok(None),
+            Pattern::Literal(node) => {
+                let val = self.walk_expecting(node, &subject.get_type())?;
+                compile_binary_values(self, &Operator::Equal, subject.clone(), val, span)?
+            }
+            Pattern::Range { start, end } => {
+                let start = self.walk_expecting(start, &subject.get_type())?;
+                let lower = compile_binary_values(
+                    self,
+                    &Operator::GreaterOrEqual,
+                    subject.clone(),
+                    start,
+                    span.clone(),
+                )?;
+                let end = self.walk_expecting(end, &subject.get_type())?;
+                let upper =
+                    compile_binary_values(self, &Operator::Less, subject.clone(), end, span)?;
+                Value::Bool(self.builder.build_and(lower.into(), upper.into(), ""))
+            }
+            Pattern::Variant { .. } => self.build_variant_test(subject, pattern, span)?,
+        };
+        Ok(Some(test.into()))
+    }
+
+    /// Dispatches on integer-like subjects or the tag of enums with a single
+    /// `switch`, when every pattern is a literal, a variant or a wildcard
+    /// without a guard.
+    fn build_match_switch(
+        &mut self,
+        subject: &Value,
+        arms: &[MatchArm],
+        exhaustive: bool,
+        after_block: &llvm::BasicBlock,
+        expected: Option<&parser::Type>,
+        span: Span,
+    ) -> CompilerResult<Vec<(Value, llvm::BasicBlock)>> {
+        let fun = self.builder.get_insert_block().get_parent();
+        let default_block = self.context.append_basic_block(&fun, "matchdefault");
+        let operand = match subject {
+            Value::Enum { val, .. } => self.build_enum_tag(*val),
+            subject => subject.clone(),
+        };
+        let switch = self.builder.build_switch(
+            &operand.into(),
+            &default_block,
+            arms.len().try_into().unwrap(),
+        );
+
+        let mut cases = Vec::new();
+        let mut branches = Vec::new();
+        for arm in arms {
+            let block = match &arm.pattern {
+                Pattern::Literal(node) => {
+                    let case = case_value(node).unwrap();
+                    if cases.contains(&case) {
+                        continue;
+                    }
+                    cases.push(case);
+
+                    let val = self.walk_expecting(node, &subject.get_type())?;
+                    if val.get_type() != subject.get_type() {
+                        Err(CompilerError::TypeError {
+                            expected: subject.get_type(),
+                            actual: val.get_type(),
+                            span: node.span.clone(),
+                        })?
+                    }
+                    let block = self.context.append_basic_block(&fun, "matcharm");
+                    switch.add_case(val.into(), block);
+                    block
+                }
+                Pattern::Variant { .. } => {
+                    let case = self.variant_case(subject, &arm.pattern, span.clone())?;
+                    if cases.contains(&case) {
+                        continue;
+                    }
+                    cases.push(case);
+
+                    let block = self.context.append_basic_block(&fun, "matcharm");
+                    switch.add_case(self.context.const_i64(case), block);
+                    block
+                }
+                _ => default_block,
+            };
+            self.builder.position_builder_at_end(&block);
+            let bindings = self.build_pattern_bindings(subject, &arm.pattern, span.clone())?;
+            branches.extend(compile_branch(
+                self,
+                &arm.body,
+                bindings,
+                after_block,
+                expected,
+            )?);
+        }
+
+        if !arms.iter().any(matches_anything) {
+            self.builder.position_builder_at_end(&default_block);
+            match exhaustive {
+                true => self.builder.build_unreachable(),
+                false => self.builder.create_br(after_block),
+            };
+        }
+
+        Ok(branches)
+    }
+
+    /// Tests the arms one after another, for ranges, guards and subjects that
+    /// can't be switched on.
+    fn build_match_chain(
+        &mut self,
+        subject: &Value,
+        arms: &[MatchArm],
+        exhaustive: bool,
+        after_block: &llvm::BasicBlock,
+        expected: Option<&parser::Type>,
+        span: Span,
+    ) -> CompilerResult<Vec<(Value, llvm::BasicBlock)>> {
+        let fun = self.builder.get_insert_block().get_parent();
+
+        let mut branches = Vec::new();
+        for arm in arms {
+            let arm_block = self.context.append_basic_block(&fun, "matcharm");
+            let next_block = self.context.append_basic_block(&fun, "matchnext");
+
+            // temporaries of the tests are only alive until the next one
+            let test = self.build_pattern_test(subject, &arm.pattern, span.clone())?;
+            self.release_maybe_orphaned();
+
+            match (test, &arm.guard) {
+                (test, Some(guard)) => {
+                    if let Some(test) = test {
+                        let guard_block = self.context.append_basic_block(&fun, "matchguard");
+                        self.builder.build_cond_br(&test, &guard_block, &next_block);
+                        self.builder.position_builder_at_end(&guard_block);
+                    }
+                    // the guard sees the names bound by the pattern
+                    self.enter_scope();
+                    for (name, val) in
+                        self.build_pattern_bindings(subject, &arm.pattern, span.clone())?
+                    {
+                        bind_value(self, &name, val);
+                    }
+                    let guard = match self.walk(guard)? {
+                        Value::Bool(b) => b,
+                        val => Err(CompilerError::TypeError {
+                            expected: parser::Type::Bool,
+                            actual: val.get_type(),
+                            span: guard.span.clone(),
+                        })?,
+                    };
+                    self.exit_scope()?;
+                    self.builder.build_cond_br(&guard, &arm_block, &next_block);
+                }
+                (Some(test), None) => {
+                    self.builder.build_cond_br(&test, &arm_block, &next_block);
+                }
+                (None, None) => {
+                    self.builder.create_br(&arm_block);
+                }
+            }
+
+            self.builder.position_builder_at_end(&arm_block);
+            let bindings = self.build_pattern_bindings(subject, &arm.pattern, span.clone())?;
+            branches.extend(compile_branch(
+                self,
+                &arm.body,
+                bindings,
+                after_block,
+                expected,
+            )?);
+            self.builder.position_builder_at_end(&next_block);
+        }
+
+        match exhaustive {
+            true => self.builder.build_unreachable(),
+            false => self.builder.create_br(after_block),
+        };
+
+        Ok(branches)
+    }
+
+    /// Compiles a match, whose arms make values of `expected` when given.
+    pub(super) fn compile_match(
+        &mut self,
+        expr: &expression::Match,
+        span: Span,
+        expected: Option<&parser::Type>,
+    ) -> CompilerResult<Value> {
+        let subject = self.walk(&expr.subject)?;
+        let arms = reachable_arms(&expr.arms);
+
+        let exhaustive = match &subject {
+            Value::Bool(_) if !covers_bool(arms, true) || !covers_bool(arms, false) => {
+                Err(CompilerError::NonExhaustiveMatch { span: span.clone() })?
+            }
+            Value::Enum { name, .. }
+                if !arms.iter().any(matches_anything)
+                    && !self
+                        .enum_variants(name)?
+                        .iter()
+                        .all(|decl| covers_variant(arms, &decl.name)) =>
+            {
+                Err(CompilerError::NonExhaustiveMatch { span: span.clone() })?
+            }
+            Value::Bool(_) | Value::Enum { .. } => true,
+            _ => arms.iter().any(matches_anything),
+        };
+
+        let switchable = arms.iter().all(|arm| arm.guard.is_none())
+            && match subject {
+                Value::Int(_) | Value::Bool(_) => arms.iter().all(|arm| match &arm.pattern {
+                    Pattern::Literal(node) => case_value(node).is_some(),
+                    Pattern::Range { .. } | Pattern::Variant { .. } => false,
+                    Pattern::Wildcard => true,
+                }),
+                Value::Enum { .. } => arms
+                    .iter()
+                    .all(|arm| matches!(arm.pattern, Pattern::Variant { .. } | Pattern::Wildcard)),
+                _ => false,
+            };
+
+        // the subject and other temporaries of the enclosing expression must
+        // outlive all arms
+        self.suspend_maybe_orphaned();
+
+        let fun = self.builder.get_insert_block().get_parent();
+        let after_block = self.context.append_basic_block(&fun, "aftermatch");
+
+        let branches = match switchable {
+            true => self.build_match_switch(
+                &subject,
+                arms,
+                exhaustive,
+                &after_block,
+                expected,
+                span.clone(),
+            )?,
+            false => self.build_match_chain(
+                &subject,
+                arms,
+                exhaustive,
+                &after_block,
+                expected,
+                span.clone(),
+            )?,
+        };
+
+        self.resume_maybe_orphaned();
+
+        merge_branches(self, branches, exhaustive, &after_block, span)
+    }
+}
+
+impl MatchVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_match(&mut self, expr: &expression::Match, span: Span) -> CompilerResult<Value> {
+        self.compile_match(expr, span, None)
+    }
+}
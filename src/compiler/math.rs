@@ -0,0 +1,98 @@
+use crate::{llvm, parser::Type};
+
+use super::{variable::Variable, Compiler, Value};
+
+// Lowered to LLVM intrinsics, which the optimizer can fold, hoist and
+// vectorize like any other instruction.
+const INTRINSICS: &[(&str, &str, usize)] = &[
+    ("sqrt", "llvm.sqrt.f64", 1),
+    ("sin", "llvm.sin.f64", 1),
+    ("cos", "llvm.cos.f64", 1),
+    ("exp", "llvm.exp.f64", 1),
+    ("log", "llvm.log.f64", 1),
+    ("pow", "llvm.pow.f64", 2),
+    ("floor", "llvm.floor.f64", 1),
+    ("ceil", "llvm.ceil.f64", 1),
+    ("round", "llvm.round.f64", 1),
+    ("abs", "llvm.fabs.f64", 1),
+    ("min", "llvm.minnum.f64", 2),
+    ("max", "llvm.maxnum.f64", 2),
+];
+
+// Without an intrinsic, these call libm directly.
+const LIBM_FUNCTIONS: &[(&str, usize)] = &[("tan", 1), ("atan2", 2), ("fmod", 2)];
+
+const CONSTANTS: &[(&str, f64)] = &[("PI", std::f64::consts::PI), ("E", std::f64::consts::E)];
+
+impl Compiler {
+    pub(super) fn init_math_builtins(&mut self) {
+        let functions = INTRINSICS.iter().copied().chain(
+            LIBM_FUNCTIONS
+                .iter()
+                .map(|&(name, arity)| (name, name, arity)),
+        );
+        for (name, symbol, arity) in functions {
+            self.declare_builtin(name, symbol, &vec![Type::Numeric; arity], Type::Numeric);
+        }
+    }
+
+    /// Builtin `name` as a value rather than called. Intrinsics have no
+    /// address, so they're replaced by a function calling them.
+    pub(super) fn builtin_value(&mut self, name: &str, builtin: Variable) -> Variable {
+        match builtin {
+            Variable::Function {
+                val,
+                typ,
+                return_type,
+                params,
+                env,
+            } if INTRINSICS.iter().any(|(intrinsic, ..)| *intrinsic == name) => {
+                Variable::Function {
+                    val: self.intrinsic_wrapper(name, val, typ, params.len()),
+                    typ,
+                    return_type,
+                    params,
+                    env,
+                }
+            }
+            builtin => builtin,
+        }
+    }
+
+    /// Function of type `typ` calling `intrinsic`, declared once per module.
+    fn intrinsic_wrapper(
+        &mut self,
+        name: &str,
+        intrinsic: llvm::Function,
+        typ: llvm::Type,
+        arity: usize,
+    ) -> llvm::Function {
+        // not a C identifier, so it can't clash with libm or ffi symbols
+        let symbol = format!("rock.{}", name);
+        if let Some(wrapper) = self.module.get_function(&symbol) {
+            return wrapper;
+        }
+        let wrapper = self.module.add_function(&symbol, typ);
+
+        let curr = self.builder.get_insert_block();
+        let block = self.context.append_basic_block(&wrapper, "entry");
+        self.builder.position_builder_at_end(&block);
+
+        let args: Vec<_> = (0..arity)
+            .map(|i| wrapper.get_param(i.try_into().unwrap()))
+            .collect();
+        let ret = self.builder.build_call(&intrinsic, &args, "");
+        self.builder.build_ret(ret);
+
+        self.builder.position_builder_at_end(&curr);
+        wrapper
+    }
+
+    /// Value of the math constant `name`, used when no variable shadows it.
+    pub(super) fn math_constant(&self, name: &str) -> Option<Value> {
+        CONSTANTS
+            .iter()
+            .find(|(constant, _)| *constant == name)
+            .map(|(_, val)| Value::Numeric(self.context.const_double(*val)))
+    }
+}
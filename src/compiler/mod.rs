@@ -2,21 +2,33 @@ mod assignment;
 mod binary;
 mod bool;
 mod break_visitor;
+mod closure;
 mod conditional;
+mod continue_visitor;
+mod enum_visitor;
 mod extern_visitor;
+mod for_visitor;
 mod func_call;
 mod func_decl_vistor;
 mod grouping;
 mod identifier;
+mod index_visitor;
+mod integer;
+mod interpolation;
 mod load;
+mod match_visitor;
+mod math;
 mod numeric;
 mod program;
+mod return_visitor;
 mod scope;
 mod string;
+mod struct_visitor;
 mod unary;
 mod utils;
 mod value;
 mod variable;
+mod vector;
 mod while_visitor;
 
 use crate::expression;
@@ -26,7 +38,6 @@ use crate::llvm::Builder;
 use crate::llvm::Context;
 use crate::llvm::Function;
 use crate::llvm::Module;
-use crate::llvm::Type;
 use crate::parser;
 use crate::parser::Program;
 use crate::parser::Span;
@@ -36,7 +47,10 @@ use std::error::Error;
 use std::ffi::c_void;
 use std::fmt;
 
+use self::conditional::compile_conditional;
+use self::return_visitor::always_returns;
 use self::scope::Scope;
+use self::utils::{get_llvm_function_type, int_literal};
 pub use self::value::Value;
 use self::variable::Variable;
 
@@ -60,6 +74,41 @@ pub enum CompilerError {
         actual: expression::Operator,
         span: Span,
     },
+    WrongArgumentCount {
+        expected: usize,
+        actual: usize,
+        span: Span,
+    },
+    WrongTypeArgumentCount {
+        expected: usize,
+        actual: usize,
+        span: Span,
+    },
+    UndefinedLabel {
+        label: String,
+        span: Span,
+    },
+    OutsideLoop {
+        span: Span,
+    },
+    NonExhaustiveMatch {
+        span: Span,
+    },
+    UndefinedField {
+        typ: parser::Type,
+        field: String,
+        span: Span,
+    },
+    MissingField {
+        typ: parser::Type,
+        field: String,
+        span: Span,
+    },
+    UndefinedVariant {
+        typ: parser::Type,
+        variant: String,
+        span: Span,
+    },
 }
 
 impl fmt::Display for CompilerError {
@@ -93,6 +142,40 @@ impl fmt::Display for CompilerError {
                 "wrong operator, expected {:#?}, but got {:#?} at {}",
                 expected, actual, span
             ),
+            CompilerError::WrongArgumentCount {
+                expected,
+                actual,
+                span,
+            } => format!(
+                "wrong number of arguments, expected {}, but got {} at {}",
+                expected, actual, span
+            ),
+            CompilerError::WrongTypeArgumentCount {
+                expected,
+                actual,
+                span,
+            } => format!(
+                "wrong number of type arguments, expected {}, but got {} at {}",
+                expected, actual, span
+            ),
+            CompilerError::UndefinedLabel { label, span } => {
+                format!("undefined label '{} at {}", label, span)
+            }
+            CompilerError::OutsideLoop { span } => {
+                format!("break or continue outside of a loop at {}", span)
+            }
+            CompilerError::NonExhaustiveMatch { span } => {
+                format!("non-exhaustive match at {}", span)
+            }
+            CompilerError::UndefinedField { typ, field, span } => {
+                format!("{} has no field {} at {}", typ, field, span)
+            }
+            CompilerError::MissingField { typ, field, span } => {
+                format!("missing field {} of {} at {}", field, typ, span)
+            }
+            CompilerError::UndefinedVariant { typ, variant, span } => {
+                format!("{} has no variant {} at {}", typ, variant, span)
+            }
         };
         write!(f, "{}", msg)
     }
@@ -108,8 +191,25 @@ pub trait Compile: ProgramVisitor<CompilerResult<Value>> {
     fn compile(&mut self) -> CompilerResult<Value>;
 }
 
+struct Frame {
+    return_type: parser::Type,
+    scope_depth: usize,
+    loop_depth: usize,
+    fun: Option<Function>,
+    captures: Vec<Variable>,
+}
+
 pub struct Compiler {
+    frames: Vec<Frame>,
+    prototypes: HashMap<String, Value>,
+    captures: HashMap<Function, Vec<(String, Value)>>,
+    structs: HashMap<String, Vec<parser::Param>>,
+    enums: HashMap<String, Vec<expression::VariantDecl>>,
     after_loop_blocks: Vec<llvm::BasicBlock>,
+    loop_header_blocks: Vec<llvm::BasicBlock>,
+    loop_scope_depths: Vec<usize>,
+    loop_labels: Vec<Option<String>>,
+    loop_collections: Vec<Option<llvm::Value>>,
     maybe_orphaned: Vec<Value>,
     program: Program,
     engine: llvm::Engine,
@@ -118,6 +218,7 @@ pub struct Compiler {
     builder: llvm::Builder,
     pass_manager: llvm::PassManager,
     optimization: bool,
+    bounds_checks: bool,
     scopes: Vec<Scope>,
     builtins: HashMap<String, Variable>,
 }
@@ -130,14 +231,27 @@ impl Visitor<CompilerResult<Value>> for Compiler {
             Expression::Unary(expr) => self.visit_unary(expr, span),
             Expression::FuncCall(expr) => self.visit_func_call(expr, span),
             Expression::Numeric(expr) => self.visit_numeric(expr),
+            Expression::Integer(expr) => self.visit_integer(expr),
             Expression::Assignment(expr) => self.visit_assignment(expr, span),
             Expression::Identifier(expr) => self.visit_identifier(expr),
             Expression::Conditional(expr) => self.visit_conditional(expr, span),
             Expression::String(expr) => self.visit_string(expr),
             Expression::Bool(expr) => self.visit_bool(expr),
-            Expression::Break => self.visit_break(),
+            Expression::Break(label) => self.visit_break(label, span),
+            Expression::Continue(label) => self.visit_continue(label, span),
+            Expression::Return(expr) => self.visit_return(expr, span),
             Expression::While(expr) => self.visit_while(expr, span),
-            Expression::FuncDecl(expr) => self.visit_func_decl(expr),
+            Expression::For(expr) => self.visit_for(expr, span),
+            Expression::Match(expr) => self.visit_match(expr, span),
+            Expression::StructDecl(expr) => self.visit_struct_decl(expr),
+            Expression::StructLiteral(expr) => self.visit_struct_literal(expr, span),
+            Expression::FieldAccess(expr) => self.visit_field_access(expr, span),
+            Expression::Index(expr) => self.visit_index(expr, span),
+            Expression::VecLiteral(expr) => self.visit_vec_literal(expr, span),
+            Expression::Interpolation(expr) => self.visit_interpolation(expr, span),
+            Expression::EnumDecl(expr) => self.visit_enum_decl(expr),
+            Expression::EnumVariant(expr) => self.visit_enum_variant(expr, span),
+            Expression::FuncDecl(expr) => self.visit_func_decl(expr, span),
             Expression::Load(expr) => self.visit_load(expr),
             Expression::Extern(expr) => self.visit_extern(expr),
             Expression::Grouping(expr) => self.visit_grouping(expr),
@@ -177,6 +291,12 @@ impl Compiler {
         self.optimization = false;
     }
 
+    /// Indexes vectors without checking the index first. Reads out of range
+    /// then give a default value and writes past the end grow the vector.
+    pub fn turn_off_bounds_checks(&mut self) {
+        self.bounds_checks = false;
+    }
+
     pub fn new(program: Program) -> CompilerResult<Self> {
         let context = llvm::Context::new();
         let module = llvm::Module::new("main", &context);
@@ -188,7 +308,16 @@ impl Compiler {
         let pass_manager = llvm::PassManager::new(&module);
 
         Ok(Compiler {
+            frames: Vec::new(),
+            prototypes: HashMap::new(),
+            captures: HashMap::new(),
+            structs: HashMap::new(),
+            enums: HashMap::new(),
             after_loop_blocks: Vec::new(),
+            loop_header_blocks: Vec::new(),
+            loop_scope_depths: Vec::new(),
+            loop_labels: Vec::new(),
+            loop_collections: Vec::new(),
             maybe_orphaned: Vec::new(),
             builtins: HashMap::new(),
             scopes: vec![],
@@ -199,180 +328,767 @@ impl Compiler {
             engine,
             pass_manager,
             optimization: true,
+            bounds_checks: true,
         })
     }
 
-    fn init_builtin(&mut self, name: &str, typ: Type, fun: *mut c_void, return_type: parser::Type) {
+    fn push_loop(
+        &mut self,
+        label: &Option<String>,
+        header: llvm::BasicBlock,
+        after: llvm::BasicBlock,
+        collection: Option<llvm::Value>,
+    ) {
+        self.after_loop_blocks.push(after);
+        self.loop_header_blocks.push(header);
+        self.loop_scope_depths.push(self.scopes.len());
+        self.loop_labels.push(label.clone());
+        self.loop_collections.push(collection);
+    }
+
+    fn pop_loop(&mut self) {
+        self.after_loop_blocks.pop();
+        self.loop_header_blocks.pop();
+        self.loop_scope_depths.pop();
+        self.loop_labels.pop();
+        self.loop_collections.pop();
+    }
+
+    /// Finds the loop of the current function targeted by `break` or
+    /// `continue`, the innermost one when there is no label.
+    fn resolve_loop(&self, label: &Option<String>, span: Span) -> CompilerResult<usize> {
+        let depth = self.frames.last().unwrap().loop_depth;
+        let loops = depth..self.after_loop_blocks.len();
+        match label {
+            Some(label) => loops
+                .rev()
+                .find(|idx| self.loop_labels[*idx].as_ref() == Some(label))
+                .ok_or_else(|| CompilerError::UndefinedLabel {
+                    label: label.clone(),
+                    span,
+                }),
+            None => loops.last().ok_or(CompilerError::OutsideLoop { span }),
+        }
+    }
+
+    fn init_builtin(
+        &mut self,
+        name: &str,
+        params: &[parser::Type],
+        return_type: parser::Type,
+        fun: *mut c_void,
+    ) {
         self.context.add_symbol(name, fun);
-        let val = self.module.add_function(name, typ);
+        self.declare_builtin(name, name, params, return_type);
+    }
+
+    /// Declares the stdlib function `name` for the code generated by the
+    /// compiler, without making it callable from programs.
+    fn init_runtime_function(
+        &mut self,
+        name: &str,
+        params: &[parser::Type],
+        return_type: parser::Type,
+        fun: *mut c_void,
+    ) {
+        self.context.add_symbol(name, fun);
+        let typ = get_llvm_function_type(&self.context, params, &return_type);
+        self.module.add_function(name, typ);
+    }
+
+    /// Makes the LLVM function `symbol`, defined outside of the program,
+    /// callable as `name`.
+    fn declare_builtin(
+        &mut self,
+        name: &str,
+        symbol: &str,
+        params: &[parser::Type],
+        return_type: parser::Type,
+    ) {
+        let typ = get_llvm_function_type(&self.context, params, &return_type);
+        let val = self.module.add_function(symbol, typ);
         self.builtins.insert(
             name.to_string(),
             Variable::Function {
                 val,
                 typ,
                 return_type,
+                params: params.to_vec(),
+                env: None,
             },
         );
     }
 
     fn init_builtins(&mut self) {
-        let string_type = self.context.function_type(
-            self.context.void_type().pointer_type(0),
-            &[self.context.double_type()],
-            false,
-        );
         self.init_builtin(
             "string",
-            string_type,
-            stdlib::string as *mut c_void,
+            &[parser::Type::Numeric],
             parser::Type::String,
+            stdlib::string as *mut c_void,
         );
-
-        let print_type = self.context.function_type(
-            self.context.void_type(),
-            &[self.context.void_type().pointer_type(0)],
-            false,
+        self.init_builtin(
+            "int",
+            &[parser::Type::Numeric],
+            parser::Type::Int,
+            stdlib::int as *mut c_void,
+        );
+        self.init_builtin(
+            "number",
+            &[parser::Type::Int],
+            parser::Type::Numeric,
+            stdlib::number as *mut c_void,
         );
         self.init_builtin(
             "print",
-            print_type,
-            stdlib::print as *mut c_void,
+            &[parser::Type::String],
             parser::Type::Void,
+            stdlib::print as *mut c_void,
         );
-
         self.init_builtin(
             "release_string_reference",
-            self.context.function_type(
-                self.context.void_type(),
-                &[self.context.void_type().pointer_type(0)],
-                false,
-            ),
-            stdlib::release_string_reference as *mut c_void,
+            &[parser::Type::String],
             parser::Type::Void,
+            stdlib::release_string_reference as *mut c_void,
         );
-
         self.init_builtin(
             "inc_string_reference",
-            self.context.function_type(
-                self.context.void_type(),
-                &[self.context.void_type().pointer_type(0)],
-                false,
-            ),
-            stdlib::inc_string_reference as *mut c_void,
+            &[parser::Type::String],
             parser::Type::Void,
+            stdlib::inc_string_reference as *mut c_void,
         );
-
         self.init_builtin(
             "inc_vec_reference",
-            self.context.function_type(
-                self.context.void_type(),
-                &[self.context.void_type().pointer_type(0)],
-                false,
-            ),
-            stdlib::inc_vec_reference as *mut c_void,
+            &[parser::Type::Ptr],
             parser::Type::Void,
+            stdlib::inc_vec_reference as *mut c_void,
         );
-
         self.init_builtin(
             "release_vec_reference",
-            self.context.function_type(
-                self.context.void_type(),
-                &[self.context.void_type().pointer_type(0)],
-                false,
-            ),
-            stdlib::release_vec_reference as *mut c_void,
+            &[parser::Type::Ptr],
             parser::Type::Void,
+            stdlib::release_vec_reference as *mut c_void,
         );
-
         self.init_builtin(
             "c_string_from_string",
-            self.context.function_type(
-                self.context.i8_type().pointer_type(0),
-                &[self.context.void_type().pointer_type(0)],
-                false,
-            ),
-            stdlib::c_string_from_string as *mut c_void,
+            &[parser::Type::String],
             parser::Type::CString,
+            stdlib::c_string_from_string as *mut c_void,
         );
-
         self.init_builtin(
             "string_from_c_string",
-            self.context.function_type(
-                self.context.void_type().pointer_type(0),
-                &[self.context.i8_type().pointer_type(0)],
-                false,
-            ),
-            stdlib::string_from_c_string as *mut c_void,
+            &[parser::Type::CString],
             parser::Type::String,
+            stdlib::string_from_c_string as *mut c_void,
+        );
+        self.init_runtime_function(
+            "string_equal",
+            &[parser::Type::String, parser::Type::String],
+            parser::Type::Bool,
+            stdlib::string_equal as *mut c_void,
         );
-
-        let vec_new_type =
-            self.context
-                .function_type(self.context.void_type().pointer_type(0), &[], false);
         self.init_builtin(
-            "vec_new",
-            vec_new_type,
-            stdlib::vec_new as *mut c_void,
-            parser::Type::Vector,
+            "string_get",
+            &[parser::Type::String, parser::Type::Numeric],
+            parser::Type::String,
+            stdlib::string_get as *mut c_void,
         );
-
-        let vec_set_type = self.context.function_type(
-            self.context.void_type(),
+        self.init_runtime_function(
+            "string_set",
             &[
-                self.context.void_type().pointer_type(0),
-                self.context.double_type(),
-                self.context.double_type(),
+                parser::Type::String,
+                parser::Type::Numeric,
+                parser::Type::String,
+                parser::Type::Int,
+                parser::Type::Int,
             ],
-            false,
+            parser::Type::String,
+            stdlib::string_set as *mut c_void,
+        );
+        self.init_builtin(
+            "string_from_int",
+            &[parser::Type::Int],
+            parser::Type::String,
+            stdlib::string_from_int as *mut c_void,
+        );
+        self.init_builtin(
+            "string_from_bool",
+            &[parser::Type::Bool],
+            parser::Type::String,
+            stdlib::string_from_bool as *mut c_void,
         );
         self.init_builtin(
-            "vec_set",
-            vec_set_type,
-            stdlib::vec_set as *mut c_void,
+            "string_new",
+            &[],
+            parser::Type::String,
+            stdlib::string_new as *mut c_void,
+        );
+        self.init_builtin(
+            "string_append",
+            &[parser::Type::String, parser::Type::String],
             parser::Type::Void,
+            stdlib::string_append as *mut c_void,
         );
-
-        let vec_get_type = self.context.function_type(
-            self.context.double_type(),
+        self.init_builtin(
+            "string_concat",
+            &[parser::Type::String, parser::Type::String],
+            parser::Type::String,
+            stdlib::string_concat as *mut c_void,
+        );
+        self.init_builtin(
+            "string_compare",
+            &[parser::Type::String, parser::Type::String],
+            parser::Type::Int,
+            stdlib::string_compare as *mut c_void,
+        );
+        self.init_builtin(
+            "string_len",
+            &[parser::Type::String],
+            parser::Type::Numeric,
+            stdlib::string_len as *mut c_void,
+        );
+        self.init_builtin(
+            "string_substring",
             &[
-                self.context.void_type().pointer_type(0),
-                self.context.double_type(),
+                parser::Type::String,
+                parser::Type::Numeric,
+                parser::Type::Numeric,
             ],
-            false,
+            parser::Type::String,
+            stdlib::string_substring as *mut c_void,
         );
         self.init_builtin(
-            "vec_get",
-            vec_get_type,
-            stdlib::vec_get as *mut c_void,
+            "string_find",
+            &[parser::Type::String, parser::Type::String],
             parser::Type::Numeric,
+            stdlib::string_find as *mut c_void,
         );
-
-        let vec_len_type = self.context.function_type(
-            self.context.double_type(),
-            &[self.context.void_type().pointer_type(0)],
-            false,
+        self.init_builtin(
+            "string_split",
+            &[parser::Type::String, parser::Type::String],
+            parser::Type::Vector(Box::new(parser::Type::String)),
+            stdlib::string_split as *mut c_void,
+        );
+        self.init_builtin(
+            "string_join",
+            &[
+                parser::Type::Vector(Box::new(parser::Type::String)),
+                parser::Type::String,
+            ],
+            parser::Type::String,
+            stdlib::string_join as *mut c_void,
+        );
+        self.init_builtin(
+            "string_trim",
+            &[parser::Type::String],
+            parser::Type::String,
+            stdlib::string_trim as *mut c_void,
+        );
+        self.init_builtin(
+            "string_replace",
+            &[
+                parser::Type::String,
+                parser::Type::String,
+                parser::Type::String,
+            ],
+            parser::Type::String,
+            stdlib::string_replace as *mut c_void,
+        );
+        self.init_builtin(
+            "string_upper",
+            &[parser::Type::String],
+            parser::Type::String,
+            stdlib::string_upper as *mut c_void,
+        );
+        self.init_builtin(
+            "string_lower",
+            &[parser::Type::String],
+            parser::Type::String,
+            stdlib::string_lower as *mut c_void,
+        );
+        self.init_builtin(
+            "string_char_at",
+            &[parser::Type::String, parser::Type::Numeric],
+            parser::Type::String,
+            stdlib::string_get as *mut c_void,
+        );
+        self.init_builtin(
+            "string_to_number",
+            &[parser::Type::String],
+            parser::Type::Numeric,
+            stdlib::string_to_number as *mut c_void,
+        );
+        self.init_builtin(
+            "is_nan",
+            &[parser::Type::Numeric],
+            parser::Type::Bool,
+            stdlib::is_nan as *mut c_void,
+        );
+        self.init_builtin(
+            "format_fixed",
+            &[parser::Type::Numeric, parser::Type::Numeric],
+            parser::Type::String,
+            stdlib::format_fixed as *mut c_void,
+        );
+        self.init_builtin(
+            "format_scientific",
+            &[parser::Type::Numeric, parser::Type::Numeric],
+            parser::Type::String,
+            stdlib::format_scientific as *mut c_void,
+        );
+        for (name, fun) in [
+            ("string_pad_left", stdlib::string_pad_left as *mut c_void),
+            ("string_pad_right", stdlib::string_pad_right as *mut c_void),
+        ] {
+            self.init_builtin(
+                name,
+                &[
+                    parser::Type::String,
+                    parser::Type::Numeric,
+                    parser::Type::String,
+                ],
+                parser::Type::String,
+                fun,
+            );
+        }
+        self.init_builtin(
+            "vec_new",
+            &[],
+            parser::Type::Ptr,
+            stdlib::vec_new as *mut c_void,
         );
         self.init_builtin(
             "vec_len",
-            vec_len_type,
-            stdlib::vec_len as *mut c_void,
+            &[parser::Type::Ptr],
             parser::Type::Numeric,
+            stdlib::vec_len as *mut c_void,
         );
-
-        let sqrt_type = self.context.function_type(
-            self.context.double_type(),
-            &[self.context.double_type()],
-            false,
+        for (name, typ, fun) in [
+            (
+                "vec_set_numeric",
+                parser::Type::Numeric,
+                stdlib::vec_set_numeric as *mut c_void,
+            ),
+            (
+                "vec_set_int",
+                parser::Type::Int,
+                stdlib::vec_set_int as *mut c_void,
+            ),
+            (
+                "vec_set_bool",
+                parser::Type::Bool,
+                stdlib::vec_set_bool as *mut c_void,
+            ),
+            (
+                "vec_set_string",
+                parser::Type::String,
+                stdlib::vec_set_string as *mut c_void,
+            ),
+            (
+                "vec_set_vec",
+                parser::Type::Ptr,
+                stdlib::vec_set_vec as *mut c_void,
+            ),
+            (
+                "vec_set_env",
+                parser::Type::Ptr,
+                stdlib::vec_set_env as *mut c_void,
+            ),
+            (
+                "vec_set_struct",
+                parser::Type::Ptr,
+                stdlib::vec_set_struct as *mut c_void,
+            ),
+            (
+                "vec_set_ptr",
+                parser::Type::Ptr,
+                stdlib::vec_set_ptr as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(
+                name,
+                &[parser::Type::Ptr, parser::Type::Numeric, typ],
+                parser::Type::Void,
+                fun,
+            );
+        }
+        for (name, typ, fun) in [
+            (
+                "vec_get_numeric",
+                parser::Type::Numeric,
+                stdlib::vec_get_numeric as *mut c_void,
+            ),
+            (
+                "vec_get_int",
+                parser::Type::Int,
+                stdlib::vec_get_int as *mut c_void,
+            ),
+            (
+                "vec_get_bool",
+                parser::Type::Bool,
+                stdlib::vec_get_bool as *mut c_void,
+            ),
+            (
+                "vec_get_ptr",
+                parser::Type::Ptr,
+                stdlib::vec_get_ptr as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(name, &[parser::Type::Ptr, parser::Type::Numeric], typ, fun);
+        }
+        for (name, fun) in [
+            ("vec_check_get", stdlib::vec_check_get as *mut c_void),
+            ("vec_check_set", stdlib::vec_check_set as *mut c_void),
+            ("string_check_get", stdlib::string_check_get as *mut c_void),
+            ("string_check_set", stdlib::string_check_set as *mut c_void),
+        ] {
+            self.init_builtin(
+                name,
+                &[
+                    parser::Type::Ptr,
+                    parser::Type::Numeric,
+                    parser::Type::Int,
+                    parser::Type::Int,
+                ],
+                parser::Type::Void,
+                fun,
+            );
+        }
+        self.init_runtime_function(
+            "vec_check_slice",
+            &[
+                parser::Type::Numeric,
+                parser::Type::Numeric,
+                parser::Type::Int,
+                parser::Type::Int,
+            ],
+            parser::Type::Void,
+            stdlib::vec_check_slice as *mut c_void,
         );
-        let val = self.module.add_function("sqrt", sqrt_type);
-        self.builtins.insert(
-            "sqrt".to_string(),
-            Variable::Function {
-                val,
-                typ: sqrt_type,
-                return_type: parser::Type::Numeric,
-            },
+        self.init_builtin(
+            "int_check_div",
+            &[
+                parser::Type::Int,
+                parser::Type::Int,
+                parser::Type::Int,
+                parser::Type::Int,
+            ],
+            parser::Type::Void,
+            stdlib::int_check_div as *mut c_void,
+        );
+        for (name, typ, fun) in [
+            (
+                "vec_insert_numeric",
+                parser::Type::Numeric,
+                stdlib::vec_insert_numeric as *mut c_void,
+            ),
+            (
+                "vec_insert_int",
+                parser::Type::Int,
+                stdlib::vec_insert_int as *mut c_void,
+            ),
+            (
+                "vec_insert_bool",
+                parser::Type::Bool,
+                stdlib::vec_insert_bool as *mut c_void,
+            ),
+            (
+                "vec_insert_string",
+                parser::Type::String,
+                stdlib::vec_insert_string as *mut c_void,
+            ),
+            (
+                "vec_insert_vec",
+                parser::Type::Ptr,
+                stdlib::vec_insert_vec as *mut c_void,
+            ),
+            (
+                "vec_insert_env",
+                parser::Type::Ptr,
+                stdlib::vec_insert_env as *mut c_void,
+            ),
+            (
+                "vec_insert_struct",
+                parser::Type::Ptr,
+                stdlib::vec_insert_struct as *mut c_void,
+            ),
+            (
+                "vec_insert_ptr",
+                parser::Type::Ptr,
+                stdlib::vec_insert_ptr as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(
+                name,
+                &[parser::Type::Ptr, parser::Type::Numeric, typ],
+                parser::Type::Void,
+                fun,
+            );
+        }
+        for (name, typ, fun) in [
+            (
+                "vec_remove_numeric",
+                parser::Type::Numeric,
+                stdlib::vec_remove_numeric as *mut c_void,
+            ),
+            (
+                "vec_remove_int",
+                parser::Type::Int,
+                stdlib::vec_remove_int as *mut c_void,
+            ),
+            (
+                "vec_remove_bool",
+                parser::Type::Bool,
+                stdlib::vec_remove_bool as *mut c_void,
+            ),
+            (
+                "vec_remove_ptr",
+                parser::Type::Ptr,
+                stdlib::vec_remove_ptr as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(name, &[parser::Type::Ptr, parser::Type::Numeric], typ, fun);
+        }
+        self.init_builtin(
+            "vec_slice",
+            &[
+                parser::Type::Ptr,
+                parser::Type::Numeric,
+                parser::Type::Numeric,
+            ],
+            parser::Type::Ptr,
+            stdlib::vec_slice as *mut c_void,
+        );
+        self.init_builtin(
+            "vec_concat",
+            &[parser::Type::Ptr, parser::Type::Ptr],
+            parser::Type::Ptr,
+            stdlib::vec_concat as *mut c_void,
+        );
+        self.init_builtin(
+            "vec_sort",
+            &[parser::Type::Ptr],
+            parser::Type::Void,
+            stdlib::vec_sort as *mut c_void,
+        );
+        self.init_builtin(
+            "vec_reverse",
+            &[parser::Type::Ptr],
+            parser::Type::Void,
+            stdlib::vec_reverse as *mut c_void,
+        );
+        for (name, typ, fun) in [
+            (
+                "vec_index_of_numeric",
+                parser::Type::Numeric,
+                stdlib::vec_index_of_numeric as *mut c_void,
+            ),
+            (
+                "vec_index_of_int",
+                parser::Type::Int,
+                stdlib::vec_index_of_int as *mut c_void,
+            ),
+            (
+                "vec_index_of_bool",
+                parser::Type::Bool,
+                stdlib::vec_index_of_bool as *mut c_void,
+            ),
+            (
+                "vec_index_of_string",
+                parser::Type::String,
+                stdlib::vec_index_of_string as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(name, &[parser::Type::Ptr, typ], parser::Type::Numeric, fun);
+        }
+        self.init_builtin(
+            "env_new",
+            &[parser::Type::Ptr],
+            parser::Type::Ptr,
+            stdlib::env_new as *mut c_void,
+        );
+        for (name, typ, fun) in [
+            (
+                "env_set_numeric",
+                parser::Type::Numeric,
+                stdlib::env_set_numeric as *mut c_void,
+            ),
+            (
+                "env_set_int",
+                parser::Type::Int,
+                stdlib::env_set_int as *mut c_void,
+            ),
+            (
+                "env_set_bool",
+                parser::Type::Bool,
+                stdlib::env_set_bool as *mut c_void,
+            ),
+            (
+                "env_set_string",
+                parser::Type::String,
+                stdlib::env_set_string as *mut c_void,
+            ),
+            (
+                "env_set_vec",
+                parser::Type::Ptr,
+                stdlib::env_set_vec as *mut c_void,
+            ),
+            (
+                "env_set_env",
+                parser::Type::Ptr,
+                stdlib::env_set_env as *mut c_void,
+            ),
+            (
+                "env_set_struct",
+                parser::Type::Ptr,
+                stdlib::env_set_struct as *mut c_void,
+            ),
+            (
+                "env_set_ptr",
+                parser::Type::Ptr,
+                stdlib::env_set_ptr as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(
+                name,
+                &[parser::Type::Ptr, parser::Type::Numeric, typ],
+                parser::Type::Void,
+                fun,
+            );
+        }
+        for (name, typ, fun) in [
+            (
+                "env_get_numeric",
+                parser::Type::Numeric,
+                stdlib::env_get_numeric as *mut c_void,
+            ),
+            (
+                "env_get_int",
+                parser::Type::Int,
+                stdlib::env_get_int as *mut c_void,
+            ),
+            (
+                "env_get_bool",
+                parser::Type::Bool,
+                stdlib::env_get_bool as *mut c_void,
+            ),
+            (
+                "env_get_ptr",
+                parser::Type::Ptr,
+                stdlib::env_get_ptr as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(name, &[parser::Type::Ptr, parser::Type::Numeric], typ, fun);
+        }
+        self.init_builtin(
+            "env_function",
+            &[parser::Type::Ptr],
+            parser::Type::Ptr,
+            stdlib::env_function as *mut c_void,
+        );
+        self.init_builtin(
+            "inc_env_reference",
+            &[parser::Type::Ptr],
+            parser::Type::Void,
+            stdlib::inc_env_reference as *mut c_void,
+        );
+        self.init_builtin(
+            "release_env_reference",
+            &[parser::Type::Ptr],
+            parser::Type::Void,
+            stdlib::release_env_reference as *mut c_void,
+        );
+        self.init_builtin(
+            "struct_new",
+            &[],
+            parser::Type::Ptr,
+            stdlib::struct_new as *mut c_void,
+        );
+        for (name, typ, fun) in [
+            (
+                "struct_set_numeric",
+                parser::Type::Numeric,
+                stdlib::struct_set_numeric as *mut c_void,
+            ),
+            (
+                "struct_set_int",
+                parser::Type::Int,
+                stdlib::struct_set_int as *mut c_void,
+            ),
+            (
+                "struct_set_bool",
+                parser::Type::Bool,
+                stdlib::struct_set_bool as *mut c_void,
+            ),
+            (
+                "struct_set_string",
+                parser::Type::String,
+                stdlib::struct_set_string as *mut c_void,
+            ),
+            (
+                "struct_set_vec",
+                parser::Type::Ptr,
+                stdlib::struct_set_vec as *mut c_void,
+            ),
+            (
+                "struct_set_env",
+                parser::Type::Ptr,
+                stdlib::struct_set_env as *mut c_void,
+            ),
+            (
+                "struct_set_struct",
+                parser::Type::Ptr,
+                stdlib::struct_set_struct as *mut c_void,
+            ),
+            (
+                "struct_set_ptr",
+                parser::Type::Ptr,
+                stdlib::struct_set_ptr as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(
+                name,
+                &[parser::Type::Ptr, parser::Type::Numeric, typ],
+                parser::Type::Void,
+                fun,
+            );
+        }
+        for (name, typ, fun) in [
+            (
+                "struct_get_numeric",
+                parser::Type::Numeric,
+                stdlib::struct_get_numeric as *mut c_void,
+            ),
+            (
+                "struct_get_int",
+                parser::Type::Int,
+                stdlib::struct_get_int as *mut c_void,
+            ),
+            (
+                "struct_get_bool",
+                parser::Type::Bool,
+                stdlib::struct_get_bool as *mut c_void,
+            ),
+            (
+                "struct_get_ptr",
+                parser::Type::Ptr,
+                stdlib::struct_get_ptr as *mut c_void,
+            ),
+        ] {
+            self.init_builtin(name, &[parser::Type::Ptr, parser::Type::Numeric], typ, fun);
+        }
+        self.init_builtin(
+            "inc_struct_reference",
+            &[parser::Type::Ptr],
+            parser::Type::Void,
+            stdlib::inc_struct_reference as *mut c_void,
+        );
+        self.init_builtin(
+            "release_struct_reference",
+            &[parser::Type::Ptr],
+            parser::Type::Void,
+            stdlib::release_struct_reference as *mut c_void,
         );
+        self.init_math_builtins();
     }
 
     fn set_param(&mut self, name: &str, val: Value) {
@@ -382,7 +1098,7 @@ impl Compiler {
     fn get_param(&self, expr: &str) -> Option<Value> {
         for scope in self.scopes.iter().rev() {
             if let Some(val) = scope.get_param(expr) {
-                return Some(*val);
+                return Some(val.clone());
             }
         }
         None
@@ -395,16 +1111,53 @@ trait LLVMCompiler: Visitor<CompilerResult<Value>> {
     fn module(&self) -> &Module;
     fn enter_scope(&mut self);
     fn exit_scope(&mut self) -> CompilerResult<()>;
-    fn after_loop_blocks(&self) -> &Vec<llvm::BasicBlock>;
+    fn build_loop_exit(&self, jump: &Value) -> CompilerResult<llvm::BasicBlock>;
     fn get_var(&self, name: &str) -> Option<Variable>;
+    /// Compiles `node` where a value of `typ` is expected, so that integer
+    /// literals become ints when `typ` is an int. Other values are left as
+    /// they are, for the caller to check against `typ`.
+    fn walk_expecting(
+        &mut self,
+        node: &expression::Node,
+        typ: &parser::Type,
+    ) -> CompilerResult<Value>;
     fn get_builtin(&self, name: &str) -> Option<Variable>;
     fn track_maybe_orphaned(&mut self, val: Value);
     fn release_maybe_orphaned(&mut self);
+    fn maybe_orphaned_len(&self) -> usize;
+    fn release_maybe_orphaned_since(&mut self, len: usize);
+    /// Sets the temporaries aside while branches are compiled, in a scope
+    /// releasing them when one of the branches leaves it early.
+    fn suspend_maybe_orphaned(&mut self);
+    /// Takes back the temporaries set aside by `suspend_maybe_orphaned`.
+    fn resume_maybe_orphaned(&mut self);
+    fn assign_field(
+        &mut self,
+        expr: &expression::FieldAccess,
+        right: &expression::Node,
+        span: Span,
+    ) -> CompilerResult<Value>;
+    fn assign_index(
+        &mut self,
+        expr: &expression::Index,
+        right: &expression::Node,
+        span: Span,
+    ) -> CompilerResult<Value>;
+    fn compile_vec_call(
+        &mut self,
+        name: &str,
+        expr: &expression::FuncCall,
+        span: Span,
+    ) -> CompilerResult<Value>;
     fn set_var(&mut self, name: &str, val: Variable);
+    fn take_prototype(&mut self, name: &str) -> Option<Value>;
+    fn build_closure(&mut self, val: Value) -> Value;
+    fn declare_function(&mut self, expr: &expression::FuncDecl) -> Value;
     fn build_function(
         &mut self,
         fun_compiler_val: Value,
         expr: &expression::FuncDecl,
+        span: Span,
     ) -> Result<(), CompilerError>;
 }
 
@@ -432,16 +1185,86 @@ impl LLVMCompiler for Compiler {
     }
 
     fn get_var(&self, name: &str) -> Option<Variable> {
-        for scope in self.scopes.iter().rev() {
-            if let Some(val) = scope.get(name) {
-                return Some(*val);
+        let scope_depth = self.frames.last().map_or(0, |frame| frame.scope_depth);
+        let (depth, var) = self
+            .scopes
+            .iter()
+            .enumerate()
+            .rev()
+            // parameters are shadowed by variables assigned to their name
+            .find_map(
+                |(depth, scope)| match (scope.get(name), scope.get_param(name)) {
+                    (Some(var), _) => Some(Some((depth, var))),
+                    (None, Some(_)) => Some(None),
+                    (None, None) => None,
+                },
+            )??;
+        // variables of enclosing functions are only reachable once captured
+        if depth < scope_depth && !matches!(var, Variable::Function { .. }) {
+            return None;
+        }
+        match var {
+            Variable::Function {
+                val,
+                typ,
+                return_type,
+                params,
+                ..
+            } if self
+                .frames
+                .last()
+                .filter(|frame| !frame.captures.is_empty())
+                .and_then(|frame| frame.fun)
+                == Some(*val) =>
+            {
+                Some(Variable::Function {
+                    val: *val,
+                    typ: *typ,
+                    return_type: return_type.clone(),
+                    params: params.clone(),
+                    env: Some(val.get_param(0)),
+                })
             }
+            var => Some(var.clone()),
         }
-        None
+    }
+
+    fn walk_expecting(
+        &mut self,
+        node: &expression::Node,
+        typ: &parser::Type,
+    ) -> CompilerResult<Value> {
+        Ok(match (&node.expression, int_literal(node)) {
+            (_, Some(n)) if *typ == parser::Type::Int => Value::Int(self.context.const_i64(n)),
+            (Expression::Grouping(expr), _) => self.walk_expecting(&expr.0, typ)?,
+            // the value of the last statement of each branch is expected
+            (Expression::Conditional(expr), _) => {
+                compile_conditional(self, expr, node.span.clone(), Some(typ))?
+            }
+            (Expression::Match(expr), _) => {
+                self.compile_match(expr, node.span.clone(), Some(typ))?
+            }
+            // vectors take their element type from where they are used
+            (Expression::VecLiteral(expr), _) => match typ {
+                parser::Type::Vector(element) => self.compile_vec_literal(expr, Some(element))?,
+                _ => self.walk(node)?,
+            },
+            (Expression::FuncCall(call), _)
+                if matches!(&call.calee.expression, Expression::Identifier(name) if name == "vec_new")
+                    && call.type_args.is_empty()
+                    && call.args.is_empty() =>
+            {
+                match typ {
+                    parser::Type::Vector(element) => self.build_vec_new(*element.clone()),
+                    _ => self.walk(node)?,
+                }
+            }
+            _ => self.walk(node)?,
+        })
     }
 
     fn get_builtin(&self, name: &str) -> Option<Variable> {
-        self.builtins.get(name).copied()
+        self.builtins.get(name).cloned()
     }
 
     fn track_maybe_orphaned(&mut self, val: Value) {
@@ -449,7 +1272,53 @@ impl LLVMCompiler for Compiler {
     }
 
     fn release_maybe_orphaned(&mut self) {
-        while let Some(val) = self.maybe_orphaned.pop() {
+        self.release_maybe_orphaned_since(0);
+    }
+
+    fn maybe_orphaned_len(&self) -> usize {
+        self.maybe_orphaned.len()
+    }
+
+    fn suspend_maybe_orphaned(&mut self) {
+        let temporaries = std::mem::take(&mut self.maybe_orphaned);
+        self.scopes.push(Scope::suspending(temporaries));
+    }
+
+    fn resume_maybe_orphaned(&mut self) {
+        let temporaries = self.scopes.pop().unwrap().take_temporaries();
+        self.maybe_orphaned.splice(0..0, temporaries);
+    }
+
+    fn assign_field(
+        &mut self,
+        expr: &expression::FieldAccess,
+        right: &expression::Node,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        self.build_field_assignment(expr, right, span)
+    }
+
+    fn assign_index(
+        &mut self,
+        expr: &expression::Index,
+        right: &expression::Node,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        self.build_index_assignment(expr, right, span)
+    }
+
+    fn compile_vec_call(
+        &mut self,
+        name: &str,
+        expr: &expression::FuncCall,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        self.compile_vec_builtin(name, expr, span)
+    }
+
+    fn release_maybe_orphaned_since(&mut self, len: usize) {
+        while self.maybe_orphaned.len() > len {
+            let val = self.maybe_orphaned.pop().unwrap();
             match val {
                 Value::Void => todo!(),
                 Value::String(v) => {
@@ -460,13 +1329,29 @@ impl LLVMCompiler for Compiler {
                     self.builder.build_call(&release, &[v], "");
                 }
                 Value::Numeric(_) => todo!(),
+                Value::Int(_) => todo!(),
                 Value::Bool(_) => todo!(),
-                Value::Function { .. } => todo!(),
-                Value::Vec(v) => {
+                Value::Function { env, .. } => {
+                    let release = self.module.get_function("release_env_reference").unwrap();
+                    self.builder.build_call(&release, &[env.unwrap()], "");
+                }
+                Value::Closure { val, .. } => {
+                    let release = self.module.get_function("release_env_reference").unwrap();
+                    self.builder.build_call(&release, &[val], "");
+                }
+                Value::Vec { val: v, .. } => {
                     let release = self.module.get_function("release_vec_reference").unwrap();
                     self.builder.build_call(&release, &[v], "");
                 }
-                Value::Break => todo!(),
+                Value::Struct { val, .. } | Value::Enum { val, .. } => {
+                    let release = self
+                        .module
+                        .get_function("release_struct_reference")
+                        .unwrap();
+                    self.builder.build_call(&release, &[val], "");
+                }
+                Value::Break(_) => todo!(),
+                Value::Continue(_) => todo!(),
                 Value::Ptr(_) => todo!(),
                 Value::CString(_) => todo!(),
             }
@@ -477,34 +1362,81 @@ impl LLVMCompiler for Compiler {
         self.scopes.last_mut().unwrap().set(name, val);
     }
 
+    fn take_prototype(&mut self, name: &str) -> Option<Value> {
+        self.prototypes.remove(name)
+    }
+
+    fn build_closure(&mut self, val: Value) -> Value {
+        self.make_closure(val)
+    }
+
+    fn declare_function(&mut self, expr: &expression::FuncDecl) -> Value {
+        let params: Vec<_> = expr.params.iter().map(|param| param.typ.clone()).collect();
+        let llvm_params: Vec<_> = std::iter::once(parser::Type::Ptr)
+            .chain(params.iter().cloned())
+            .collect();
+        let fun_type = get_llvm_function_type(&self.context, &llvm_params, &expr.return_type);
+        let val = self.module.add_function("", fun_type);
+        let (env, captures) = self.build_env(val, expr);
+        self.captures.insert(val, captures);
+
+        let fun = Value::Function {
+            return_type: expr.return_type.clone(),
+            typ: fun_type,
+            val,
+            params,
+            env: Some(env),
+        };
+        if !env.is_null() {
+            self.track_maybe_orphaned(fun.clone());
+        }
+        fun
+    }
+
     fn build_function(
         &mut self,
         fun_compiler_val: Value,
         expr: &expression::FuncDecl,
+        span: Span,
     ) -> Result<(), CompilerError> {
         let fun = match fun_compiler_val {
             Value::Function { val, .. } => val,
+            Value::Closure { .. } => todo!(),
             Value::Void => todo!(),
             Value::String(_) => todo!(),
             Value::Numeric(_) => todo!(),
+            Value::Int(_) => todo!(),
             Value::Bool(_) => todo!(),
-            Value::Vec(_) => todo!(),
-            Value::Break => todo!(),
+            Value::Vec { .. } => todo!(),
+            Value::Struct { .. } => todo!(),
+            Value::Enum { .. } => todo!(),
+            Value::Break(_) => todo!(),
+            Value::Continue(_) => todo!(),
             Value::Ptr(_) => todo!(),
             Value::CString(_) => todo!(),
         };
 
         let curr = self.builder.get_insert_block();
+        let maybe_orphaned = std::mem::take(&mut self.maybe_orphaned);
 
         let block = self.context.append_basic_block(&fun, "entry");
         self.builder.position_builder_at_end(&block);
 
         self.enter_scope();
+        let captures = self.captures.remove(&fun).unwrap_or_default();
+        let captures = self.load_captures(fun.get_param(0), &captures);
+        self.frames.push(Frame {
+            return_type: expr.return_type.clone(),
+            scope_depth: self.scopes.len() - 1,
+            loop_depth: self.after_loop_blocks.len(),
+            fun: Some(fun),
+            captures,
+        });
 
         for (i, param) in expr.params.iter().enumerate() {
-            let val = fun.get_param(i.try_into().unwrap());
+            let val = fun.get_param((i + 1).try_into().unwrap());
 
-            let val = match param.typ {
+            let val = match &param.typ {
                 parser::Type::String => {
                     let release = self.module.get_function("inc_vec_reference").unwrap();
                     self.builder.build_call(&release, &[val], "");
@@ -512,15 +1444,49 @@ impl LLVMCompiler for Compiler {
                     Value::String(val)
                 }
                 parser::Type::Numeric => Value::Numeric(val),
+                parser::Type::Int => Value::Int(val),
                 parser::Type::Bool => Value::Bool(val),
-                parser::Type::Vector => {
+                parser::Type::Vector(element) => {
                     let release = self.module.get_function("inc_vec_reference").unwrap();
                     self.builder.build_call(&release, &[val], "");
 
-                    Value::Vec(val)
+                    Value::Vec {
+                        val,
+                        element: *element.clone(),
+                    }
                 }
                 parser::Type::Void => todo!(),
-                parser::Type::Function => todo!(),
+                parser::Type::Function {
+                    params,
+                    return_type,
+                } => {
+                    let inc = self.module.get_function("inc_env_reference").unwrap();
+                    self.builder.build_call(&inc, &[val], "");
+
+                    Value::Closure {
+                        val,
+                        return_type: *return_type.clone(),
+                        params: params.clone(),
+                    }
+                }
+                parser::Type::Struct(name) => {
+                    let inc = self.module.get_function("inc_struct_reference").unwrap();
+                    self.builder.build_call(&inc, &[val], "");
+
+                    Value::Struct {
+                        val,
+                        name: name.clone(),
+                    }
+                }
+                parser::Type::Enum(name) => {
+                    let inc = self.module.get_function("inc_struct_reference").unwrap();
+                    self.builder.build_call(&inc, &[val], "");
+
+                    Value::Enum {
+                        val,
+                        name: name.clone(),
+                    }
+                }
                 parser::Type::Ptr => todo!(),
                 parser::Type::CString => todo!(),
             };
@@ -529,15 +1495,35 @@ impl LLVMCompiler for Compiler {
 
         let mut last_val = Value::Void;
 
-        for stmt in expr.body.clone() {
+        for (i, stmt) in expr.body.iter().enumerate() {
             self.release_maybe_orphaned();
-            last_val = self.walk(&stmt)?;
+            last_val = match expr.return_type {
+                parser::Type::Void => self.walk(stmt)?,
+                _ if i + 1 < expr.body.len() => self.walk(stmt)?,
+                _ => self.walk_expecting(stmt, &expr.return_type)?,
+            };
+        }
+
+        let returns = always_returns(&expr.body);
+
+        if !returns
+            && expr.return_type != parser::Type::Void
+            && last_val.get_type() != expr.return_type
+        {
+            Err(CompilerError::TypeError {
+                expected: expr.return_type.clone(),
+                actual: last_val.get_type(),
+                span: expr.body.last().map_or(span, |stmt| stmt.span.clone()),
+            })?
         }
 
         let ret_val = match last_val {
+            _ if returns || expr.return_type == parser::Type::Void => None,
             Value::Void => None,
             Value::Numeric(n) => Some(n),
-            Value::Vec(n) => {
+            Value::Int(n) => Some(n),
+            Value::Bool(n) => Some(n),
+            Value::Vec { val: n, .. } => {
                 let release = self.module.get_function("inc_vec_reference").unwrap();
                 self.builder.build_call(&release, &[n], "");
                 Some(n)
@@ -548,21 +1534,38 @@ impl LLVMCompiler for Compiler {
 
                 Some(n)
             }
-            Value::Bool(_) => todo!(),
-            Value::Function { .. } => todo!(),
-            Value::Break => todo!(),
-            Value::Ptr(_) => todo!(),
+            Value::Struct { val, .. } | Value::Enum { val, .. } => {
+                let inc = self.module.get_function("inc_struct_reference").unwrap();
+                self.builder.build_call(&inc, &[val], "");
+                Some(val)
+            }
+            val @ (Value::Function { .. } | Value::Closure { .. }) => {
+                let val = self.make_closure(val);
+                let inc = self.module.get_function("inc_env_reference").unwrap();
+                self.builder.build_call(&inc, &[(&val).into()], "");
+                Some(val.into())
+            }
+            Value::Break(_) => todo!(),
+            Value::Continue(_) => todo!(),
+            Value::Ptr(n) => Some(n),
             Value::CString(_) => todo!(),
         };
 
+        self.store_captures();
         self.exit_scope()?;
+        self.frames.pop();
 
-        match ret_val {
-            Some(v) => self.builder.build_ret(v),
-            None => self.builder.build_ret_void(),
-        };
+        if returns {
+            self.builder.build_unreachable();
+        } else {
+            match ret_val {
+                Some(v) => self.builder.build_ret(v),
+                None => self.builder.build_ret_void(),
+            };
+        }
 
         self.builder.position_builder_at_end(&curr);
+        self.maybe_orphaned = maybe_orphaned;
 
         self.verify_function(fun)?;
 
@@ -573,7 +1576,25 @@ impl LLVMCompiler for Compiler {
         Ok(())
     }
 
-    fn after_loop_blocks(&self) -> &Vec<llvm::BasicBlock> {
-        &self.after_loop_blocks
+    /// Releases everything that goes out of scope when jumping out of the
+    /// loop bodies up to the loop targeted by `jump`, and returns the block to
+    /// jump to.
+    fn build_loop_exit(&self, jump: &Value) -> CompilerResult<llvm::BasicBlock> {
+        let (idx, block) = match jump {
+            Value::Break(idx) => (*idx, self.after_loop_blocks[*idx]),
+            Value::Continue(idx) => (*idx, self.loop_header_blocks[*idx]),
+            _ => unreachable!(),
+        };
+
+        for scope in self.scopes[self.loop_scope_depths[idx]..].iter().rev() {
+            scope.release_references(&self.context, &self.module, &self.builder)?;
+        }
+        // collections of the inner loops are otherwise released after them
+        for vec in self.loop_collections[idx + 1..].iter().flatten().rev() {
+            let release = self.module.get_function("release_vec_reference").unwrap();
+            self.builder.build_call(&release, &[*vec], "");
+        }
+
+        Ok(block)
     }
 }
@@ -1,13 +1,107 @@
+use std::collections::HashMap;
+
 use crate::compiler::LLVMCompiler;
+use crate::expression::{Expression, FuncDecl};
 use crate::{
-    parser::Program,
+    parser::{self, Program},
     visitor::{ProgramVisitor, Visitor},
 };
 
-use super::{Compiler, CompilerResult, Value, MAIN_FUNCTION};
+use super::closure::free_identifiers;
+use super::{Compiler, CompilerResult, Frame, Value, Variable, MAIN_FUNCTION};
+
+impl Compiler {
+    /// Registers the top-level structs and enums, so that they can be used
+    /// before they are declared.
+    fn declare_types(&mut self, program: &Program) -> CompilerResult<()> {
+        for stmt in &program.body {
+            if let Expression::StructDecl(_) | Expression::EnumDecl(_) = stmt.expression {
+                self.walk(stmt)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn declare_prototypes(&mut self, program: &Program) -> CompilerResult<()> {
+        let mut assignments: HashMap<&str, usize> = HashMap::new();
+        for stmt in &program.body {
+            if let Expression::Assignment(assignment) = &stmt.expression {
+                if let Expression::Identifier(name) = &assignment.left.expression {
+                    *assignments.entry(name).or_default() += 1;
+                }
+            }
+        }
+
+        let mut functions: Vec<(&str, &FuncDecl)> = program
+            .body
+            .iter()
+            .filter_map(|stmt| match &stmt.expression {
+                Expression::Assignment(assignment) => {
+                    match (&assignment.left.expression, &assignment.right.expression) {
+                        (Expression::Identifier(name), Expression::FuncDecl(func_decl))
+                            if assignments[name.as_str()] == 1 =>
+                        {
+                            Some((name.as_str(), func_decl))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+
+        // Functions referring to other top-level variables capture them when
+        // they are defined, so those can't be declared ahead.
+        loop {
+            let len = functions.len();
+            let names: Vec<&str> = functions.iter().map(|(name, _)| *name).collect();
+            functions.retain(|(_, func_decl)| {
+                free_identifiers(func_decl).iter().all(|name| {
+                    !assignments.contains_key(name.as_str()) || names.contains(&name.as_str())
+                })
+            });
+            if functions.len() == len {
+                break;
+            }
+        }
+
+        for (name, func_decl) in functions {
+            let prototype = self.declare_function(func_decl);
+            if let Value::Function {
+                val,
+                typ,
+                ref return_type,
+                ref params,
+                env,
+            } = prototype
+            {
+                self.set_var(
+                    name,
+                    Variable::Function {
+                        val,
+                        typ,
+                        return_type: return_type.clone(),
+                        params: params.clone(),
+                        env,
+                    },
+                );
+            }
+            self.prototypes.insert(name.to_string(), prototype);
+        }
+
+        Ok(())
+    }
+}
 
 impl ProgramVisitor<CompilerResult<Value>> for Compiler {
     fn visit_program(&mut self, program: Program) -> CompilerResult<Value> {
+        self.frames.push(Frame {
+            return_type: parser::Type::Void,
+            scope_depth: self.scopes.len(),
+            loop_depth: 0,
+            fun: None,
+            captures: Vec::new(),
+        });
         self.enter_scope();
         self.init_builtins();
 
@@ -19,11 +113,15 @@ impl ProgramVisitor<CompilerResult<Value>> for Compiler {
         let block = self.context.append_basic_block(&main_fun, "");
         self.builder.position_builder_at_end(&block);
 
+        self.declare_types(&program)?;
+        self.declare_prototypes(&program)?;
+
         for stmt in program.body {
             self.release_maybe_orphaned();
             self.walk(&stmt)?;
         }
         self.exit_scope()?;
+        self.frames.pop();
 
         self.builder.build_ret_void();
 
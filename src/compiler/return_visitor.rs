@@ -0,0 +1,92 @@
+use crate::expression::{Expression, Node, Pattern};
+use crate::parser::Span;
+use crate::visitor::ReturnVisitor;
+
+use super::{Compiler, CompilerError, CompilerResult, Value};
+use crate::compiler::LLVMCompiler;
+
+pub fn always_returns(body: &[Node]) -> bool {
+    body.iter().any(|stmt| match &stmt.expression {
+        Expression::Return(_) => true,
+        Expression::Conditional(cond) => {
+            always_returns(&cond.body) && always_returns(&cond.else_body)
+        }
+        // matches on enums are rejected unless they cover every variant
+        Expression::Match(expr) => {
+            expr.arms.iter().any(|arm| {
+                matches!(arm.pattern, Pattern::Wildcard) && arm.guard.is_none()
+                    || matches!(arm.pattern, Pattern::Variant { .. })
+            }) && expr.arms.iter().all(|arm| always_returns(&arm.body))
+        }
+        _ => false,
+    })
+}
+
+impl ReturnVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_return(&mut self, expr: &Option<Box<Node>>, span: Span) -> CompilerResult<Value> {
+        let return_type = self.frames.last().unwrap().return_type.clone();
+        let scope_depth = self.frames.last().unwrap().scope_depth;
+        let loop_depth = self.frames.last().unwrap().loop_depth;
+
+        let val = match expr {
+            Some(expr) => self.walk_expecting(expr, &return_type)?,
+            None => Value::Void,
+        };
+
+        if val.get_type() != return_type {
+            Err(CompilerError::TypeError {
+                expected: return_type,
+                actual: val.get_type(),
+                span,
+            })?
+        }
+
+        let ret_val = match val {
+            Value::Void => None,
+            Value::String(n) => {
+                let inc = self.module.get_function("inc_string_reference").unwrap();
+                self.builder.build_call(&inc, &[n], "");
+                Some(n)
+            }
+            Value::Vec { val: n, .. } => {
+                let inc = self.module.get_function("inc_vec_reference").unwrap();
+                self.builder.build_call(&inc, &[n], "");
+                Some(n)
+            }
+            Value::Struct { val, .. } | Value::Enum { val, .. } => {
+                let inc = self.module.get_function("inc_struct_reference").unwrap();
+                self.builder.build_call(&inc, &[val], "");
+                Some(val)
+            }
+            val @ (Value::Function { .. } | Value::Closure { .. }) => {
+                let val = self.make_closure(val);
+                let inc = self.module.get_function("inc_env_reference").unwrap();
+                self.builder.build_call(&inc, &[(&val).into()], "");
+                Some(val.into())
+            }
+            val => Some(val.into()),
+        };
+
+        self.release_maybe_orphaned();
+        self.store_captures();
+        for scope in self.scopes[scope_depth..].iter().rev() {
+            scope.release_references(&self.context, &self.module, &self.builder)?;
+        }
+        // collections the loops of this function keep alive while iterating
+        for vec in self.loop_collections[loop_depth..].iter().flatten().rev() {
+            let release = self.module.get_function("release_vec_reference").unwrap();
+            self.builder.build_call(&release, &[*vec], "");
+        }
+
+        match ret_val {
+            Some(v) => self.builder.build_ret(v),
+            None => self.builder.build_ret_void(),
+        };
+
+        let fun = self.builder.get_insert_block().get_parent();
+        let after_return_block = self.context.append_basic_block(&fun, "afterreturn");
+        self.builder.position_builder_at_end(&after_return_block);
+
+        Ok(Value::Void)
+    }
+}
@@ -6,6 +6,7 @@ use super::{variable::Variable, CompilerResult, Value};
 pub struct Scope {
     env: HashMap<String, Variable>,
     params: HashMap<String, Value>,
+    temporaries: Vec<Value>,
 }
 
 impl Scope {
@@ -13,9 +14,23 @@ impl Scope {
         Scope {
             params: HashMap::new(),
             env: HashMap::new(),
+            temporaries: Vec::new(),
         }
     }
 
+    /// Scope holding the temporaries of an expression whose evaluation is
+    /// suspended, which are released with the scope.
+    pub fn suspending(temporaries: Vec<Value>) -> Self {
+        Scope {
+            temporaries,
+            ..Scope::new()
+        }
+    }
+
+    pub fn take_temporaries(self) -> Vec<Value> {
+        self.temporaries
+    }
+
     pub fn get(&self, literal: &str) -> Option<&Variable> {
         self.env.get(literal)
     }
@@ -43,7 +58,7 @@ impl Scope {
                         "",
                     );
                 }
-                Variable::Vec(val) => {
+                Variable::Vec { val, .. } => {
                     let release = module.get_function("release_vec_reference").unwrap();
                     builder.build_call(
                         &release,
@@ -51,7 +66,28 @@ impl Scope {
                         "",
                     );
                 }
+                Variable::Closure { val, .. } => {
+                    let release = module.get_function("release_env_reference").unwrap();
+                    builder.build_call(
+                        &release,
+                        &[builder.build_load(&var.llvm_type(context), val, "")],
+                        "",
+                    );
+                }
+                Variable::Struct { val, .. } | Variable::Enum { val, .. } => {
+                    let release = module.get_function("release_struct_reference").unwrap();
+                    builder.build_call(
+                        &release,
+                        &[builder.build_load(&var.llvm_type(context), val, "")],
+                        "",
+                    );
+                }
+                Variable::Function { env: Some(env), .. } if !env.is_null() => {
+                    let release = module.get_function("release_env_reference").unwrap();
+                    builder.build_call(&release, &[*env], "");
+                }
                 Variable::Numeric(_)
+                | Variable::Int(_)
                 | Variable::Bool(_)
                 | Variable::Function { .. }
                 | Variable::Ptr(_) => {}
@@ -63,16 +99,50 @@ impl Scope {
                     let release = module.get_function("release_string_reference").unwrap();
                     builder.build_call(&release, &[*val], "");
                 }
-                Value::Vec(val) => {
+                Value::Vec { val, .. } => {
                     let release = module.get_function("release_vec_reference").unwrap();
                     builder.build_call(&release, &[*val], "");
                 }
-                Value::Numeric(_) | Value::Bool(_) | Value::Function { .. } | Value::Ptr(_) => {}
+                Value::Closure { val, .. } => {
+                    let release = module.get_function("release_env_reference").unwrap();
+                    builder.build_call(&release, &[*val], "");
+                }
+                Value::Struct { val, .. } | Value::Enum { val, .. } => {
+                    let release = module.get_function("release_struct_reference").unwrap();
+                    builder.build_call(&release, &[*val], "");
+                }
+                Value::Numeric(_)
+                | Value::Int(_)
+                | Value::Bool(_)
+                | Value::Function { .. }
+                | Value::Ptr(_) => {}
                 Value::Void => unreachable!(),
-                Value::Break => unreachable!(),
+                Value::Break(_) => unreachable!(),
+                Value::Continue(_) => unreachable!(),
                 Value::CString(_) => todo!(),
             }
         }
+        for val in self.temporaries.iter().rev() {
+            match val {
+                Value::String(val) => {
+                    let release = module.get_function("release_string_reference").unwrap();
+                    builder.build_call(&release, &[*val], "");
+                }
+                Value::Vec { val, .. } => {
+                    let release = module.get_function("release_vec_reference").unwrap();
+                    builder.build_call(&release, &[*val], "");
+                }
+                Value::Closure { val, .. } | Value::Function { env: Some(val), .. } => {
+                    let release = module.get_function("release_env_reference").unwrap();
+                    builder.build_call(&release, &[*val], "");
+                }
+                Value::Struct { val, .. } | Value::Enum { val, .. } => {
+                    let release = module.get_function("release_struct_reference").unwrap();
+                    builder.build_call(&release, &[*val], "");
+                }
+                _ => unreachable!(),
+            }
+        }
         Ok(())
     }
 
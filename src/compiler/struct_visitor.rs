@@ -0,0 +1,211 @@
+use crate::expression::{self, Expression, Node};
+use crate::llvm;
+use crate::parser::{self, Param, Span};
+use crate::visitor::{FieldAccessVisitor, StructDeclVisitor, StructLiteralVisitor, Visitor};
+
+use super::{
+    conditional::{build_reference_call, reference_kind},
+    utils::{slot_kind, typed_value},
+    Compiler, CompilerError, CompilerResult, LLVMCompiler, Value,
+};
+
+impl Compiler {
+    fn struct_fields(&self, name: &str) -> CompilerResult<Vec<Param>> {
+        self.structs
+            .get(name)
+            .cloned()
+            .ok_or_else(|| CompilerError::UndefinedIdentifier(name.to_string()))
+    }
+
+    /// Pointer to the struct `object`, index of its `field` and the field type.
+    pub(super) fn lookup_field(
+        &self,
+        object: &Value,
+        field: &str,
+        span: Span,
+    ) -> CompilerResult<(llvm::Value, usize, parser::Type)> {
+        let (ptr, fields) = match object {
+            Value::Struct { val, name } => (Some(*val), self.struct_fields(name)?),
+            _ => (None, Vec::new()),
+        };
+        fields
+            .iter()
+            .position(|param| param.name == field)
+            .map(|idx| (ptr.unwrap(), idx, fields[idx].typ.clone()))
+            .ok_or_else(|| CompilerError::UndefinedField {
+                typ: object.get_type(),
+                field: field.to_string(),
+                span,
+            })
+    }
+
+    /// Compiles `node`, assigned to a field or element of `typ`. Functions are
+    /// only declared, the caller builds their body once they are stored.
+    pub(super) fn assigned_value(
+        &mut self,
+        node: &Node,
+        typ: &parser::Type,
+    ) -> CompilerResult<Value> {
+        match &node.expression {
+            Expression::FuncDecl(e) => Ok(self.declare_function(e)),
+            _ => self.walk_expecting(node, typ),
+        }
+    }
+
+    /// Converts `val` to the type of a field or payload, failing when they
+    /// don't agree.
+    pub(super) fn slot_value(
+        &mut self,
+        val: Value,
+        typ: &parser::Type,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        let val = self.make_closure(val);
+        if val.get_type() != *typ {
+            Err(CompilerError::TypeError {
+                expected: typ.clone(),
+                actual: val.get_type(),
+                span,
+            })?
+        }
+        Ok(val)
+    }
+
+    /// Stores `val` in slot `idx` of the struct or enum at `ptr`. The setters
+    /// hold a reference to heap values and release the value they replace.
+    pub(super) fn build_slot_store(&self, ptr: llvm::Value, idx: usize, val: &Value) {
+        let setter = format!("struct_set_{}", slot_kind(&val.get_type()));
+        let setter = self.module.get_function(&setter).unwrap();
+        let idx = self.context.const_double(idx as f64);
+        self.builder
+            .build_call(&setter, &[ptr, idx, val.into()], "");
+    }
+
+    /// Loads slot `idx` of the struct or enum at `ptr` as a value of `typ`,
+    /// without taking a reference to it.
+    pub(super) fn build_slot_load(&self, ptr: llvm::Value, idx: usize, typ: parser::Type) -> Value {
+        let getter = match typ {
+            parser::Type::Numeric => "struct_get_numeric",
+            parser::Type::Int => "struct_get_int",
+            parser::Type::Bool => "struct_get_bool",
+            _ => "struct_get_ptr",
+        };
+        let getter = self.module.get_function(getter).unwrap();
+        let idx = self.context.const_double(idx as f64);
+        let loaded = self.builder.build_call(&getter, &[ptr, idx], "");
+
+        typed_value(loaded, typ)
+    }
+
+    pub(super) fn build_field_assignment(
+        &mut self,
+        expr: &expression::FieldAccess,
+        right: &Node,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        let object = self.walk(&expr.object)?;
+        let (ptr, idx, typ) = self.lookup_field(&object, &expr.field, span.clone())?;
+        let right = self.assigned_value(right, &typ)?;
+        let val = self.slot_value(right.clone(), &typ, span)?;
+        self.build_slot_store(ptr, idx, &val);
+        Ok(right)
+    }
+}
+
+impl StructDeclVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_struct_decl(&mut self, expr: &expression::StructDecl) -> CompilerResult<Value> {
+        if expr
+            .fields
+            .iter()
+            .any(|param| param.typ == parser::Type::Void)
+        {
+            Err(CompilerError::VoidAssignment)?
+        }
+        self.structs.insert(expr.name.clone(), expr.fields.clone());
+        Ok(Value::Void)
+    }
+}
+
+impl StructLiteralVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_struct_literal(
+        &mut self,
+        expr: &expression::StructLiteral,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        let params = self.struct_fields(&expr.name)?;
+        let typ = parser::Type::Struct(expr.name.clone());
+
+        if let Some(field) = expr
+            .fields
+            .iter()
+            .find(|field| !params.iter().any(|param| param.name == field.name))
+        {
+            Err(CompilerError::UndefinedField {
+                typ: typ.clone(),
+                field: field.name.clone(),
+                span: field.value.span.clone(),
+            })?
+        }
+        if let Some(param) = params
+            .iter()
+            .find(|param| !expr.fields.iter().any(|field| field.name == param.name))
+        {
+            Err(CompilerError::MissingField {
+                typ,
+                field: param.name.clone(),
+                span,
+            })?
+        }
+
+        // fields are evaluated in the order they are written, but stored in
+        // the order they are declared
+        let mut values = Vec::new();
+        for field in &expr.fields {
+            let param = params
+                .iter()
+                .find(|param| param.name == field.name)
+                .unwrap();
+            let val = self.walk_expecting(&field.value, &param.typ)?;
+            let val = self.slot_value(val, &param.typ, field.value.span.clone())?;
+            values.push((&field.name, val));
+        }
+
+        let struct_new = self.module.get_function("struct_new").unwrap();
+        let ptr = self.builder.build_call(&struct_new, &[], "");
+        for (idx, param) in params.iter().enumerate() {
+            let (_, val) = values
+                .iter()
+                .find(|(name, _)| **name == param.name)
+                .unwrap();
+            self.build_slot_store(ptr, idx, val);
+        }
+
+        let val = Value::Struct {
+            val: ptr,
+            name: expr.name.clone(),
+        };
+        self.track_maybe_orphaned(val.clone());
+        Ok(val)
+    }
+}
+
+impl FieldAccessVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_field_access(
+        &mut self,
+        expr: &expression::FieldAccess,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        let object = self.walk(&expr.object)?;
+        let (ptr, idx, typ) = self.lookup_field(&object, &expr.field, span)?;
+
+        let val = self.build_slot_load(ptr, idx, typ);
+
+        // the field may be reassigned while its value is still in use
+        if reference_kind(&val).is_some() {
+            build_reference_call(self, "inc", &val);
+            self.track_maybe_orphaned(val.clone());
+        }
+
+        Ok(val)
+    }
+}
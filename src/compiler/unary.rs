@@ -4,7 +4,7 @@ use crate::{
     visitor::{UnaryVisitor, Visitor},
 };
 
-use super::{Compiler, CompilerError, CompilerResult, Value};
+use super::{Compiler, CompilerError, CompilerResult, LLVMCompiler, Value};
 
 impl UnaryVisitor<CompilerResult<Value>> for Compiler {
     fn visit_unary(
@@ -13,17 +13,36 @@ impl UnaryVisitor<CompilerResult<Value>> for Compiler {
         span: Span,
     ) -> CompilerResult<Value> {
         match &expr.operator {
-            expression::Operator::Minus => {
+            expression::Operator::Minus => match self.walk(&expr.right)? {
+                Value::Numeric(p) => Ok(Value::Numeric(self.builder.build_fneg(p, ""))),
+                Value::Int(p) => Ok(Value::Int(self.builder.build_neg(p, ""))),
+                val => Err(CompilerError::TypeError {
+                    expected: parser::Type::Numeric,
+                    actual: val.get_type(),
+                    span,
+                })?,
+            },
+            expression::Operator::Not => {
                 let r = match self.walk(&expr.right)? {
-                    Value::Numeric(p) => p,
+                    Value::Bool(p) => p,
                     val => Err(CompilerError::TypeError {
-                        expected: parser::Type::Numeric,
+                        expected: parser::Type::Bool,
                         actual: val.get_type(),
                         span,
                     })?,
                 };
 
-                Ok(Value::Numeric(self.builder.build_fneg(r, "")))
+                Ok(Value::Bool(self.builder.build_not(r, "")))
+            }
+            expression::Operator::BitNot => {
+                match self.walk_expecting(&expr.right, &parser::Type::Int)? {
+                    Value::Int(p) => Ok(Value::Int(self.builder.build_not(p, ""))),
+                    val => Err(CompilerError::TypeError {
+                        expected: parser::Type::Int,
+                        actual: val.get_type(),
+                        span,
+                    })?,
+                }
             }
             operator => Err(CompilerError::WrongOperator {
                 expected: expression::Operator::Minus,
@@ -1,17 +1,113 @@
 use crate::{
+    expression::{Expression, Node, Operator},
     llvm::{self, Context},
     parser,
 };
 
+use super::Value;
+
 pub fn get_llvm_type(context: &Context, typ: &parser::Type) -> llvm::Type {
     match typ {
-        parser::Type::Vector => context.void_type().pointer_type(0),
+        parser::Type::Vector(_) => context.void_type().pointer_type(0),
         parser::Type::Numeric => context.double_type(),
-        parser::Type::Function => todo!(),
+        parser::Type::Int => context.i64_type(),
+        parser::Type::Function { .. } => context.void_type().pointer_type(0),
         parser::Type::Void => context.void_type(),
         parser::Type::Ptr => context.void_type().pointer_type(0),
         parser::Type::String => context.void_type().pointer_type(0),
         parser::Type::Bool => context.i1_type(),
         parser::Type::CString => context.i8_type().pointer_type(0),
+        parser::Type::Struct(_) | parser::Type::Enum(_) => context.void_type().pointer_type(0),
+    }
+}
+
+pub fn get_llvm_function_type(
+    context: &Context,
+    params: &[parser::Type],
+    return_type: &parser::Type,
+) -> llvm::Type {
+    let params: Vec<llvm::Type> = params
+        .iter()
+        .map(|typ| get_llvm_type(context, typ))
+        .collect();
+
+    context.function_type(get_llvm_type(context, return_type), &params, false)
+}
+
+/// Value of an expression made only of integer literals, which is an int where
+/// one is expected and a number anywhere else. Folded like the int operators
+/// would compute it, except for divisions that would abort.
+pub fn int_literal(node: &Node) -> Option<i64> {
+    match &node.expression {
+        Expression::Integer(n) => Some(*n),
+        Expression::Grouping(expr) => int_literal(&expr.0),
+        Expression::Unary(expr) => {
+            let right = int_literal(&expr.right)?;
+            match expr.operator {
+                Operator::Minus => Some(right.wrapping_neg()),
+                Operator::BitNot => Some(!right),
+                _ => None,
+            }
+        }
+        Expression::Binary(expr) => {
+            let (lhs, rhs) = (int_literal(&expr.left)?, int_literal(&expr.right)?);
+            match expr.operator {
+                Operator::Plus => Some(lhs.wrapping_add(rhs)),
+                Operator::Minus => Some(lhs.wrapping_sub(rhs)),
+                Operator::Asterisk => Some(lhs.wrapping_mul(rhs)),
+                Operator::Slash => lhs.checked_div(rhs),
+                Operator::Mod => lhs.checked_rem(rhs),
+                Operator::BitAnd => Some(lhs & rhs),
+                Operator::BitOr => Some(lhs | rhs),
+                Operator::BitXor => Some(lhs ^ rhs),
+                Operator::ShiftLeft => Some(lhs.wrapping_shl(rhs as u32 & 63)),
+                Operator::ShiftRight => Some(lhs.wrapping_shr(rhs as u32 & 63)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Suffix of the stdlib functions storing a struct field or vector element of
+/// type `typ`.
+pub fn slot_kind(typ: &parser::Type) -> &'static str {
+    match typ {
+        parser::Type::Numeric => "numeric",
+        parser::Type::Int => "int",
+        parser::Type::Bool => "bool",
+        parser::Type::String => "string",
+        parser::Type::Vector(_) => "vec",
+        parser::Type::Function { .. } => "env",
+        parser::Type::Struct(_) | parser::Type::Enum(_) => "struct",
+        parser::Type::Ptr | parser::Type::CString => "ptr",
+        parser::Type::Void => unreachable!(),
+    }
+}
+
+/// Wraps a loaded llvm value of the rock type `typ`.
+pub fn typed_value(val: llvm::Value, typ: parser::Type) -> Value {
+    match typ {
+        parser::Type::Numeric => Value::Numeric(val),
+        parser::Type::Int => Value::Int(val),
+        parser::Type::Bool => Value::Bool(val),
+        parser::Type::String => Value::String(val),
+        parser::Type::Vector(element) => Value::Vec {
+            val,
+            element: *element,
+        },
+        parser::Type::Ptr => Value::Ptr(val),
+        parser::Type::CString => Value::CString(val),
+        parser::Type::Struct(name) => Value::Struct { val, name },
+        parser::Type::Enum(name) => Value::Enum { val, name },
+        parser::Type::Function {
+            params,
+            return_type,
+        } => Value::Closure {
+            val,
+            return_type: *return_type,
+            params,
+        },
+        parser::Type::Void => unreachable!(),
     }
 }
@@ -2,32 +2,55 @@ use crate::llvm::Context;
 use crate::llvm::{self};
 use crate::parser;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Value {
     Void,
     String(llvm::Value),
     CString(llvm::Value),
     Numeric(llvm::Value),
+    Int(llvm::Value),
     Bool(llvm::Value),
     Function {
         val: llvm::Function,
         typ: llvm::Type,
         return_type: parser::Type,
+        params: Vec<parser::Type>,
+        env: Option<llvm::Value>,
     },
-    Vec(llvm::Value),
-    Break,
+    Closure {
+        val: llvm::Value,
+        return_type: parser::Type,
+        params: Vec<parser::Type>,
+    },
+    Vec {
+        val: llvm::Value,
+        element: parser::Type,
+    },
+    Struct {
+        val: llvm::Value,
+        name: String,
+    },
+    Enum {
+        val: llvm::Value,
+        name: String,
+    },
+    Break(usize),
+    Continue(usize),
     Ptr(llvm::Value),
 }
 
 impl From<Value> for llvm::Value {
     fn from(v: Value) -> Self {
         match v {
-            Value::Void | Value::Break => unreachable!(),
+            Value::Void | Value::Break(_) | Value::Continue(_) => unreachable!(),
             Value::String(lv) => lv,
             Value::Numeric(lv) => lv,
+            Value::Int(lv) => lv,
             Value::Bool(lv) => lv,
             Value::Function { val, .. } => llvm::Value(val.0),
-            Value::Vec(lv) => lv,
+            Value::Closure { val, .. } => val,
+            Value::Vec { val, .. } => val,
+            Value::Struct { val, .. } | Value::Enum { val, .. } => val,
             Value::Ptr(lv) => lv,
             Value::CString(_) => todo!(),
         }
@@ -37,12 +60,15 @@ impl From<Value> for llvm::Value {
 impl From<&Value> for llvm::Value {
     fn from(v: &Value) -> Self {
         match *v {
-            Value::Void | Value::Break => unreachable!(),
+            Value::Void | Value::Break(_) | Value::Continue(_) => unreachable!(),
             Value::String(lv) => lv,
             Value::Numeric(lv) => lv,
+            Value::Int(lv) => lv,
             Value::Bool(lv) => lv,
             Value::Function { val, .. } => llvm::Value(val.0),
-            Value::Vec(lv) => lv,
+            Value::Closure { val, .. } => val,
+            Value::Vec { val, .. } => val,
+            Value::Struct { val, .. } | Value::Enum { val, .. } => val,
             Value::Ptr(lv) => lv,
             Value::CString(_) => todo!(),
         }
@@ -53,25 +79,43 @@ impl Value {
     pub fn llvm_type(&self, context: &Context) -> llvm::Type {
         match self {
             Value::Numeric(_) => context.double_type(),
+            Value::Int(_) => context.i64_type(),
             Value::Bool(_) => context.i1_type(),
             Value::Ptr(_) => context.void_type().pointer_type(0),
             Value::String(_) => context.void_type().pointer_type(0),
-            Value::Vec(_) => context.void_type().pointer_type(0),
+            Value::Vec { .. } => context.void_type().pointer_type(0),
+            Value::Struct { .. } | Value::Enum { .. } => context.void_type().pointer_type(0),
             Value::Function { typ, .. } => typ.pointer_type(0),
-            Value::Void | Value::Break => unreachable!(),
+            Value::Closure { .. } => context.void_type().pointer_type(0),
+            Value::Void | Value::Break(_) | Value::Continue(_) => unreachable!(),
             Value::CString(_) => todo!(),
         }
     }
 
     pub fn get_type(&self) -> parser::Type {
         match self {
-            Value::Void | Value::Break => parser::Type::Void,
+            Value::Void | Value::Break(_) | Value::Continue(_) => parser::Type::Void,
             Value::Numeric(_) => parser::Type::Numeric,
+            Value::Int(_) => parser::Type::Int,
             Value::Bool(_) => parser::Type::Bool,
             Value::Ptr(_) => parser::Type::Ptr,
             Value::String(_) => parser::Type::String,
-            Value::Vec(_) => parser::Type::Vector,
-            Value::Function { .. } => parser::Type::Function,
+            Value::Vec { element, .. } => parser::Type::Vector(Box::new(element.clone())),
+            Value::Struct { name, .. } => parser::Type::Struct(name.clone()),
+            Value::Enum { name, .. } => parser::Type::Enum(name.clone()),
+            Value::Function {
+                return_type,
+                params,
+                ..
+            }
+            | Value::Closure {
+                return_type,
+                params,
+                ..
+            } => parser::Type::Function {
+                params: params.clone(),
+                return_type: Box::new(return_type.clone()),
+            },
             Value::CString(_) => parser::Type::CString,
         }
     }
@@ -3,17 +3,36 @@ use crate::{
     parser,
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Variable {
     String(llvm::Value),
     Numeric(llvm::Value),
+    Int(llvm::Value),
     Bool(llvm::Value),
     Function {
         val: llvm::Function,
         typ: llvm::Type,
         return_type: parser::Type,
+        params: Vec<parser::Type>,
+        env: Option<llvm::Value>,
+    },
+    Closure {
+        val: llvm::Value,
+        return_type: parser::Type,
+        params: Vec<parser::Type>,
+    },
+    Vec {
+        val: llvm::Value,
+        element: parser::Type,
+    },
+    Struct {
+        val: llvm::Value,
+        name: String,
+    },
+    Enum {
+        val: llvm::Value,
+        name: String,
     },
-    Vec(llvm::Value),
     Ptr(llvm::Value),
 }
 
@@ -22,9 +41,12 @@ impl From<Variable> for llvm::Value {
         match v {
             Variable::String(lv) => lv,
             Variable::Numeric(lv) => lv,
+            Variable::Int(lv) => lv,
             Variable::Bool(lv) => lv,
             Variable::Function { val, .. } => llvm::Value(val.0),
-            Variable::Vec(lv) => lv,
+            Variable::Closure { val, .. } => val,
+            Variable::Vec { val, .. } => val,
+            Variable::Struct { val, .. } | Variable::Enum { val, .. } => val,
             Variable::Ptr(lv) => lv,
         }
     }
@@ -35,9 +57,12 @@ impl From<&Variable> for llvm::Value {
         match *v {
             Variable::String(lv) => lv,
             Variable::Numeric(lv) => lv,
+            Variable::Int(lv) => lv,
             Variable::Bool(lv) => lv,
             Variable::Function { val, .. } => llvm::Value(val.0),
-            Variable::Vec(lv) => lv,
+            Variable::Closure { val, .. } => val,
+            Variable::Vec { val, .. } => val,
+            Variable::Struct { val, .. } | Variable::Enum { val, .. } => val,
             Variable::Ptr(lv) => lv,
         }
     }
@@ -46,22 +71,40 @@ impl Variable {
     pub fn llvm_type(&self, context: &Context) -> llvm::Type {
         match self {
             Variable::Numeric(_) => context.double_type(),
+            Variable::Int(_) => context.i64_type(),
             Variable::Bool(_) => context.i1_type(),
             Variable::Ptr(_) => context.void_type().pointer_type(0),
             Variable::String(_) => context.void_type().pointer_type(0),
-            Variable::Vec(_) => context.void_type().pointer_type(0),
+            Variable::Vec { .. } => context.void_type().pointer_type(0),
+            Variable::Struct { .. } | Variable::Enum { .. } => context.void_type().pointer_type(0),
             Variable::Function { typ, .. } => typ.pointer_type(0),
+            Variable::Closure { .. } => context.void_type().pointer_type(0),
         }
     }
 
     pub fn get_type(&self) -> parser::Type {
         match self {
             Variable::Numeric(_) => parser::Type::Numeric,
+            Variable::Int(_) => parser::Type::Int,
             Variable::Bool(_) => parser::Type::Bool,
             Variable::Ptr(_) => parser::Type::Ptr,
             Variable::String(_) => parser::Type::String,
-            Variable::Vec(_) => parser::Type::Vector,
-            Variable::Function { .. } => parser::Type::Function,
+            Variable::Vec { element, .. } => parser::Type::Vector(Box::new(element.clone())),
+            Variable::Struct { name, .. } => parser::Type::Struct(name.clone()),
+            Variable::Enum { name, .. } => parser::Type::Enum(name.clone()),
+            Variable::Function {
+                return_type,
+                params,
+                ..
+            }
+            | Variable::Closure {
+                return_type,
+                params,
+                ..
+            } => parser::Type::Function {
+                params: params.clone(),
+                return_type: Box::new(return_type.clone()),
+            },
         }
     }
 
@@ -73,14 +116,23 @@ impl Variable {
             Variable::Numeric(v) => {
                 v.0 = ptr.0;
             }
+            Variable::Int(v) => {
+                v.0 = ptr.0;
+            }
             Variable::Bool(v) => {
                 v.0 = ptr.0;
             }
             Variable::Function { val, .. } => {
                 val.0 = ptr.0;
             }
-            Variable::Vec(v) => {
-                v.0 = ptr.0;
+            Variable::Closure { val, .. } => {
+                val.0 = ptr.0;
+            }
+            Variable::Vec { val, .. } => {
+                val.0 = ptr.0;
+            }
+            Variable::Struct { val, .. } | Variable::Enum { val, .. } => {
+                val.0 = ptr.0;
             }
             Variable::Ptr(v) => {
                 v.0 = ptr.0;
@@ -0,0 +1,430 @@
+use crate::expression::{self, Node};
+use crate::llvm::{self, Cmp};
+use crate::parser::{self, Span};
+use crate::visitor::{VecLiteralVisitor, Visitor};
+
+use super::{
+    conditional::{build_reference_call, reference_kind},
+    utils::{slot_kind, typed_value},
+    Compiler, CompilerError, CompilerResult, LLVMCompiler, Value,
+};
+
+/// Builtins taking vectors of any element type. Calls to them are lowered to
+/// the stdlib functions for the type of the elements.
+pub(super) const VEC_FUNCTIONS: [&str; 14] = [
+    "vec_new",
+    "vec_len",
+    "vec_get",
+    "vec_set",
+    "vec_push",
+    "vec_pop",
+    "vec_insert",
+    "vec_remove",
+    "vec_slice",
+    "vec_concat",
+    "vec_sort",
+    "vec_reverse",
+    "vec_contains",
+    "vec_index_of",
+];
+
+/// Element types that can be ordered and compared by the stdlib.
+fn is_comparable(element: &parser::Type) -> bool {
+    matches!(
+        element,
+        parser::Type::Numeric | parser::Type::Int | parser::Type::Bool | parser::Type::String
+    )
+}
+
+impl Compiler {
+    pub(super) fn build_vec_new(&mut self, element: parser::Type) -> Value {
+        let vec_new = self.module.get_function("vec_new").unwrap();
+        let val = Value::Vec {
+            val: self.builder.build_call(&vec_new, &[], ""),
+            element,
+        };
+        self.track_maybe_orphaned(val.clone());
+        val
+    }
+
+    pub(super) fn build_vec_len(&self, vec: llvm::Value) -> llvm::Value {
+        let vec_len = self.module.get_function("vec_len").unwrap();
+        self.builder.build_call(&vec_len, &[vec], "")
+    }
+
+    /// Loads the element at `idx` of `vec` as a value of `element`, without
+    /// taking a reference to it.
+    pub(super) fn build_vec_get(
+        &self,
+        vec: llvm::Value,
+        idx: llvm::Value,
+        element: parser::Type,
+    ) -> Value {
+        let getter = match element {
+            parser::Type::Numeric => "vec_get_numeric",
+            parser::Type::Int => "vec_get_int",
+            parser::Type::Bool => "vec_get_bool",
+            _ => "vec_get_ptr",
+        };
+        let getter = self.module.get_function(getter).unwrap();
+        let loaded = self.builder.build_call(&getter, &[vec, idx], "");
+
+        typed_value(loaded, element)
+    }
+
+    /// Stores `val` at `idx` of `vec`. The setters hold a reference to heap
+    /// values and release the element they replace.
+    pub(super) fn build_vec_set(&self, vec: llvm::Value, idx: llvm::Value, val: &Value) {
+        let setter = format!("vec_set_{}", slot_kind(&val.get_type()));
+        let setter = self.module.get_function(&setter).unwrap();
+        self.builder
+            .build_call(&setter, &[vec, idx, val.into()], "");
+    }
+
+    /// Aborts with a runtime error pointing at `span` when `idx` is not a
+    /// valid position in the vector or string `object`, unless bounds checks
+    /// are turned off. `checker` is `vec_check_get` or `string_check_get` for
+    /// reads and `vec_check_set` or `string_check_set` for writes, which may
+    /// also append to `object`.
+    pub(super) fn build_index_check(
+        &self,
+        checker: &str,
+        object: llvm::Value,
+        idx: llvm::Value,
+        span: &Span,
+    ) {
+        if !self.bounds_checks {
+            return;
+        }
+        let checker = self.module.get_function(checker).unwrap();
+        let line = self.context.const_i64(span.line.into());
+        let column = self.context.const_i64(span.column.into());
+        self.builder
+            .build_call(&checker, &[object, idx, line, column], "");
+    }
+
+    /// Element at `idx` of `vec`, holding a reference to heap elements as they
+    /// may be replaced while still in use.
+    pub(super) fn build_vec_element(
+        &mut self,
+        vec: llvm::Value,
+        idx: llvm::Value,
+        element: parser::Type,
+    ) -> Value {
+        let val = self.build_vec_get(vec, idx, element);
+        if reference_kind(&val).is_some() {
+            build_reference_call(self, "inc", &val);
+            self.track_maybe_orphaned(val.clone());
+        }
+        val
+    }
+
+    /// Inserts `val` at `idx` of `vec`, shifting the elements after it.
+    fn build_vec_insert(&self, vec: llvm::Value, idx: llvm::Value, val: &Value) {
+        let inserter = format!("vec_insert_{}", slot_kind(&val.get_type()));
+        let inserter = self.module.get_function(&inserter).unwrap();
+        self.builder
+            .build_call(&inserter, &[vec, idx, val.into()], "");
+    }
+
+    /// Takes the element at `idx` out of `vec`, along with the reference the
+    /// vector held to it.
+    fn build_vec_remove(
+        &mut self,
+        vec: llvm::Value,
+        idx: llvm::Value,
+        element: parser::Type,
+    ) -> Value {
+        let remover = match element {
+            parser::Type::Numeric => "vec_remove_numeric",
+            parser::Type::Int => "vec_remove_int",
+            parser::Type::Bool => "vec_remove_bool",
+            _ => "vec_remove_ptr",
+        };
+        let remover = self.module.get_function(remover).unwrap();
+        let removed = self.builder.build_call(&remover, &[vec, idx], "");
+
+        let val = typed_value(removed, element);
+        if reference_kind(&val).is_some() {
+            self.track_maybe_orphaned(val.clone());
+        }
+        val
+    }
+
+    /// Position of the first element of `vec` equal to `val`, -1 if there is
+    /// none.
+    fn build_vec_index_of(&self, vec: llvm::Value, val: &Value) -> llvm::Value {
+        let finder = format!("vec_index_of_{}", slot_kind(&val.get_type()));
+        let finder = self.module.get_function(&finder).unwrap();
+        self.builder.build_call(&finder, &[vec, val.into()], "")
+    }
+
+    /// Builds a call to a stdlib function returning a new vector of `element`.
+    fn build_new_vec_call(
+        &mut self,
+        name: &str,
+        args: &[llvm::Value],
+        element: parser::Type,
+    ) -> Value {
+        let fun = self.module.get_function(name).unwrap();
+        let val = Value::Vec {
+            val: self.builder.build_call(&fun, args, ""),
+            element,
+        };
+        self.track_maybe_orphaned(val.clone());
+        val
+    }
+
+    fn vec_arg(&mut self, node: &Node) -> CompilerResult<(llvm::Value, parser::Type)> {
+        match self.walk(node)? {
+            Value::Vec { val, element } => Ok((val, element)),
+            val => Err(CompilerError::TypeError {
+                expected: parser::Type::Vector(Box::new(parser::Type::Numeric)),
+                actual: val.get_type(),
+                span: node.span.clone(),
+            }),
+        }
+    }
+
+    /// Vector whose elements can be ordered and compared.
+    fn comparable_vec_arg(&mut self, node: &Node) -> CompilerResult<(llvm::Value, parser::Type)> {
+        let (vec, element) = self.vec_arg(node)?;
+        if !is_comparable(&element) {
+            Err(CompilerError::TypeError {
+                expected: parser::Type::Vector(Box::new(parser::Type::Numeric)),
+                actual: parser::Type::Vector(Box::new(element.clone())),
+                span: node.span.clone(),
+            })?
+        }
+        Ok((vec, element))
+    }
+
+    fn element_arg(&mut self, node: &Node, element: &parser::Type) -> CompilerResult<Value> {
+        let val = self.walk_expecting(node, element)?;
+        self.slot_value(val, element, node.span.clone())
+    }
+
+    pub(super) fn index_arg(&mut self, node: &Node) -> CompilerResult<llvm::Value> {
+        match self.walk(node)? {
+            Value::Numeric(idx) => Ok(idx),
+            // the stdlib takes positions as numbers, which hold any int that
+            // can be a position exactly
+            Value::Int(idx) => Ok(self
+                .builder
+                .build_si_to_fp(idx, self.context.double_type(), "")),
+            val => Err(CompilerError::TypeError {
+                expected: parser::Type::Numeric,
+                actual: val.get_type(),
+                span: node.span.clone(),
+            }),
+        }
+    }
+
+    pub(super) fn compile_vec_builtin(
+        &mut self,
+        name: &str,
+        expr: &expression::FuncCall,
+        span: Span,
+    ) -> CompilerResult<Value> {
+        let (params, type_params) = match name {
+            "vec_new" => (0, 1),
+            "vec_len" => (1, 0),
+            "vec_get" => (2, 0),
+            "vec_set" => (3, 0),
+            "vec_push" => (2, 0),
+            "vec_pop" => (1, 0),
+            "vec_insert" => (3, 0),
+            "vec_remove" => (2, 0),
+            "vec_slice" => (3, 0),
+            "vec_concat" => (2, 0),
+            "vec_sort" => (1, 0),
+            "vec_reverse" => (1, 0),
+            "vec_contains" => (2, 0),
+            "vec_index_of" => (2, 0),
+            _ => unreachable!(),
+        };
+        if expr.type_args.len() > type_params {
+            Err(CompilerError::WrongTypeArgumentCount {
+                expected: type_params,
+                actual: expr.type_args.len(),
+                span: span.clone(),
+            })?
+        }
+        if expr.args.len() != params {
+            Err(CompilerError::WrongArgumentCount {
+                expected: params,
+                actual: expr.args.len(),
+                span: span.clone(),
+            })?
+        }
+
+        match (name, expr.args.as_slice()) {
+            // a vector of numbers unless told otherwise
+            ("vec_new", []) => match expr.type_args.first() {
+                Some(parser::Type::Void) => Err(CompilerError::VoidAssignment),
+                Some(element) => Ok(self.build_vec_new(element.clone())),
+                None => Ok(self.build_vec_new(parser::Type::Numeric)),
+            },
+            ("vec_len", [vec]) => {
+                let (vec, _) = self.vec_arg(vec)?;
+                Ok(Value::Numeric(self.build_vec_len(vec)))
+            }
+            ("vec_get", [vec, idx]) => {
+                let (vec, element) = self.vec_arg(vec)?;
+                let span = idx.span.clone();
+                let idx = self.index_arg(idx)?;
+                self.build_index_check("vec_check_get", vec, idx, &span);
+                Ok(self.build_vec_element(vec, idx, element))
+            }
+            ("vec_set", [vec, idx, val]) => {
+                let (vec, element) = self.vec_arg(vec)?;
+                let span = idx.span.clone();
+                let idx = self.index_arg(idx)?;
+                let val = self.element_arg(val, &element)?;
+                self.build_index_check("vec_check_set", vec, idx, &span);
+                self.build_vec_set(vec, idx, &val);
+                Ok(Value::Void)
+            }
+            ("vec_push", [vec, val]) => {
+                let (vec, element) = self.vec_arg(vec)?;
+                let val = self.element_arg(val, &element)?;
+                let len = self.build_vec_len(vec);
+                self.build_vec_insert(vec, len, &val);
+                Ok(Value::Void)
+            }
+            ("vec_pop", [vec]) => {
+                let (vec, element) = self.vec_arg(vec)?;
+                let len = self.build_vec_len(vec);
+                let last = self
+                    .builder
+                    .build_fsub(len, self.context.const_double(1.), "");
+                self.build_index_check("vec_check_get", vec, last, &span);
+                Ok(self.build_vec_remove(vec, last, element))
+            }
+            ("vec_insert", [vec, idx, val]) => {
+                let (vec, element) = self.vec_arg(vec)?;
+                let span = idx.span.clone();
+                let idx = self.index_arg(idx)?;
+                let val = self.element_arg(val, &element)?;
+                self.build_index_check("vec_check_set", vec, idx, &span);
+                self.build_vec_insert(vec, idx, &val);
+                Ok(Value::Void)
+            }
+            ("vec_remove", [vec, idx]) => {
+                let (vec, element) = self.vec_arg(vec)?;
+                let span = idx.span.clone();
+                let idx = self.index_arg(idx)?;
+                self.build_index_check("vec_check_get", vec, idx, &span);
+                Ok(self.build_vec_remove(vec, idx, element))
+            }
+            ("vec_slice", [vec, start, end]) => {
+                let (vec, element) = self.vec_arg(vec)?;
+                let (start_span, end_span) = (start.span.clone(), end.span.clone());
+                let start = self.index_arg(start)?;
+                let end = self.index_arg(end)?;
+                // the bounds are positions of elements or the one after the last
+                self.build_index_check("vec_check_set", vec, start, &start_span);
+                self.build_index_check("vec_check_set", vec, end, &end_span);
+                if self.bounds_checks {
+                    let checker = self.module.get_function("vec_check_slice").unwrap();
+                    let line = self.context.const_i64(end_span.line.into());
+                    let column = self.context.const_i64(end_span.column.into());
+                    self.builder
+                        .build_call(&checker, &[start, end, line, column], "");
+                }
+                Ok(self.build_new_vec_call("vec_slice", &[vec, start, end], element))
+            }
+            ("vec_concat", [lhs, rhs]) => {
+                let (lhs, element) = self.vec_arg(lhs)?;
+                let span = rhs.span.clone();
+                let (rhs, rhs_element) = self.vec_arg(rhs)?;
+                if rhs_element != element {
+                    Err(CompilerError::TypeError {
+                        expected: parser::Type::Vector(Box::new(element.clone())),
+                        actual: parser::Type::Vector(Box::new(rhs_element)),
+                        span,
+                    })?
+                }
+                Ok(self.build_new_vec_call("vec_concat", &[lhs, rhs], element))
+            }
+            ("vec_sort", [vec]) => {
+                let (vec, _) = self.comparable_vec_arg(vec)?;
+                let vec_sort = self.module.get_function("vec_sort").unwrap();
+                self.builder.build_call(&vec_sort, &[vec], "");
+                Ok(Value::Void)
+            }
+            ("vec_reverse", [vec]) => {
+                let (vec, _) = self.vec_arg(vec)?;
+                let vec_reverse = self.module.get_function("vec_reverse").unwrap();
+                self.builder.build_call(&vec_reverse, &[vec], "");
+                Ok(Value::Void)
+            }
+            ("vec_contains", [vec, val]) => {
+                let (vec, element) = self.comparable_vec_arg(vec)?;
+                let val = self.element_arg(val, &element)?;
+                let idx = self.build_vec_index_of(vec, &val);
+                Ok(Value::Bool(self.builder.build_fcmp(
+                    idx,
+                    self.context.const_double(0.),
+                    Cmp::GreaterOrEqual,
+                    "",
+                )))
+            }
+            ("vec_index_of", [vec, val]) => {
+                let (vec, element) = self.comparable_vec_arg(vec)?;
+                let val = self.element_arg(val, &element)?;
+                Ok(Value::Numeric(self.build_vec_index_of(vec, &val)))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Compiles a vector literal whose elements are of `expected` when given.
+    /// Otherwise they are of the type of the first one and an empty literal is
+    /// a vector of numbers.
+    pub(super) fn compile_vec_literal(
+        &mut self,
+        expr: &expression::VecLiteral,
+        expected: Option<&parser::Type>,
+    ) -> CompilerResult<Value> {
+        let mut values = Vec::new();
+        let mut element = expected.cloned();
+        for node in &expr.elements {
+            let val = match &element {
+                Some(element) => self.walk_expecting(node, element)?,
+                None => self.walk(node)?,
+            };
+            let val = self.make_closure(val);
+            element.get_or_insert_with(|| val.get_type());
+            values.push((val, node.span.clone()));
+        }
+        let element = match element {
+            Some(parser::Type::Void) => Err(CompilerError::VoidAssignment)?,
+            Some(element) => element,
+            None => parser::Type::Numeric,
+        };
+
+        let mut elements = Vec::new();
+        for (val, span) in values {
+            elements.push(self.slot_value(val, &element, span)?);
+        }
+
+        let vec = self.build_vec_new(element);
+        let vec_ptr: llvm::Value = (&vec).into();
+        for (idx, val) in elements.iter().enumerate() {
+            let idx = self.context.const_double(idx as f64);
+            self.build_vec_set(vec_ptr, idx, val);
+        }
+        Ok(vec)
+    }
+}
+
+impl VecLiteralVisitor<CompilerResult<Value>> for Compiler {
+    fn visit_vec_literal(
+        &mut self,
+        expr: &expression::VecLiteral,
+        _span: Span,
+    ) -> CompilerResult<Value> {
+        self.compile_vec_literal(expr, None)
+    }
+}
@@ -24,8 +24,9 @@ impl WhileVisitor<CompilerResult<Value>> for Compiler {
         let fun = self.builder().get_insert_block().get_parent();
 
         let loop_block = self.context().append_basic_block(&fun, "loop");
+        let cond_block = self.context().append_basic_block(&fun, "loopcond");
         let after_loop_block = self.context().append_basic_block(&fun, "afterloop");
-        self.after_loop_blocks.push(after_loop_block);
+        self.push_loop(&expr.label, cond_block, after_loop_block, None);
 
         self.builder
             .build_cond_br(&predicate, &loop_block, &after_loop_block);
@@ -34,25 +35,28 @@ impl WhileVisitor<CompilerResult<Value>> for Compiler {
 
         self.enter_scope();
 
-        let mut is_break = false;
+        let mut jump = None;
         for stmt in &expr.body {
             self.release_maybe_orphaned();
-            if let Value::Break = self.walk(stmt)? {
-                is_break = true;
+            if let val @ (Value::Break(_) | Value::Continue(_)) = self.walk(stmt)? {
+                jump = Some(val);
                 break;
-            };
+            }
         }
         self.exit_scope().unwrap();
+        let exit = match jump {
+            Some(jump) => self.build_loop_exit(&jump)?,
+            None => cond_block,
+        };
+        self.builder.build_br(&exit);
 
+        self.builder.position_builder_at_end(&cond_block);
         let pred: llvm::Value = self.walk(&expr.predicate)?.into();
-        if is_break {
-            self.builder.build_br(&after_loop_block);
-        } else {
-            self.builder
-                .build_cond_br(&pred, &loop_block, &after_loop_block);
-        }
+        self.builder
+            .build_cond_br(&pred, &loop_block, &after_loop_block);
+
         self.builder.position_builder_at_end(&after_loop_block);
-        self.after_loop_blocks.pop();
+        self.pop_loop();
 
         Ok(Value::Void)
     }
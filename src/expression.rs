@@ -16,6 +16,15 @@ pub enum Operator {
     NotEqual,
     Greater,
     GreaterOrEqual,
+    And,
+    Or,
+    Not,
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    ShiftLeft,
+    ShiftRight,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -42,6 +51,108 @@ pub struct Assignment {
 pub struct While {
     pub predicate: Box<Node>,
     pub body: Vec<Node>,
+    pub label: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub enum Iterable {
+    Range { start: Box<Node>, end: Box<Node> },
+    Collection(Box<Node>),
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct For {
+    pub variable: String,
+    pub iterable: Iterable,
+    pub body: Vec<Node>,
+    pub label: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub enum Pattern {
+    Literal(Box<Node>),
+    Range {
+        start: Box<Node>,
+        end: Box<Node>,
+    },
+    Variant {
+        name: String,
+        variant: String,
+        bindings: Vec<String>,
+    },
+    Wildcard,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Box<Node>>,
+    pub body: Vec<Node>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Match {
+    pub subject: Box<Node>,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct StructDecl {
+    pub name: String,
+    pub fields: Vec<Param>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FieldValue {
+    pub name: String,
+    pub value: Node,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct StructLiteral {
+    pub name: String,
+    pub fields: Vec<FieldValue>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FieldAccess {
+    pub object: Box<Node>,
+    pub field: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Index {
+    pub object: Box<Node>,
+    pub index: Box<Node>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Interpolation {
+    pub parts: Vec<Node>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct VecLiteral {
+    pub elements: Vec<Node>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct VariantDecl {
+    pub name: String,
+    pub fields: Vec<Type>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EnumDecl {
+    pub name: String,
+    pub variants: Vec<VariantDecl>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EnumVariant {
+    pub name: String,
+    pub variant: String,
+    pub args: Vec<Node>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -53,6 +164,8 @@ pub struct Unary {
 #[derive(Serialize, Debug, Clone)]
 pub struct FuncCall {
     pub calee: Box<Node>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub type_args: Vec<Type>,
     pub args: Vec<Node>,
 }
 
@@ -75,15 +188,28 @@ pub struct Grouping(pub Box<Node>);
 
 #[derive(Serialize, Debug, Clone)]
 pub enum Expression {
-    Break,
+    Break(Option<String>),
+    Continue(Option<String>),
+    Return(Option<Box<Node>>),
     Bool(bool),
     String(String),
     Identifier(String),
     Numeric(f64),
+    Integer(i64),
     Conditional(Conditional),
     Assignment(Assignment),
     Binary(Binary),
     While(While),
+    For(For),
+    Match(Match),
+    StructDecl(StructDecl),
+    StructLiteral(StructLiteral),
+    FieldAccess(FieldAccess),
+    Index(Index),
+    VecLiteral(VecLiteral),
+    Interpolation(Interpolation),
+    EnumDecl(EnumDecl),
+    EnumVariant(EnumVariant),
     Unary(Unary),
     Grouping(Grouping),
     FuncCall(FuncCall),
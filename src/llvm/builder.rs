@@ -40,6 +40,10 @@ impl Builder {
         Value::from(unsafe { LLVMBuildBr(self.0, dest.0) })
     }
 
+    pub fn build_switch(&self, value: &Value, els: &BasicBlock, num_cases: u32) -> Value {
+        Value::from(unsafe { LLVMBuildSwitch(self.0, value.0, els.0, num_cases) })
+    }
+
     pub fn build_alloca(&self, el_type: Type, name: &str) -> Value {
         Value::from(unsafe { LLVMBuildAlloca(self.0, el_type.0, c_str(name).as_ptr()) })
     }
@@ -60,6 +64,50 @@ impl Builder {
         Value::from(unsafe { LLVMBuildAdd(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
     }
 
+    pub fn build_sub(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildSub(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_mul(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildMul(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_sdiv(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildSDiv(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_srem(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildSRem(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_neg(&self, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildNeg(self.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_and(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildAnd(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_or(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildOr(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_xor(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildXor(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_shl(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildShl(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_ashr(&self, lhs: Value, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildAShr(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_si_to_fp(&self, value: Value, dest_type: Type, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildSIToFP(self.0, value.0, dest_type.0, c_str(name).as_ptr()) })
+    }
+
     pub fn build_fadd(&self, lhs: Value, rhs: Value, name: &str) -> Value {
         Value::from(unsafe { LLVMBuildFAdd(self.0, lhs.0, rhs.0, c_str(name).as_ptr()) })
     }
@@ -103,6 +151,33 @@ impl Builder {
         })
     }
 
+    pub fn build_phi(&self, typ: Type, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildPhi(self.0, typ.0, c_str(name).as_ptr()) })
+    }
+
+    pub fn build_icmp(&self, lhs: Value, rhs: Value, operator: Cmp, name: &str) -> Value {
+        Value::from(unsafe {
+            LLVMBuildICmp(
+                self.0,
+                match operator {
+                    Cmp::LessOrEqual => llvm::LLVMIntPredicate::LLVMIntSLE,
+                    Cmp::Less => llvm::LLVMIntPredicate::LLVMIntSLT,
+                    Cmp::GreaterOrEqual => llvm::LLVMIntPredicate::LLVMIntSGE,
+                    Cmp::Greater => llvm::LLVMIntPredicate::LLVMIntSGT,
+                    Cmp::Equal => llvm::LLVMIntPredicate::LLVMIntEQ,
+                    Cmp::NotEqual => llvm::LLVMIntPredicate::LLVMIntNE,
+                },
+                lhs.0,
+                rhs.0,
+                c_str(name).as_ptr(),
+            )
+        })
+    }
+
+    pub fn build_not(&self, rhs: Value, name: &str) -> Value {
+        Value::from(unsafe { LLVMBuildNot(self.0, rhs.0, c_str(name).as_ptr()) })
+    }
+
     pub fn build_free(&self, value: Value) -> Value {
         Value::from(unsafe { LLVMBuildFree(self.0, value.0) })
     }
@@ -123,6 +198,10 @@ impl Builder {
         Value::from(unsafe { LLVMBuildRetVoid(self.0) })
     }
 
+    pub fn build_unreachable(&self) -> Value {
+        Value::from(unsafe { LLVMBuildUnreachable(self.0) })
+    }
+
     pub fn get_insert_block(&self) -> BasicBlock {
         BasicBlock(unsafe { LLVMGetInsertBlock(self.0) })
     }
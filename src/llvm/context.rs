@@ -119,6 +119,14 @@ impl Context {
         Value::from(unsafe { LLVMConstInt(self.u64_type().0, value, 1) })
     }
 
+    pub fn const_i64(&self, value: i64) -> Value {
+        Value::from(unsafe { LLVMConstInt(self.i64_type().0, value as u64, 1) })
+    }
+
+    pub fn const_null(&self, typ: Type) -> Value {
+        Value::from(unsafe { LLVMConstNull(typ.0) })
+    }
+
     pub fn const_bool(&self, value: bool) -> Value {
         Value::from(unsafe { LLVMConstInt(self.i1_type().0, if value { 1 } else { 0 }, 0) })
     }
@@ -7,7 +7,7 @@ use llvm_sys::{
 
 use super::{LLVMError, Value};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Function(pub *mut llvm::LLVMValue);
 
 impl Function {
@@ -3,7 +3,7 @@ use std::{
     ffi::{CStr, CString},
 };
 
-pub fn c_str(mut s: &str) -> Cow<CStr> {
+pub fn c_str(mut s: &str) -> Cow<'_, CStr> {
     if s.is_empty() {
         s = "\0";
     }
@@ -1,11 +1,15 @@
-use llvm::core::LLVMSetInitializer;
+use llvm::core::{LLVMAddCase, LLVMAddIncoming, LLVMIsNull, LLVMSetInitializer};
+
+use super::BasicBlock;
 
 extern crate llvm_sys as llvm;
 
+#[allow(dead_code)]
 trait LLVMValue {
     fn value(&self) -> *mut llvm::LLVMValue;
 }
 
+#[allow(dead_code)]
 trait Initializer<T: LLVMValue = Self>: LLVMValue {
     fn set_initializer(&self, value: T) {
         unsafe {
@@ -23,6 +27,31 @@ impl From<*mut llvm::LLVMValue> for Value {
     }
 }
 
+impl Value {
+    pub fn is_null(&self) -> bool {
+        unsafe { LLVMIsNull(self.0) != 0 }
+    }
+
+    pub fn add_case(&self, on: Value, dest: BasicBlock) {
+        unsafe { LLVMAddCase(self.0, on.0, dest.0) }
+    }
+
+    pub fn add_incoming(&self, incoming: &[(Value, BasicBlock)]) {
+        let mut values: Vec<*mut llvm::LLVMValue> = incoming.iter().map(|(v, _)| v.0).collect();
+        let mut blocks: Vec<*mut llvm::LLVMBasicBlock> =
+            incoming.iter().map(|(_, b)| b.0).collect();
+
+        unsafe {
+            LLVMAddIncoming(
+                self.0,
+                values.as_mut_ptr(),
+                blocks.as_mut_ptr(),
+                incoming.len().try_into().unwrap(),
+            )
+        }
+    }
+}
+
 impl LLVMValue for Value {
     fn value(&self) -> *mut llvm::LLVMValue {
         self.0
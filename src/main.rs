@@ -29,12 +29,14 @@ fn main() -> Result<()> {
     let mut dump_ir = false;
     let mut no_opt = false;
     let mut dump_ast = false;
+    let mut no_bounds_check = false;
 
     for arg in std::env::args() {
         match arg.as_str() {
             "--ir" => dump_ir = true,
             "--no-opt" => no_opt = true,
             "--ast" => dump_ast = true,
+            "--no-bounds-check" => no_bounds_check = true,
             _ => (),
         }
     }
@@ -57,6 +59,9 @@ fn main() -> Result<()> {
     if no_opt {
         compiler.turn_off_optimization();
     }
+    if no_bounds_check {
+        compiler.turn_off_bounds_checks();
+    }
 
     compiler.compile()?;
 
@@ -1,8 +1,9 @@
 use crate::expression::{self, Node};
 use crate::expression::{Expression, Operator};
-use crate::token::{Token, TokenKind};
+use crate::token::{StringPart, Token, TokenKind};
 use backtrace::Backtrace;
 use serde::Serialize;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Display;
 
@@ -23,6 +24,7 @@ macro_rules! consume {
 #[derive(Clone, Debug)]
 pub enum ParserError {
     SyntaxError { token: Token, backtrace: Backtrace },
+    UndefinedType { token: Token, backtrace: Backtrace },
 }
 impl Display for ParserError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
@@ -37,6 +39,12 @@ impl Display for ParserError {
                     token.kind, token.span
                 )
             }
+            ParserError::UndefinedType {
+                token,
+                backtrace: _,
+            } => {
+                write!(f, "Undefined type {} at {}", token.kind, token.span)
+            }
         }
     }
 }
@@ -44,29 +52,46 @@ impl Error for ParserError {}
 
 type Result<T> = std::result::Result<T, ParserError>;
 
-#[derive(Copy, Clone, Serialize, Debug)]
+#[derive(Clone, Serialize, Debug, PartialEq)]
 pub enum Type {
     Numeric,
+    Int,
     Bool,
-    Vector,
+    Vector(Box<Type>),
     Void,
-    Function,
+    Function {
+        params: Vec<Type>,
+        return_type: Box<Type>,
+    },
     Ptr,
     String,
     CString,
+    Struct(String),
+    Enum(String),
 }
 
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let name = match self {
             Type::Numeric => "Numeric",
+            Type::Int => "Int",
             Type::Bool => "Bool",
-            Type::Vector => "Vector",
+            Type::Vector(element) => return write!(f, "Vector<{}>", element),
             Type::Void => "Void",
-            Type::Function => "Function",
+            Type::Function {
+                params,
+                return_type,
+            } => {
+                write!(f, "Function<")?;
+                for param in params {
+                    write!(f, "{}, ", param)?;
+                }
+                return write!(f, "{}>", return_type);
+            }
             Type::Ptr => "Ptr",
             Type::String => "String",
             Type::CString => "CString",
+            Type::Struct(name) | Type::Enum(name) => name,
         };
         write!(f, "{}", name)
     }
@@ -90,6 +115,8 @@ pub trait Parse {
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    enums: HashSet<String>,
+    structs: HashSet<String>,
 }
 
 #[derive(Debug, Default, Serialize, Clone)]
@@ -118,14 +145,106 @@ impl Parse for Parser {
 
 impl Parser {
     pub fn new(tokens: &[Token]) -> Self {
+        // types may be named before they are declared
+        let mut enums = HashSet::new();
+        let mut structs = HashSet::new();
+        for pair in tokens.windows(2) {
+            match (&pair[0].kind, &pair[1].kind) {
+                (TokenKind::Enum, TokenKind::Identifier(name)) => enums.insert(name.clone()),
+                (TokenKind::Struct, TokenKind::Identifier(name)) => structs.insert(name.clone()),
+                _ => false,
+            };
+        }
         Parser {
             tokens: tokens.to_vec(),
             current: 0,
+            enums,
+            structs,
         }
     }
 
     fn expression(&mut self) -> Result<Node> {
-        self.while_loop()
+        self.labeled_loop()
+    }
+
+    fn labeled_loop(&mut self) -> Result<Node> {
+        let label = match &self.peek().kind {
+            TokenKind::Label(label) => label.clone(),
+            _ => return self.for_loop(),
+        };
+        self.advance();
+        consume!(self, TokenKind::Colon)?;
+
+        let mut node = match self.peek().kind {
+            TokenKind::For | TokenKind::While => self.for_loop()?,
+            _ => {
+                return Err(ParserError::SyntaxError {
+                    token: self.peek().clone(),
+                    backtrace: Backtrace::new(),
+                })
+            }
+        };
+        match &mut node.expression {
+            Expression::For(expr) => expr.label = Some(label),
+            Expression::While(expr) => expr.label = Some(label),
+            _ => unreachable!(),
+        }
+        Ok(node)
+    }
+
+    fn for_loop(&mut self) -> Result<Node> {
+        match self.peek().kind {
+            TokenKind::For => {
+                self.advance();
+
+                let variable = match &self.advance().kind {
+                    TokenKind::Identifier(name) => name.clone(),
+                    _ => {
+                        return Err(ParserError::SyntaxError {
+                            token: self.previous().clone(),
+                            backtrace: Backtrace::new(),
+                        })
+                    }
+                };
+                consume!(self, TokenKind::In)?;
+
+                let start = self.expression()?;
+                let iterable = match self.peek().kind {
+                    TokenKind::DotDot => {
+                        self.advance();
+                        expression::Iterable::Range {
+                            start: Box::new(start),
+                            end: Box::new(self.expression()?),
+                        }
+                    }
+                    _ => expression::Iterable::Collection(Box::new(start)),
+                };
+
+                consume!(self, TokenKind::LCurly)?;
+
+                let mut body: Vec<Node> = Vec::new();
+
+                loop {
+                    match self.peek().kind {
+                        TokenKind::RCurly => {
+                            self.advance();
+                            break;
+                        }
+                        _ => {
+                            body.push(self.expression()?);
+                        }
+                    }
+                }
+
+                Ok(self.node(Expression::For(expression::For {
+                    variable,
+                    iterable,
+                    body,
+                    label: None,
+                })))
+            }
+            _ => self.while_loop(),
+        }
     }
 
     fn while_loop(&mut self) -> Result<Node> {
@@ -161,6 +280,7 @@ impl Parser {
                 Ok(self.node(Expression::While(expression::While {
                     predicate: Box::new(predicate),
                     body,
+                    label: None,
                 })))
             }
             _ => self.conditional(),
@@ -178,6 +298,15 @@ impl Parser {
         match self.peek().kind {
             TokenKind::If => {
                 self.advance();
+                let pattern = match self.peek().kind {
+                    TokenKind::Let => {
+                        self.advance();
+                        let pattern = self.variant_pattern()?;
+                        consume!(self, TokenKind::Equal)?;
+                        Some(pattern)
+                    }
+                    _ => None,
+                };
                 let predicate = self.expression()?;
 
                 match self.advance().kind {
@@ -209,7 +338,21 @@ impl Parser {
                     self.advance();
 
                     match self.advance().kind {
-                        TokenKind::LCurly => (),
+                        TokenKind::If => {
+                            self.current -= 1;
+                            else_body.push(self.conditional()?);
+                        }
+                        TokenKind::LCurly => loop {
+                            match self.peek().kind {
+                                TokenKind::RCurly => {
+                                    self.advance();
+                                    break;
+                                }
+                                _ => {
+                                    else_body.push(self.expression()?);
+                                }
+                            }
+                        },
                         _ => {
                             return Err(ParserError::SyntaxError {
                                 token: self.previous().clone(),
@@ -217,36 +360,47 @@ impl Parser {
                             })
                         }
                     };
-
-                    loop {
-                        match self.peek().kind {
-                            TokenKind::RCurly => {
-                                self.advance();
-                                break;
-                            }
-                            _ => {
-                                else_body.push(self.expression()?);
-                            }
-                        }
-                    }
                 }
 
-                Ok(self.node(Expression::Conditional(expression::Conditional {
-                    predicate: Box::new(predicate),
-                    body,
-                    else_body,
-                })))
+                // `if let` is a match of the pattern with a wildcard arm for
+                // the else body
+                let expr = match pattern {
+                    Some(pattern) => Expression::Match(expression::Match {
+                        subject: Box::new(predicate),
+                        arms: vec![
+                            expression::MatchArm {
+                                pattern,
+                                guard: None,
+                                body,
+                            },
+                            expression::MatchArm {
+                                pattern: expression::Pattern::Wildcard,
+                                guard: None,
+                                body: else_body,
+                            },
+                        ],
+                    }),
+                    None => Expression::Conditional(expression::Conditional {
+                        predicate: Box::new(predicate),
+                        body,
+                        else_body,
+                    }),
+                };
+                Ok(self.node(expr))
             }
             _ => self.assignment(),
         }
     }
 
     fn assignment(&mut self) -> Result<Node> {
-        let mut expr = self.equality()?;
+        let mut expr = self.logical_or()?;
 
         while let TokenKind::Equal = self.peek().kind {
             self.advance();
-            let right = self.equality()?;
+            let right = match self.peek().kind {
+                TokenKind::If => self.conditional()?,
+                _ => self.logical_or()?,
+            };
             expr = self.node(Expression::Assignment(expression::Assignment {
                 left: Box::new(expr),
                 right: Box::new(right),
@@ -256,14 +410,94 @@ impl Parser {
         Ok(expr)
     }
 
+    fn logical_or(&mut self) -> Result<Node> {
+        let mut expr = self.logical_and()?;
+
+        while let TokenKind::Or = self.peek().kind {
+            self.advance();
+            let right = self.logical_and()?;
+            expr = self.node(Expression::Binary(expression::Binary {
+                left: Box::new(expr),
+                operator: Operator::Or,
+                right: Box::new(right),
+            }));
+        }
+
+        Ok(expr)
+    }
+
+    fn logical_and(&mut self) -> Result<Node> {
+        let mut expr = self.bitwise_or()?;
+
+        while let TokenKind::And = self.peek().kind {
+            self.advance();
+            let right = self.bitwise_or()?;
+            expr = self.node(Expression::Binary(expression::Binary {
+                left: Box::new(expr),
+                operator: Operator::And,
+                right: Box::new(right),
+            }));
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_or(&mut self) -> Result<Node> {
+        let mut expr = self.bitwise_xor()?;
+
+        while let TokenKind::Pipe = self.peek().kind {
+            self.advance();
+            let right = self.bitwise_xor()?;
+            expr = self.node(Expression::Binary(expression::Binary {
+                left: Box::new(expr),
+                operator: Operator::BitOr,
+                right: Box::new(right),
+            }));
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_xor(&mut self) -> Result<Node> {
+        let mut expr = self.bitwise_and()?;
+
+        while let TokenKind::Caret = self.peek().kind {
+            self.advance();
+            let right = self.bitwise_and()?;
+            expr = self.node(Expression::Binary(expression::Binary {
+                left: Box::new(expr),
+                operator: Operator::BitXor,
+                right: Box::new(right),
+            }));
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_and(&mut self) -> Result<Node> {
+        let mut expr = self.equality()?;
+
+        while let TokenKind::Ampersand = self.peek().kind {
+            self.advance();
+            let right = self.equality()?;
+            expr = self.node(Expression::Binary(expression::Binary {
+                left: Box::new(expr),
+                operator: Operator::BitAnd,
+                right: Box::new(right),
+            }));
+        }
+
+        Ok(expr)
+    }
+
     fn equality(&mut self) -> Result<Node> {
-        let mut expr = self.addition_or_modulo()?;
+        let mut expr = self.shift()?;
 
         loop {
             match self.peek().kind {
                 TokenKind::DoubleEqual => {
                     self.advance();
-                    let right = self.addition_or_modulo()?;
+                    let right = self.shift()?;
                     expr = self.node(Expression::Binary(expression::Binary {
                         left: Box::new(expr),
                         operator: Operator::Equal,
@@ -272,7 +506,7 @@ impl Parser {
                 }
                 TokenKind::NotEqual => {
                     self.advance();
-                    let right = self.addition_or_modulo()?;
+                    let right = self.shift()?;
                     expr = self.node(Expression::Binary(expression::Binary {
                         left: Box::new(expr),
                         operator: Operator::NotEqual,
@@ -281,7 +515,7 @@ impl Parser {
                 }
                 TokenKind::LessOrEqual => {
                     self.advance();
-                    let right = self.addition_or_modulo()?;
+                    let right = self.shift()?;
                     expr = self.node(Expression::Binary(expression::Binary {
                         left: Box::new(expr),
                         operator: Operator::LessOrEqual,
@@ -290,7 +524,7 @@ impl Parser {
                 }
                 TokenKind::Less => {
                     self.advance();
-                    let right = self.addition_or_modulo()?;
+                    let right = self.shift()?;
                     expr = self.node(Expression::Binary(expression::Binary {
                         left: Box::new(expr),
                         operator: Operator::Less,
@@ -299,7 +533,7 @@ impl Parser {
                 }
                 TokenKind::Greater => {
                     self.advance();
-                    let right = self.addition_or_modulo()?;
+                    let right = self.shift()?;
                     expr = self.node(Expression::Binary(expression::Binary {
                         left: Box::new(expr),
                         operator: Operator::Greater,
@@ -308,7 +542,7 @@ impl Parser {
                 }
                 TokenKind::GreaterOrEqual => {
                     self.advance();
-                    let right = self.addition_or_modulo()?;
+                    let right = self.shift()?;
                     expr = self.node(Expression::Binary(expression::Binary {
                         left: Box::new(expr),
                         operator: Operator::GreaterOrEqual,
@@ -322,6 +556,27 @@ impl Parser {
         Ok(expr)
     }
 
+    fn shift(&mut self) -> Result<Node> {
+        let mut expr = self.addition_or_modulo()?;
+
+        loop {
+            let operator = match self.peek().kind {
+                TokenKind::ShiftLeft => Operator::ShiftLeft,
+                TokenKind::ShiftRight => Operator::ShiftRight,
+                _ => break,
+            };
+            self.advance();
+            let right = self.addition_or_modulo()?;
+            expr = self.node(Expression::Binary(expression::Binary {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            }));
+        }
+
+        Ok(expr)
+    }
+
     fn addition_or_modulo(&mut self) -> Result<Node> {
         let mut expr = self.factor()?;
 
@@ -401,6 +656,22 @@ impl Parser {
                     right: Box::new(right),
                 })))
             }
+            TokenKind::Exclamation => {
+                self.advance();
+                let right = self.unary()?;
+                Ok(self.node(Expression::Unary(expression::Unary {
+                    operator: Operator::Not,
+                    right: Box::new(right),
+                })))
+            }
+            TokenKind::Tilde => {
+                self.advance();
+                let right = self.unary()?;
+                Ok(self.node(Expression::Unary(expression::Unary {
+                    operator: Operator::BitNot,
+                    right: Box::new(right),
+                })))
+            }
             _ => self.extern_stmt(),
         }
     }
@@ -410,33 +681,7 @@ impl Parser {
             TokenKind::Extern => {
                 self.advance();
 
-                if !matches!(self.advance().kind, TokenKind::Less) {
-                    return Err(ParserError::SyntaxError {
-                        token: self.previous().clone(),
-                        backtrace: Backtrace::new(),
-                    });
-                };
-
-                let mut types = vec![];
-
-                loop {
-                    let token = self.advance().clone();
-                    match token.kind {
-                        TokenKind::Identifier(ref s) => {
-                            types.push(self.type_from_literal(s)?);
-                        }
-                        TokenKind::Comma => (),
-                        TokenKind::Greater => {
-                            break;
-                        }
-                        _ => {
-                            return Err(ParserError::SyntaxError {
-                                token: self.previous().clone(),
-                                backtrace: Backtrace::new(),
-                            });
-                        }
-                    }
-                }
+                let (types, return_type) = self.signature()?;
 
                 if !matches!(self.advance().kind, TokenKind::LeftParen) {
                     return Err(ParserError::SyntaxError {
@@ -461,18 +706,8 @@ impl Parser {
                     });
                 };
 
-                let return_type = match types.last() {
-                    Some(v) => *v,
-                    _ => {
-                        return Err(ParserError::SyntaxError {
-                            token: self.previous().clone(),
-                            backtrace: Backtrace::new(),
-                        });
-                    }
-                };
-
                 Ok(self.node(Expression::Extern(expression::Extern {
-                    types: types[0..types.len() - 1].to_vec(),
+                    types,
                     return_type,
                     name,
                 })))
@@ -534,18 +769,8 @@ impl Parser {
                     }
                 }
 
-                let return_type = match &self.advance().kind {
-                    TokenKind::Identifier(type_literal) => match type_literal.as_str() {
-                        "number" => Type::Numeric,
-                        "vec" => Type::Vector,
-                        "void" => Type::Void,
-                        _ => {
-                            return Err(ParserError::SyntaxError {
-                                token: self.previous().clone(),
-                                backtrace: Backtrace::new(),
-                            })
-                        }
-                    },
+                let return_type = match &self.advance().clone().kind {
+                    TokenKind::Identifier(type_literal) => self.type_from_literal(type_literal)?,
                     _ => {
                         return Err(ParserError::SyntaxError {
                             token: self.previous().clone(),
@@ -600,10 +825,61 @@ impl Parser {
 
     fn func_call(&mut self) -> Result<Node> {
         let mut expr = self.load()?;
+        let mut type_args = Vec::new();
+
+        loop {
+            if let TokenKind::Dot = self.peek().kind {
+                self.advance();
+                let field = match &self.advance().kind {
+                    TokenKind::Identifier(field) => field.clone(),
+                    _ => {
+                        return Err(ParserError::SyntaxError {
+                            token: self.previous().clone(),
+                            backtrace: Backtrace::new(),
+                        })
+                    }
+                };
+                expr = self.node(Expression::FieldAccess(expression::FieldAccess {
+                    object: Box::new(expr),
+                    field,
+                }));
+                continue;
+            }
+            // on a new line the bracket starts another statement
+            if matches!(self.peek().kind, TokenKind::LeftBracket)
+                && self.peek().span.line == self.previous().span.line
+            {
+                self.advance();
+                let index = self.expression()?;
+                consume!(self, TokenKind::RightBracket)?;
+                expr = self.node(Expression::Index(expression::Index {
+                    object: Box::new(expr),
+                    index: Box::new(index),
+                }));
+                continue;
+            }
+            // `name::<T>(..)` passes type arguments to a generic builtin
+            if let (TokenKind::DoubleColon, Expression::Identifier(_)) =
+                (&self.peek().kind, &expr.expression)
+            {
+                self.advance();
+                type_args = self.type_list()?;
+                if !matches!(self.peek().kind, TokenKind::LeftParen) {
+                    return Err(ParserError::SyntaxError {
+                        token: self.peek().clone(),
+                        backtrace: Backtrace::new(),
+                    });
+                }
+            }
+            if !matches!(self.peek().kind, TokenKind::LeftParen) {
+                break;
+            }
 
-        while let TokenKind::LeftParen = self.peek().kind {
             match expr.expression {
-                Expression::Identifier { .. } => {
+                Expression::Identifier { .. }
+                | Expression::FuncCall(_)
+                | Expression::FieldAccess(_)
+                | Expression::Index(_) => {
                     self.advance();
                     let mut args: Vec<Node> = Vec::new();
 
@@ -622,6 +898,7 @@ impl Parser {
 
                     expr = self.node(Expression::FuncCall(expression::FuncCall {
                         calee: Box::new(expr),
+                        type_args: std::mem::take(&mut type_args),
                         args,
                     }));
                 }
@@ -674,6 +951,7 @@ impl Parser {
         let token = self.advance().clone();
         match &token.kind {
             TokenKind::Numeric(val) => Ok(self.node(Expression::Numeric(*val))),
+            TokenKind::Integer(val) => Ok(self.node(Expression::Integer(*val))),
             TokenKind::LeftParen => {
                 let expr = Expression::Grouping(expression::Grouping(Box::new(self.expression()?)));
 
@@ -691,13 +969,45 @@ impl Parser {
 
                 Ok(self.node(expr))
             }
+            TokenKind::Identifier(literal) if self.at_struct_literal() => {
+                self.struct_literal(literal)
+            }
+            TokenKind::Identifier(literal)
+                if matches!(self.peek().kind, TokenKind::DoubleColon)
+                    && !matches!(
+                        self.tokens.get(self.current + 1).map(|token| &token.kind),
+                        Some(TokenKind::Less)
+                    ) =>
+            {
+                self.enum_variant(literal)
+            }
             TokenKind::Identifier(literal) => {
                 Ok(self.node(Expression::Identifier(literal.to_string())))
             }
+            TokenKind::LeftBracket => self.vec_literal(),
+            TokenKind::Struct => self.struct_declaration(),
+            TokenKind::Enum => self.enum_declaration(),
             TokenKind::String(literal) => Ok(self.node(Expression::String(literal.to_string()))),
+            TokenKind::InterpolatedString(parts) => self.interpolation(parts),
             TokenKind::True => Ok(self.node(Expression::Bool(true))),
             TokenKind::False => Ok(self.node(Expression::Bool(false))),
-            TokenKind::Break => Ok(self.node(Expression::Break)),
+            TokenKind::Break => {
+                let label = self.loop_label(token.span.line);
+                Ok(self.node(Expression::Break(label)))
+            }
+            TokenKind::Continue => {
+                let label = self.loop_label(token.span.line);
+                Ok(self.node(Expression::Continue(label)))
+            }
+            TokenKind::Match => self.match_expression(),
+            TokenKind::Return => {
+                let value = match self.peek().kind {
+                    TokenKind::RCurly | TokenKind::Eof => None,
+                    _ if self.peek().span.line != token.span.line => None,
+                    _ => Some(Box::new(self.expression()?)),
+                };
+                Ok(self.node(Expression::Return(value)))
+            }
             _ => Err(ParserError::SyntaxError {
                 token: token.clone(),
                 backtrace: Backtrace::new(),
@@ -705,6 +1015,355 @@ impl Parser {
         }
     }
 
+    /// A name followed by `{ field:` starts a struct literal rather than the
+    /// body of a loop or conditional.
+    fn at_struct_literal(&self) -> bool {
+        matches!(self.peek().kind, TokenKind::LCurly)
+            && matches!(
+                self.tokens.get(self.current + 1).map(|token| &token.kind),
+                Some(TokenKind::Identifier(_))
+            )
+            && matches!(
+                self.tokens.get(self.current + 2).map(|token| &token.kind),
+                Some(TokenKind::Colon)
+            )
+    }
+
+    /// String literal with placeholders, each holding the tokens of a single
+    /// expression.
+    fn interpolation(&mut self, parts: &[StringPart]) -> Result<Node> {
+        let mut nodes = Vec::new();
+        for part in parts {
+            match part {
+                StringPart::Text(text) => nodes.push(self.node(Expression::String(text.clone()))),
+                StringPart::Code(tokens) => {
+                    let mut parser = Parser {
+                        tokens: tokens.clone(),
+                        current: 0,
+                        enums: self.enums.clone(),
+                        structs: self.structs.clone(),
+                    };
+                    // an empty placeholder holds nothing but the end of input
+                    if parser.at_end() {
+                        return Err(ParserError::SyntaxError {
+                            token: parser.peek().clone(),
+                            backtrace: Backtrace::new(),
+                        });
+                    }
+                    nodes.push(parser.expression()?);
+                    if !parser.at_end() {
+                        return Err(ParserError::SyntaxError {
+                            token: parser.peek().clone(),
+                            backtrace: Backtrace::new(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(
+            self.node(Expression::Interpolation(expression::Interpolation {
+                parts: nodes,
+            })),
+        )
+    }
+
+    /// `[a, b, c]`, the opening bracket already consumed.
+    fn vec_literal(&mut self) -> Result<Node> {
+        let mut elements = Vec::new();
+        loop {
+            if let TokenKind::RightBracket = self.peek().kind {
+                self.advance();
+                break;
+            }
+            elements.push(self.expression()?);
+            if let TokenKind::RightBracket = self.peek().kind {
+                self.advance();
+                break;
+            }
+            consume!(self, TokenKind::Comma)?;
+        }
+
+        Ok(self.node(Expression::VecLiteral(expression::VecLiteral { elements })))
+    }
+
+    fn struct_literal(&mut self, name: &str) -> Result<Node> {
+        consume!(self, TokenKind::LCurly)?;
+
+        let mut fields = Vec::new();
+        loop {
+            match self.advance().clone().kind {
+                TokenKind::RCurly => break,
+                TokenKind::Comma => {}
+                TokenKind::Identifier(field) => {
+                    consume!(self, TokenKind::Colon)?;
+                    fields.push(expression::FieldValue {
+                        name: field,
+                        value: self.expression()?,
+                    });
+                }
+                _ => {
+                    return Err(ParserError::SyntaxError {
+                        token: self.previous().clone(),
+                        backtrace: Backtrace::new(),
+                    })
+                }
+            }
+        }
+
+        Ok(
+            self.node(Expression::StructLiteral(expression::StructLiteral {
+                name: name.to_string(),
+                fields,
+            })),
+        )
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        match self.advance().clone().kind {
+            TokenKind::Identifier(name) => Ok(name),
+            _ => Err(ParserError::SyntaxError {
+                token: self.previous().clone(),
+                backtrace: Backtrace::new(),
+            }),
+        }
+    }
+
+    fn struct_declaration(&mut self) -> Result<Node> {
+        let name = self.identifier()?;
+        consume!(self, TokenKind::LCurly)?;
+
+        let mut fields = Vec::new();
+        loop {
+            match self.advance().clone().kind {
+                TokenKind::RCurly => break,
+                TokenKind::Comma => {}
+                TokenKind::Identifier(field) => {
+                    consume!(self, TokenKind::Colon)?;
+                    let type_literal = self.identifier()?;
+                    let typ = self.type_from_literal(&type_literal)?;
+                    fields.push(Param { name: field, typ });
+                }
+                _ => {
+                    return Err(ParserError::SyntaxError {
+                        token: self.previous().clone(),
+                        backtrace: Backtrace::new(),
+                    })
+                }
+            }
+        }
+
+        Ok(self.node(Expression::StructDecl(expression::StructDecl {
+            name,
+            fields,
+        })))
+    }
+
+    fn enum_declaration(&mut self) -> Result<Node> {
+        let name = self.identifier()?;
+        consume!(self, TokenKind::LCurly)?;
+
+        let mut variants = Vec::new();
+        loop {
+            match self.advance().clone().kind {
+                TokenKind::RCurly => break,
+                TokenKind::Comma => {}
+                TokenKind::Identifier(variant) => {
+                    let mut fields = Vec::new();
+                    if let TokenKind::LeftParen = self.peek().kind {
+                        self.advance();
+                        loop {
+                            match self.advance().clone().kind {
+                                TokenKind::RightParen => break,
+                                TokenKind::Comma => {}
+                                TokenKind::Identifier(type_literal) => {
+                                    fields.push(self.type_from_literal(&type_literal)?)
+                                }
+                                _ => {
+                                    return Err(ParserError::SyntaxError {
+                                        token: self.previous().clone(),
+                                        backtrace: Backtrace::new(),
+                                    })
+                                }
+                            }
+                        }
+                    }
+                    variants.push(expression::VariantDecl {
+                        name: variant,
+                        fields,
+                    });
+                }
+                _ => {
+                    return Err(ParserError::SyntaxError {
+                        token: self.previous().clone(),
+                        backtrace: Backtrace::new(),
+                    })
+                }
+            }
+        }
+
+        Ok(self.node(Expression::EnumDecl(expression::EnumDecl {
+            name,
+            variants,
+        })))
+    }
+
+    fn enum_variant(&mut self, name: &str) -> Result<Node> {
+        consume!(self, TokenKind::DoubleColon)?;
+        let variant = self.identifier()?;
+
+        let mut args = Vec::new();
+        if let TokenKind::LeftParen = self.peek().kind {
+            self.advance();
+            loop {
+                if let TokenKind::RightParen = self.peek().kind {
+                    self.advance();
+                    break;
+                }
+                args.push(self.expression()?);
+                if let TokenKind::RightParen = self.peek().kind {
+                    self.advance();
+                    break;
+                }
+                consume!(self, TokenKind::Comma)?;
+            }
+        }
+
+        Ok(self.node(Expression::EnumVariant(expression::EnumVariant {
+            name: name.to_string(),
+            variant,
+            args,
+        })))
+    }
+
+    /// `Enum::Variant(a, b)`, binding the payload of the variant to names.
+    fn variant_pattern(&mut self) -> Result<expression::Pattern> {
+        let name = self.identifier()?;
+        consume!(self, TokenKind::DoubleColon)?;
+        let variant = self.identifier()?;
+
+        let mut bindings = Vec::new();
+        if let TokenKind::LeftParen = self.peek().kind {
+            self.advance();
+            loop {
+                match self.advance().clone().kind {
+                    TokenKind::RightParen => break,
+                    TokenKind::Comma => {}
+                    TokenKind::Identifier(binding) => bindings.push(binding),
+                    _ => {
+                        return Err(ParserError::SyntaxError {
+                            token: self.previous().clone(),
+                            backtrace: Backtrace::new(),
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(expression::Pattern::Variant {
+            name,
+            variant,
+            bindings,
+        })
+    }
+
+    fn match_expression(&mut self) -> Result<Node> {
+        let subject = self.expression()?;
+        consume!(self, TokenKind::LCurly)?;
+
+        let mut arms = Vec::new();
+        loop {
+            match &self.peek().kind {
+                TokenKind::RCurly => {
+                    self.advance();
+                    break;
+                }
+                TokenKind::Identifier(name) if name == "_" => {
+                    self.advance();
+                    arms.push(self.match_arm(expression::Pattern::Wildcard)?);
+                }
+                TokenKind::Identifier(_)
+                    if matches!(
+                        self.tokens.get(self.current + 1).map(|token| &token.kind),
+                        Some(TokenKind::DoubleColon)
+                    ) =>
+                {
+                    let pattern = self.variant_pattern()?;
+                    arms.push(self.match_arm(pattern)?);
+                }
+                _ => {
+                    let start = self.unary()?;
+                    let pattern = match self.peek().kind {
+                        TokenKind::DotDot => {
+                            self.advance();
+                            expression::Pattern::Range {
+                                start: Box::new(start),
+                                end: Box::new(self.unary()?),
+                            }
+                        }
+                        _ => expression::Pattern::Literal(Box::new(start)),
+                    };
+                    arms.push(self.match_arm(pattern)?);
+                }
+            }
+        }
+
+        Ok(self.node(Expression::Match(expression::Match {
+            subject: Box::new(subject),
+            arms,
+        })))
+    }
+
+    fn match_arm(&mut self, pattern: expression::Pattern) -> Result<expression::MatchArm> {
+        let guard = match self.peek().kind {
+            TokenKind::If => {
+                self.advance();
+                Some(Box::new(self.logical_or()?))
+            }
+            _ => None,
+        };
+        consume!(self, TokenKind::Arrow)?;
+
+        let mut body = Vec::new();
+        match self.peek().kind {
+            TokenKind::LCurly => {
+                self.advance();
+                loop {
+                    match self.peek().kind {
+                        TokenKind::RCurly => {
+                            self.advance();
+                            break;
+                        }
+                        _ => body.push(self.expression()?),
+                    }
+                }
+            }
+            _ => body.push(self.expression()?),
+        }
+
+        if let TokenKind::Comma = self.peek().kind {
+            self.advance();
+        }
+
+        Ok(expression::MatchArm {
+            pattern,
+            guard,
+            body,
+        })
+    }
+
+    /// Optional label after `break` or `continue`, on the same line.
+    fn loop_label(&mut self, line: u32) -> Option<String> {
+        match &self.peek().kind {
+            TokenKind::Label(label) if self.peek().span.line == line => {
+                let label = label.clone();
+                self.advance();
+                Some(label)
+            }
+            _ => None,
+        }
+    }
+
     fn previous(&self) -> &Token {
         &self.tokens[self.current - 1]
     }
@@ -720,16 +1379,89 @@ impl Parser {
         self.previous()
     }
 
+    /// Parses `<T1, T2, ..., R>`, where the last type is the return type.
+    fn signature(&mut self) -> Result<(Vec<Type>, Type)> {
+        let mut types = self.type_list()?;
+
+        match types.pop() {
+            Some(return_type) => Ok((types, return_type)),
+            None => Err(ParserError::SyntaxError {
+                token: self.previous().clone(),
+                backtrace: Backtrace::new(),
+            }),
+        }
+    }
+
+    /// Types between angle brackets, as in `fun<number, bool>` or `vec<string>`.
+    fn type_list(&mut self) -> Result<Vec<Type>> {
+        consume!(self, TokenKind::Less)?;
+
+        let mut types = vec![];
+
+        loop {
+            let token = self.advance().clone();
+            match token.kind {
+                TokenKind::Identifier(ref s) => {
+                    types.push(self.type_from_literal(s)?);
+                }
+                TokenKind::Comma => (),
+                TokenKind::Greater => {
+                    break;
+                }
+                // `>>` closing a nested signature, leave the second `>` for
+                // the enclosing one
+                TokenKind::ShiftRight => {
+                    self.current -= 1;
+                    self.tokens[self.current].kind = TokenKind::Greater;
+                    break;
+                }
+                _ => {
+                    return Err(ParserError::SyntaxError {
+                        token: self.previous().clone(),
+                        backtrace: Backtrace::new(),
+                    });
+                }
+            }
+        }
+
+        Ok(types)
+    }
+
     fn type_from_literal(&mut self, type_literal: &str) -> Result<Type> {
         match type_literal {
             "void" => Ok(Type::Void),
             "string" => Ok(Type::String),
             "cstring" => Ok(Type::CString),
             "number" => Ok(Type::Numeric),
-            "vec" => Ok(Type::Vector),
-            "fun" => Ok(Type::Function),
+            "int" => Ok(Type::Int),
+            "bool" => Ok(Type::Bool),
+            "vec" if matches!(self.peek().kind, TokenKind::Less) => {
+                match self.type_list()?.as_slice() {
+                    [element] if *element != Type::Void => {
+                        Ok(Type::Vector(Box::new(element.clone())))
+                    }
+                    _ => Err(ParserError::SyntaxError {
+                        token: self.previous().clone(),
+                        backtrace: Backtrace::new(),
+                    }),
+                }
+            }
+            "vec" => Ok(Type::Vector(Box::new(Type::Numeric))),
+            "fun" if matches!(self.peek().kind, TokenKind::Less) => {
+                let (params, return_type) = self.signature()?;
+                Ok(Type::Function {
+                    params,
+                    return_type: Box::new(return_type),
+                })
+            }
+            "fun" => Ok(Type::Function {
+                params: vec![],
+                return_type: Box::new(Type::Void),
+            }),
             "ptr" => Ok(Type::Ptr),
-            _ => Err(ParserError::SyntaxError {
+            name if self.enums.contains(name) => Ok(Type::Enum(name.to_string())),
+            name if self.structs.contains(name) => Ok(Type::Struct(name.to_string())),
+            _ => Err(ParserError::UndefinedType {
                 token: self.previous().clone(),
                 backtrace: Backtrace::new(),
             }),
@@ -756,7 +1488,7 @@ mod test {
 
     macro_rules! assert_is_err {
         ($val: expr) => {
-            assert!(matches!($val, Err(_)));
+            assert!($val.is_err());
         };
     }
 
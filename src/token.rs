@@ -3,6 +3,13 @@ use std::fmt::Display;
 
 use crate::parser::Span;
 
+/// Piece of a string literal with `{expression}` placeholders.
+#[derive(Clone, Debug)]
+pub enum StringPart {
+    Text(String),
+    Code(Vec<Token>),
+}
+
 #[derive(Clone, Debug)]
 pub enum TokenKind {
     DoubleEqual,
@@ -11,14 +18,26 @@ pub enum TokenKind {
     LessOrEqual,
     Comma,
     Break,
+    Continue,
+    Match,
+    Struct,
+    Enum,
+    Let,
+    Return,
     True,
     False,
     NotEqual,
     Percent,
     LeftParen,
+    LeftBracket,
+    RightBracket,
     LCurly,
     RCurly,
     While,
+    For,
+    In,
+    DotDot,
+    Dot,
     If,
     RightParen,
     Slash,
@@ -30,12 +49,22 @@ pub enum TokenKind {
     Exclamation,
     Or,
     And,
+    Pipe,
+    Ampersand,
+    Caret,
+    Tilde,
+    ShiftLeft,
+    ShiftRight,
     Greater,
     GreaterOrEqual,
     Colon,
+    DoubleColon,
     String(String),
+    InterpolatedString(Vec<StringPart>),
     Identifier(String),
+    Label(String),
     Numeric(f64),
+    Integer(i64),
     Load,
     Extern,
     Eof,
@@ -52,9 +81,13 @@ fn token_name(token: &TokenKind) -> &str {
         TokenKind::NotEqual => "NotEqual",
         TokenKind::LeftParen => "LeftParen",
         TokenKind::RightParen => "RightParen",
+        TokenKind::LeftBracket => "LeftBracket",
+        TokenKind::RightBracket => "RightBracket",
         TokenKind::Slash => "Slash",
         TokenKind::Identifier { .. } => "Identifier",
+        TokenKind::Label { .. } => "Label",
         TokenKind::Numeric { .. } => "Numeric",
+        TokenKind::Integer { .. } => "Integer",
         TokenKind::Plus => "Plus",
         TokenKind::Minus => "Minus",
         TokenKind::Asterisk => "Asterisk",
@@ -63,13 +96,24 @@ fn token_name(token: &TokenKind) -> &str {
         TokenKind::RCurly => "RCurly",
         TokenKind::If => "If",
         TokenKind::While => "While",
+        TokenKind::For => "For",
+        TokenKind::In => "In",
+        TokenKind::DotDot => "DotDot",
+        TokenKind::Dot => "Dot",
         TokenKind::True => "True",
         TokenKind::False => "False",
         TokenKind::DoubleEqual => "DoubleEqual",
         TokenKind::Percent => "Percent",
         TokenKind::Exclamation => "Exclamation",
         TokenKind::Break => "Break",
+        TokenKind::Continue => "Continue",
+        TokenKind::Match => "Match",
+        TokenKind::Struct => "Struct",
+        TokenKind::Enum => "Enum",
+        TokenKind::Let => "Let",
+        TokenKind::Return => "Return",
         TokenKind::String { .. } => "String",
+        TokenKind::InterpolatedString { .. } => "InterpolatedString",
         TokenKind::Eof => "Eof",
         TokenKind::Comma => "Comma",
         TokenKind::Arrow => "Arrow",
@@ -79,8 +123,15 @@ fn token_name(token: &TokenKind) -> &str {
         TokenKind::GreaterOrEqual => "GreaterOrEqual",
         TokenKind::Or => "Or",
         TokenKind::And => "And",
+        TokenKind::Pipe => "Pipe",
+        TokenKind::Ampersand => "Ampersand",
+        TokenKind::Caret => "Caret",
+        TokenKind::Tilde => "Tilde",
+        TokenKind::ShiftLeft => "ShiftLeft",
+        TokenKind::ShiftRight => "ShiftRight",
         TokenKind::Else => "Else",
         TokenKind::Colon => "Colon",
+        TokenKind::DoubleColon => "DoubleColon",
         TokenKind::Load => "Load",
         TokenKind::Extern => "Extern",
     }
@@ -92,7 +143,12 @@ impl Display for TokenKind {
             TokenKind::Numeric(value) => {
                 write!(f, "<{}({})>", token_name(self), value)
             }
-            TokenKind::Identifier(literal) | TokenKind::String(literal) => {
+            TokenKind::Integer(value) => {
+                write!(f, "<{}({})>", token_name(self), value)
+            }
+            TokenKind::Identifier(literal)
+            | TokenKind::Label(literal)
+            | TokenKind::String(literal) => {
                 write!(f, "<{}({})>", token_name(self), literal)
             }
             _ => write!(f, "<{}>", token_name(self)),
@@ -1,5 +1,5 @@
 use crate::parser::Span;
-use crate::token::{Token, TokenKind};
+use crate::token::{StringPart, Token, TokenKind};
 use std::error::Error;
 use std::fmt;
 
@@ -33,16 +33,52 @@ impl Tokenize for Tokenizer {
 }
 
 #[derive(Clone, Debug)]
-pub struct TokenizerError {
-    pub chr: char,
-    pub line: usize,
+pub enum TokenizerError {
+    UnexpectedCharacter {
+        chr: char,
+        line: usize,
+    },
+    UnterminatedString {
+        line: usize,
+        column: usize,
+    },
+    UnterminatedInterpolation {
+        line: usize,
+        column: usize,
+    },
+    InvalidEscape {
+        sequence: String,
+        line: usize,
+        column: usize,
+    },
 }
 
 impl Error for TokenizerError {}
 
 impl fmt::Display for TokenizerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "unexpected character '{}'", self.chr)
+        match self {
+            TokenizerError::UnexpectedCharacter { chr, .. } => {
+                write!(f, "unexpected character '{}'", chr)
+            }
+            TokenizerError::UnterminatedString { line, column } => {
+                write!(f, "unterminated string at {}:{}", line, column)
+            }
+            TokenizerError::UnterminatedInterpolation { line, column } => {
+                write!(f, "unterminated interpolation at {}:{}", line, column)
+            }
+            TokenizerError::InvalidEscape {
+                sequence,
+                line,
+                column,
+            } => {
+                write!(
+                    f,
+                    "invalid escape sequence '{}' at {}:{}",
+                    sequence, line, column
+                )
+            }
+        }
     }
 }
 
@@ -67,29 +103,38 @@ impl Tokenizer {
                 self.line += 1;
                 self.column = 0;
             }
-            '<' => {
-                if '=' == self.peek() {
+            '<' => match self.peek() {
+                '=' => {
                     self.advance();
                     self.add_token(TokenKind::LessOrEqual)
-                } else {
-                    self.add_token(TokenKind::Less)
                 }
-            }
-            '>' => {
-                if '=' == self.peek() {
+                '<' => {
+                    self.advance();
+                    self.add_token(TokenKind::ShiftLeft)
+                }
+                _ => self.add_token(TokenKind::Less),
+            },
+            '>' => match self.peek() {
+                '=' => {
                     self.advance();
                     self.add_token(TokenKind::GreaterOrEqual)
-                } else {
-                    self.add_token(TokenKind::Greater)
                 }
-            }
+                '>' => {
+                    self.advance();
+                    self.add_token(TokenKind::ShiftRight)
+                }
+                _ => self.add_token(TokenKind::Greater),
+            },
             '(' => self.add_token(TokenKind::LeftParen),
             ')' => self.add_token(TokenKind::RightParen),
+            '[' => self.add_token(TokenKind::LeftBracket),
+            ']' => self.add_token(TokenKind::RightBracket),
             '+' => self.add_token(TokenKind::Plus),
             '-' => self.add_token(TokenKind::Minus),
             '*' => self.add_token(TokenKind::Asterisk),
             '%' => self.add_token(TokenKind::Percent),
-            '"' => self.string(),
+            '"' => self.string()?,
+            'r' if matches!(self.peek(), '"' | '#') => self.raw_string()?,
             '!' => {
                 if '=' == self.peek() {
                     self.advance();
@@ -98,12 +143,24 @@ impl Tokenizer {
                     self.add_token(TokenKind::Exclamation);
                 }
             }
-            '|' if self.advance() == '|' => {
-                self.add_token(TokenKind::Or);
+            '|' => {
+                if '|' == self.peek() {
+                    self.advance();
+                    self.add_token(TokenKind::Or);
+                } else {
+                    self.add_token(TokenKind::Pipe);
+                }
             }
-            '&' if self.advance() == '&' => {
-                self.add_token(TokenKind::And);
+            '&' => {
+                if '&' == self.peek() {
+                    self.advance();
+                    self.add_token(TokenKind::And);
+                } else {
+                    self.add_token(TokenKind::Ampersand);
+                }
             }
+            '^' => self.add_token(TokenKind::Caret),
+            '~' => self.add_token(TokenKind::Tilde),
             '=' => match self.peek() {
                 '=' => {
                     self.add_token(TokenKind::DoubleEqual);
@@ -118,7 +175,16 @@ impl Tokenizer {
             '{' => self.add_token(TokenKind::LCurly),
             '}' => self.add_token(TokenKind::RCurly),
             ',' => self.add_token(TokenKind::Comma),
+            ':' if !self.at_end() && ':' == self.peek() => {
+                self.advance();
+                self.add_token(TokenKind::DoubleColon);
+            }
             ':' => self.add_token(TokenKind::Colon),
+            '.' if '.' == self.peek() => {
+                self.advance();
+                self.add_token(TokenKind::DotDot);
+            }
+            '.' => self.add_token(TokenKind::Dot),
             '/' => {
                 if '/' == self.peek() {
                     while self.peek() != '\n' && !self.at_end() {
@@ -128,10 +194,11 @@ impl Tokenizer {
                     self.add_token(TokenKind::Slash);
                 }
             }
-            c if c.is_alphabetic() => self.identifier(),
+            '\'' if self.peek().is_alphabetic() => self.label(),
+            c if c.is_alphabetic() || c == '_' => self.identifier(),
             c if c.is_numeric() => self.numeric(),
             chr => {
-                return Err(TokenizerError {
+                return Err(TokenizerError::UnexpectedCharacter {
                     chr,
                     line: self.line,
                 })
@@ -140,15 +207,171 @@ impl Tokenizer {
         Ok(())
     }
 
-    fn string(&mut self) {
+    /// Consumes the character of a string literal starting at `line` and
+    /// `column`, failing at the end of the source.
+    fn string_char(&mut self, line: usize, column: usize) -> Result<char> {
+        if self.at_end() {
+            Err(TokenizerError::UnterminatedString { line, column })?
+        }
+        let chr = self.advance();
+        if chr == '\n' {
+            self.line += 1;
+            self.column = 0;
+        }
+        Ok(chr)
+    }
+
+    fn string(&mut self) -> Result<()> {
+        let (line, column) = (self.line, self.column - 1);
+        let mut parts = Vec::new();
         let mut literal = String::new();
 
-        while self.peek() != '"' {
-            let chr = self.advance();
-            literal.push(chr);
+        loop {
+            match self.string_char(line, column)? {
+                '"' => break,
+                '\\' => literal.push(self.escape(line, column)?),
+                '{' => {
+                    if !literal.is_empty() {
+                        parts.push(StringPart::Text(std::mem::take(&mut literal)));
+                    }
+                    let brace = (self.line, self.column - 1);
+                    parts.push(StringPart::Code(self.interpolated_code(brace.0, brace.1)?));
+                }
+                chr => literal.push(chr),
+            }
+        }
+
+        if parts.is_empty() {
+            self.add_token(TokenKind::String(literal));
+        } else {
+            if !literal.is_empty() {
+                parts.push(StringPart::Text(literal));
+            }
+            self.add_token(TokenKind::InterpolatedString(parts));
+        }
+        Ok(())
+    }
+
+    /// Consumes a character of the placeholder whose brace is at `line` and
+    /// `column`, failing at the end of the source.
+    fn code_char(&mut self, line: usize, column: usize) -> Result<char> {
+        self.string_char(line, column)
+            .map_err(|_| TokenizerError::UnterminatedInterpolation { line, column })
+    }
+
+    /// Tokens of the expression between the braces of a placeholder, the
+    /// opening one at `line` and `column` already consumed. Strings within it
+    /// may contain braces.
+    fn interpolated_code(&mut self, line: usize, column: usize) -> Result<Vec<Token>> {
+        let (code_line, code_column) = (self.line, self.column);
+        let mut code = String::new();
+        let mut depth = 0;
+
+        loop {
+            let chr = self.code_char(line, column)?;
+            match chr {
+                '}' if depth == 0 => break,
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                '"' => {
+                    code.push(chr);
+                    loop {
+                        let chr = self.code_char(line, column)?;
+                        code.push(chr);
+                        match chr {
+                            '"' => break,
+                            '\\' => code.push(self.code_char(line, column)?),
+                            _ => (),
+                        }
+                    }
+                    continue;
+                }
+                _ => (),
+            }
+            code.push(chr);
+        }
+
+        let mut tokenizer = Tokenizer {
+            source: code,
+            current: 0,
+            column: code_column,
+            tokens: Vec::new(),
+            line: code_line,
+        };
+        while !tokenizer.at_end() {
+            tokenizer.scan_token()?;
+        }
+        tokenizer.add_token(TokenKind::Eof);
+
+        Ok(tokenizer.tokens)
+    }
+
+    /// Character of the escape sequence following a backslash.
+    fn escape(&mut self, line: usize, column: usize) -> Result<char> {
+        let (escape_line, escape_column) = (self.line, self.column - 1);
+        let chr = match self.string_char(line, column)? {
+            'n' => '\n',
+            't' => '\t',
+            '\\' => '\\',
+            '"' => '"',
+            '{' => '{',
+            '}' => '}',
+            'u' if self.peek() == '{' => {
+                self.advance();
+                let mut digits = String::new();
+                while self.peek().is_ascii_hexdigit() {
+                    digits.push(self.advance());
+                }
+                let closed = self.peek() == '}';
+                if closed {
+                    self.advance();
+                }
+                match u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                {
+                    Some(chr) if closed && digits.len() <= 6 => chr,
+                    _ => Err(TokenizerError::InvalidEscape {
+                        sequence: format!("\\u{{{}", digits),
+                        line: escape_line,
+                        column: escape_column,
+                    })?,
+                }
+            }
+            chr => Err(TokenizerError::InvalidEscape {
+                sequence: format!("\\{}", chr),
+                line: escape_line,
+                column: escape_column,
+            })?,
+        };
+        Ok(chr)
+    }
+
+    /// `r"..."`, or `r#"..."#` with any number of `#` to contain quotes,
+    /// taken as written over any number of lines.
+    fn raw_string(&mut self) -> Result<()> {
+        let (line, column) = (self.line, self.column - 1);
+        let mut hashes = 0;
+        while self.peek() == '#' {
+            self.advance();
+            hashes += 1;
+        }
+        if self.peek() != '"' {
+            return Err(TokenizerError::UnexpectedCharacter {
+                chr: self.peek(),
+                line: self.line,
+            });
         }
         self.advance();
+
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let mut literal = String::new();
+        while !literal.ends_with(&terminator) {
+            literal.push(self.string_char(line, column)?);
+        }
+        literal.truncate(literal.len() - terminator.len());
         self.add_token(TokenKind::String(literal));
+        Ok(())
     }
 
     fn numeric(&mut self) {
@@ -158,7 +381,7 @@ impl Tokenizer {
         loop {
             let chr = self.peek();
 
-            if chr.is_numeric() || chr == '.' {
+            if chr.is_numeric() || (chr == '.' && self.peek_next() != '.') {
                 literal.push(chr);
                 self.advance();
             } else {
@@ -166,9 +389,13 @@ impl Tokenizer {
             }
         }
 
-        self.add_token(TokenKind::Numeric(
-            literal.parse().expect("Error parsing number"),
-        ));
+        // integers too large for an int are still valid numbers
+        match literal.parse() {
+            Ok(int) if !literal.contains('.') => self.add_token(TokenKind::Integer(int)),
+            _ => self.add_token(TokenKind::Numeric(
+                literal.parse().expect("Error parsing number"),
+            )),
+        }
     }
 
     fn identifier(&mut self) {
@@ -189,9 +416,17 @@ impl Tokenizer {
         match literal.as_str() {
             "if" => self.add_token(TokenKind::If),
             "while" => self.add_token(TokenKind::While),
+            "for" => self.add_token(TokenKind::For),
+            "in" => self.add_token(TokenKind::In),
             "true" => self.add_token(TokenKind::True),
             "false" => self.add_token(TokenKind::False),
             "break" => self.add_token(TokenKind::Break),
+            "continue" => self.add_token(TokenKind::Continue),
+            "match" => self.add_token(TokenKind::Match),
+            "struct" => self.add_token(TokenKind::Struct),
+            "enum" => self.add_token(TokenKind::Enum),
+            "let" => self.add_token(TokenKind::Let),
+            "return" => self.add_token(TokenKind::Return),
             "else" => self.add_token(TokenKind::Else),
             "load" => self.add_token(TokenKind::Load),
             "extern" => self.add_token(TokenKind::Extern),
@@ -199,6 +434,16 @@ impl Tokenizer {
         };
     }
 
+    fn label(&mut self) {
+        let mut literal = String::new();
+
+        while self.peek().is_alphanumeric() || self.peek() == '_' {
+            literal.push(self.advance());
+        }
+
+        self.add_token(TokenKind::Label(literal));
+    }
+
     fn add_token(&mut self, kind: TokenKind) {
         self.tokens.push(Token {
             kind,
@@ -222,7 +467,11 @@ impl Tokenizer {
     }
 
     fn peek(&mut self) -> char {
-        self.source.chars().nth(self.current).unwrap()
+        self.source.chars().nth(self.current).unwrap_or('\0')
+    }
+
+    fn peek_next(&self) -> char {
+        self.source.chars().nth(self.current + 1).unwrap_or('\0')
     }
 
     fn at_end(&self) -> bool {
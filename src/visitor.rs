@@ -13,6 +13,10 @@ pub trait NumericVisitor<T> {
     fn visit_numeric(&mut self, expr: &f64) -> T;
 }
 
+pub trait IntegerVisitor<T> {
+    fn visit_integer(&mut self, expr: &i64) -> T;
+}
+
 pub trait StringVisitor<T> {
     fn visit_string(&mut self, expr: &str) -> T;
 }
@@ -41,6 +45,46 @@ pub trait WhileVisitor<T> {
     fn visit_while(&mut self, expr: &expression::While, span: Span) -> T;
 }
 
+pub trait ForVisitor<T> {
+    fn visit_for(&mut self, expr: &expression::For, span: Span) -> T;
+}
+
+pub trait MatchVisitor<T> {
+    fn visit_match(&mut self, expr: &expression::Match, span: Span) -> T;
+}
+
+pub trait StructDeclVisitor<T> {
+    fn visit_struct_decl(&mut self, expr: &expression::StructDecl) -> T;
+}
+
+pub trait StructLiteralVisitor<T> {
+    fn visit_struct_literal(&mut self, expr: &expression::StructLiteral, span: Span) -> T;
+}
+
+pub trait FieldAccessVisitor<T> {
+    fn visit_field_access(&mut self, expr: &expression::FieldAccess, span: Span) -> T;
+}
+
+pub trait IndexVisitor<T> {
+    fn visit_index(&mut self, expr: &expression::Index, span: Span) -> T;
+}
+
+pub trait VecLiteralVisitor<T> {
+    fn visit_vec_literal(&mut self, expr: &expression::VecLiteral, span: Span) -> T;
+}
+
+pub trait InterpolationVisitor<T> {
+    fn visit_interpolation(&mut self, expr: &expression::Interpolation, span: Span) -> T;
+}
+
+pub trait EnumDeclVisitor<T> {
+    fn visit_enum_decl(&mut self, expr: &expression::EnumDecl) -> T;
+}
+
+pub trait EnumVariantVisitor<T> {
+    fn visit_enum_variant(&mut self, expr: &expression::EnumVariant, span: Span) -> T;
+}
+
 pub trait IdentifierVisitor<T> {
     fn visit_identifier(&mut self, expr: &str) -> T;
 }
@@ -50,11 +94,19 @@ pub trait BoolVisitor<T> {
 }
 
 pub trait BreakVisitor<T> {
-    fn visit_break(&mut self) -> T;
+    fn visit_break(&mut self, label: &Option<String>, span: Span) -> T;
+}
+
+pub trait ContinueVisitor<T> {
+    fn visit_continue(&mut self, label: &Option<String>, span: Span) -> T;
+}
+
+pub trait ReturnVisitor<T> {
+    fn visit_return(&mut self, expr: &Option<Box<Node>>, span: Span) -> T;
 }
 
 pub trait FuncDeclVisitor<T> {
-    fn visit_func_decl(&mut self, body: &expression::FuncDecl) -> T;
+    fn visit_func_decl(&mut self, body: &expression::FuncDecl, span: Span) -> T;
 }
 
 pub trait LoadVisitor<T> {
@@ -69,6 +121,7 @@ pub trait Visitor<T>:
     BinaryVisitor<T>
     + FuncCallVisitor<T>
     + NumericVisitor<T>
+    + IntegerVisitor<T>
     + StringVisitor<T>
     + ProgramVisitor<T>
     + AssignmentVisitor<T>
@@ -76,9 +129,21 @@ pub trait Visitor<T>:
     + UnaryVisitor<T>
     + GroupingVisitor<T>
     + WhileVisitor<T>
+    + ForVisitor<T>
+    + MatchVisitor<T>
+    + StructDeclVisitor<T>
+    + StructLiteralVisitor<T>
+    + FieldAccessVisitor<T>
+    + IndexVisitor<T>
+    + VecLiteralVisitor<T>
+    + InterpolationVisitor<T>
+    + EnumDeclVisitor<T>
+    + EnumVariantVisitor<T>
     + IdentifierVisitor<T>
     + BoolVisitor<T>
     + BreakVisitor<T>
+    + ContinueVisitor<T>
+    + ReturnVisitor<T>
     + FuncDeclVisitor<T>
     + LoadVisitor<T>
     + ExternVisitor<T>
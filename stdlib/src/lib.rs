@@ -1,6 +1,7 @@
 use std::{
     cell::RefCell,
-    ffi::{CStr, CString},
+    cmp::Ordering,
+    ffi::{c_void, CStr, CString},
     rc::Rc,
 };
 
@@ -29,11 +30,385 @@ pub unsafe extern "C" fn c_string_from_string(ptr: *const RefCell<String>) -> *c
     ptr
 }
 
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_equal(
+    lhs: *const RefCell<String>,
+    rhs: *const RefCell<String>,
+) -> bool {
+    let lhs = Rc::from_raw(lhs);
+    let rhs = Rc::from_raw(rhs);
+    let equal = *lhs.borrow() == *rhs.borrow();
+    std::mem::forget(lhs);
+    std::mem::forget(rhs);
+    equal
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_get(
+    ptr: *const RefCell<String>,
+    idx: f64,
+) -> *const RefCell<String> {
+    let rc = Rc::from_raw(ptr);
+    let char = rc.borrow().chars().nth(idx as usize);
+    std::mem::forget(rc);
+    Rc::into_raw(Rc::new(RefCell::new(
+        char.map(String::from).unwrap_or_default(),
+    )))
+}
+
+/// Replaces the character at `idx` with the single character `val`,
+/// appending it past the end of the string. A string held elsewhere too is
+/// copied rather than changed. Returns a new reference to the string holding
+/// the change, which the caller stores in place of the one at `ptr`.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_set(
+    ptr: *const RefCell<String>,
+    idx: f64,
+    val: *const RefCell<String>,
+    line: i64,
+    column: i64,
+) -> *const RefCell<String> {
+    let val = Rc::from_raw(val);
+    let char = {
+        let val = val.borrow();
+        let mut chars = val.chars();
+        match (chars.next(), chars.next()) {
+            (Some(char), None) => char,
+            _ => runtime_error(
+                format!("expected a single character, got {:?}", *val),
+                line,
+                column,
+            ),
+        }
+    };
+    std::mem::forget(val);
+
+    let shared = Rc::from_raw(ptr);
+    let rc = match Rc::strong_count(&shared) {
+        1 => Rc::clone(&shared),
+        _ => Rc::new(RefCell::new(shared.borrow().clone())),
+    };
+    std::mem::forget(shared);
+    {
+        let mut string = rc.try_borrow_mut().unwrap();
+        match string.char_indices().nth(idx as usize) {
+            Some((start, old)) => {
+                string.replace_range(start..start + old.len_utf8(), char.encode_utf8(&mut [0; 4]))
+            }
+            None => string.push(char),
+        }
+    }
+    Rc::into_raw(rc)
+}
+
 pub extern "C" fn string(num: f64) -> *const RefCell<String> {
     let rc = Rc::new(RefCell::new(num.to_string()));
     Rc::into_raw(rc)
 }
 
+pub extern "C" fn string_from_int(num: i64) -> *const RefCell<String> {
+    let rc = Rc::new(RefCell::new(num.to_string()));
+    Rc::into_raw(rc)
+}
+
+pub extern "C" fn string_from_bool(val: bool) -> *const RefCell<String> {
+    let rc = Rc::new(RefCell::new(val.to_string()));
+    Rc::into_raw(rc)
+}
+
+pub extern "C" fn string_new() -> *const RefCell<String> {
+    let rc = Rc::new(RefCell::new(String::new()));
+    Rc::into_raw(rc)
+}
+
+/// Appends `val` to the string at `ptr`.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_append(ptr: *const RefCell<String>, val: *const RefCell<String>) {
+    let rc = Rc::from_raw(ptr);
+    let val = Rc::from_raw(val);
+    {
+        // the value may be the string itself
+        let val = val.borrow().clone();
+        rc.try_borrow_mut().unwrap().push_str(&val);
+    }
+    std::mem::forget(rc);
+    std::mem::forget(val);
+}
+
+fn new_string(string: String) -> *const RefCell<String> {
+    Rc::into_raw(Rc::new(RefCell::new(string)))
+}
+
+unsafe fn with_string<T>(ptr: *const RefCell<String>, f: impl FnOnce(&str) -> T) -> T {
+    let rc = Rc::from_raw(ptr);
+    let val = f(&rc.borrow());
+    std::mem::forget(rc);
+    val
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_concat(
+    lhs: *const RefCell<String>,
+    rhs: *const RefCell<String>,
+) -> *const RefCell<String> {
+    with_string(lhs, |lhs| {
+        with_string(rhs, |rhs| new_string([lhs, rhs].concat()))
+    })
+}
+
+/// Orders strings by their contents, -1 when `lhs` comes first, 1 when
+/// `rhs` does and 0 when they are equal.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_compare(
+    lhs: *const RefCell<String>,
+    rhs: *const RefCell<String>,
+) -> i64 {
+    with_string(lhs, |lhs| with_string(rhs, |rhs| lhs.cmp(rhs) as i64))
+}
+
+/// Number of characters in the string.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_len(ptr: *const RefCell<String>) -> f64 {
+    with_string(ptr, |string| string.chars().count() as f64)
+}
+
+/// Characters from `start` up to, but not including, `end`. Both are clamped
+/// to the length of the string.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_substring(
+    ptr: *const RefCell<String>,
+    start: f64,
+    end: f64,
+) -> *const RefCell<String> {
+    with_string(ptr, |string| {
+        let end = end.max(0.) as usize;
+        let start = (start.max(0.) as usize).min(end);
+        new_string(string.chars().skip(start).take(end - start).collect())
+    })
+}
+
+/// Position of the first character of `needle` in the string, -1 if it
+/// doesn't occur.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_find(
+    ptr: *const RefCell<String>,
+    needle: *const RefCell<String>,
+) -> f64 {
+    with_string(ptr, |string| {
+        with_string(needle, |needle| {
+            string
+                .find(needle)
+                .map_or(-1., |idx| string[..idx].chars().count() as f64)
+        })
+    })
+}
+
+/// Parts of the string between occurrences of `separator`, or its characters
+/// when the separator is empty.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_split(
+    ptr: *const RefCell<String>,
+    separator: *const RefCell<String>,
+) -> *const Vector {
+    let parts: Vec<String> = with_string(ptr, |string| {
+        with_string(separator, |separator| match separator {
+            "" => string.chars().map(String::from).collect(),
+            separator => string.split(separator).map(String::from).collect(),
+        })
+    });
+    let elements = parts
+        .into_iter()
+        .map(|part| Capture::String(new_string(part)))
+        .collect();
+    Rc::into_raw(Rc::new(Vector {
+        elements: RefCell::new(elements),
+    }))
+}
+
+/// Strings of the vector with `separator` between each of them.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_join(
+    ptr: *const Vector,
+    separator: *const RefCell<String>,
+) -> *const RefCell<String> {
+    let rc = Rc::from_raw(ptr);
+    let parts: Vec<String> = rc
+        .elements
+        .borrow()
+        .iter()
+        .map(|element| match element {
+            Capture::String(string) => (**string).borrow().clone(),
+            _ => String::new(),
+        })
+        .collect();
+    std::mem::forget(rc);
+    with_string(separator, |separator| new_string(parts.join(separator)))
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_trim(ptr: *const RefCell<String>) -> *const RefCell<String> {
+    with_string(ptr, |string| new_string(string.trim().to_string()))
+}
+
+/// Replaces all occurrences of `from` in the string with `to`.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_replace(
+    ptr: *const RefCell<String>,
+    from: *const RefCell<String>,
+    to: *const RefCell<String>,
+) -> *const RefCell<String> {
+    with_string(ptr, |string| {
+        with_string(from, |from| {
+            with_string(to, |to| new_string(string.replace(from, to)))
+        })
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_upper(ptr: *const RefCell<String>) -> *const RefCell<String> {
+    with_string(ptr, |string| new_string(string.to_uppercase()))
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_lower(ptr: *const RefCell<String>) -> *const RefCell<String> {
+    with_string(ptr, |string| new_string(string.to_lowercase()))
+}
+
+/// Number written in the string, ignoring surrounding whitespace. Aborts
+/// when the string isn't a number, so NaN only comes from a string saying so.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_to_number(ptr: *const RefCell<String>) -> f64 {
+    with_string(ptr, |string| match string.trim().parse() {
+        Ok(num) => num,
+        Err(_) => abort(format!("invalid number {:?}", string)),
+    })
+}
+
+pub extern "C" fn is_nan(num: f64) -> bool {
+    num.is_nan()
+}
+
+fn precision(digits: f64) -> usize {
+    digits.clamp(0., 64.) as usize
+}
+
+/// `num` with exactly `digits` digits after the decimal point.
+pub extern "C" fn format_fixed(num: f64, digits: f64) -> *const RefCell<String> {
+    new_string(format!("{:.*}", precision(digits), num))
+}
+
+/// `num` in scientific notation with `digits` digits after the decimal point
+/// and a signed exponent of at least two digits, like `1.50e+03`.
+pub extern "C" fn format_scientific(num: f64, digits: f64) -> *const RefCell<String> {
+    let formatted = format!("{:.*e}", precision(digits), num);
+    let formatted = match formatted.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let exponent: i32 = exponent.parse().unwrap();
+            let sign = if exponent < 0 { '-' } else { '+' };
+            format!("{}e{}{:02}", mantissa, sign, exponent.abs())
+        }
+        // NaN and infinities
+        None => formatted,
+    };
+    new_string(formatted)
+}
+
+unsafe fn pad(
+    ptr: *const RefCell<String>,
+    width: f64,
+    fill: *const RefCell<String>,
+    left: bool,
+) -> *const RefCell<String> {
+    let fill = with_string(fill, |fill| fill.chars().next().unwrap_or(' '));
+    with_string(ptr, |string| {
+        let len = string.chars().count();
+        let padding: String =
+            std::iter::repeat_n(fill, (width.max(0.) as usize).saturating_sub(len)).collect();
+        match left {
+            true => new_string(padding + string),
+            false => new_string(string.to_string() + &padding),
+        }
+    })
+}
+
+/// Prepends the first character of `fill`, or a space when it is empty,
+/// until the string is `width` characters long.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_pad_left(
+    ptr: *const RefCell<String>,
+    width: f64,
+    fill: *const RefCell<String>,
+) -> *const RefCell<String> {
+    pad(ptr, width, fill, true)
+}
+
+/// Appends the first character of `fill`, or a space when it is empty,
+/// until the string is `width` characters long.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_pad_right(
+    ptr: *const RefCell<String>,
+    width: f64,
+    fill: *const RefCell<String>,
+) -> *const RefCell<String> {
+    pad(ptr, width, fill, false)
+}
+
+pub extern "C" fn int(num: f64) -> i64 {
+    num as i64
+}
+
+pub extern "C" fn number(num: i64) -> f64 {
+    num as f64
+}
+
 /// # Safety
 ///
 /// loads raw ptr
@@ -63,63 +438,885 @@ pub unsafe extern "C" fn inc_string_reference(ptr: *const RefCell<String>) {
 /// # Safety
 ///
 /// loads raw ptr
-pub unsafe extern "C" fn inc_vec_reference(ptr: *const RefCell<Vec<f64>>) {
+pub unsafe extern "C" fn inc_vec_reference(ptr: *const Vector) {
     Rc::increment_strong_count(ptr);
 }
 
-pub extern "C" fn vec_new() -> *const RefCell<Vec<f64>> {
-    let rc = Rc::new(RefCell::new(Vec::new()));
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn release_vec_reference(ptr: *const Vector) {
+    Rc::decrement_strong_count(ptr);
+}
+
+/// Elements of any type, stored like captures so that heap elements are
+/// released together with the vector.
+pub struct Vector {
+    elements: RefCell<Vec<Capture>>,
+}
+
+pub extern "C" fn vec_new() -> *const Vector {
+    let rc = Rc::new(Vector {
+        elements: RefCell::new(Vec::new()),
+    });
     Rc::into_raw(rc)
 }
 
 /// # Safety
 ///
 /// loads raw ptr
-pub unsafe extern "C" fn vec_set(ptr: *const RefCell<Vec<f64>>, idx: f64, val: f64) {
+pub unsafe extern "C" fn vec_len(ptr: *const Vector) -> f64 {
+    let rc = Rc::from_raw(ptr);
+    let val = rc.elements.borrow().len();
+    std::mem::forget(rc);
+    val as f64
+}
+
+unsafe fn vec_set(ptr: *const Vector, idx: f64, element: Capture) {
     let rc = Rc::from_raw(ptr);
     {
-        let mut vec = rc.try_borrow_mut().unwrap();
-        if (idx as usize) >= vec.len() {
-            vec.resize((idx as usize) + 1, 0.);
+        let mut elements = rc.elements.try_borrow_mut().unwrap();
+        // vectors only grow by one, there is no value to fill a gap with
+        index_guard(idx, elements.len(), 1);
+        match idx as usize {
+            idx if idx == elements.len() => elements.push(element),
+            idx => elements[idx] = element,
         }
-        vec[idx as usize] = val;
     }
     std::mem::forget(rc);
 }
 
+unsafe fn vec_get<T>(ptr: *const Vector, idx: f64, get: impl FnOnce(Option<&Capture>) -> T) -> T {
+    let rc = Rc::from_raw(ptr);
+    let val = get(rc.elements.borrow().get(idx as usize));
+    std::mem::forget(rc);
+    val
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_set_numeric(ptr: *const Vector, idx: f64, val: f64) {
+    vec_set(ptr, idx, Capture::Numeric(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_set_int(ptr: *const Vector, idx: f64, val: i64) {
+    vec_set(ptr, idx, Capture::Int(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_set_bool(ptr: *const Vector, idx: f64, val: bool) {
+    vec_set(ptr, idx, Capture::Bool(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_set_string(ptr: *const Vector, idx: f64, val: *const RefCell<String>) {
+    inc_string_reference(val);
+    vec_set(ptr, idx, Capture::String(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_set_vec(ptr: *const Vector, idx: f64, val: *const Vector) {
+    inc_vec_reference(val);
+    vec_set(ptr, idx, Capture::Vec(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_set_env(ptr: *const Vector, idx: f64, val: *const Env) {
+    inc_env_reference(val);
+    vec_set(ptr, idx, Capture::Env(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_set_struct(ptr: *const Vector, idx: f64, val: *const Struct) {
+    inc_struct_reference(val);
+    vec_set(ptr, idx, Capture::Struct(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_set_ptr(ptr: *const Vector, idx: f64, val: *const c_void) {
+    vec_set(ptr, idx, Capture::Ptr(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_get_numeric(ptr: *const Vector, idx: f64) -> f64 {
+    vec_get(ptr, idx, |element| match element {
+        Some(Capture::Numeric(val)) => *val,
+        _ => 0.,
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_get_int(ptr: *const Vector, idx: f64) -> i64 {
+    vec_get(ptr, idx, |element| match element {
+        Some(Capture::Int(val)) => *val,
+        _ => 0,
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_get_bool(ptr: *const Vector, idx: f64) -> bool {
+    vec_get(ptr, idx, |element| match element {
+        Some(Capture::Bool(val)) => *val,
+        _ => false,
+    })
+}
+
 /// # Safety
 ///
 /// loads raw ptr
-pub unsafe extern "C" fn vec_get(ptr: *const RefCell<Vec<f64>>, idx: f64) -> f64 {
+pub unsafe extern "C" fn vec_get_ptr(ptr: *const Vector, idx: f64) -> *const c_void {
     let rc = Rc::from_raw(ptr);
     let val = {
-        let vec = rc.borrow();
-        if (idx as usize) < vec.len() {
-            vec[idx as usize]
-        } else {
-            0.
-        }
+        let elements = rc.elements.borrow();
+        index_guard(idx, elements.len(), 0);
+        capture_ptr(&elements[idx as usize])
     };
     std::mem::forget(rc);
     val
 }
 
+fn runtime_error(message: String, line: i64, column: i64) -> ! {
+    eprintln!("Runtime error: {} at {}:{}", message, line, column);
+    std::process::exit(1)
+}
+
+/// Like `runtime_error`, for builtins that aren't told where they're called.
+fn abort(message: String) -> ! {
+    eprintln!("Runtime error: {}", message);
+    std::process::exit(1)
+}
+
+/// Why `idx` is neither the position of one of the `len` elements of a `kind`
+/// nor within `end` positions after the last, if it isn't.
+fn index_error(kind: &str, idx: f64, len: usize, end: usize) -> Option<String> {
+    if idx.fract() != 0. || idx.is_nan() {
+        Some(format!("invalid index {}", idx))
+    } else if idx < 0. || idx as usize >= len + end {
+        Some(format!(
+            "index {} out of bounds for {} of length {}",
+            idx, kind, len
+        ))
+    } else {
+        None
+    }
+}
+
+/// Aborts unless `idx` is valid for a vector of `len` elements. Calls compiled
+/// without bounds checks end up here rather than panicking, which can't unwind
+/// out of the stdlib.
+fn index_guard(idx: f64, len: usize, end: usize) {
+    if let Some(message) = index_error("vector", idx, len, end) {
+        abort(message)
+    }
+}
+
+unsafe fn vec_check(ptr: *const Vector, idx: f64, end: usize, line: i64, column: i64) {
+    let rc = Rc::from_raw(ptr);
+    let len = rc.elements.borrow().len();
+    std::mem::forget(rc);
+    if let Some(message) = index_error("vector", idx, len, end) {
+        runtime_error(message, line, column)
+    }
+}
+
+/// Aborts unless `idx` is the position of an element of the vector.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_check_get(ptr: *const Vector, idx: f64, line: i64, column: i64) {
+    vec_check(ptr, idx, 0, line, column);
+}
+
+/// Aborts unless `idx` is the position of an element of the vector or the
+/// one right after the last, where the vector grows by one.
+///
 /// # Safety
 ///
 /// loads raw ptr
-pub unsafe extern "C" fn vec_len(ptr: *const RefCell<Vec<f64>>) -> f64 {
+pub unsafe extern "C" fn vec_check_set(ptr: *const Vector, idx: f64, line: i64, column: i64) {
+    vec_check(ptr, idx, 1, line, column);
+}
+
+unsafe fn string_check(ptr: *const RefCell<String>, idx: f64, end: usize, line: i64, column: i64) {
     let rc = Rc::from_raw(ptr);
-    let val = {
-        let vec = rc.borrow();
-        vec.len()
-    };
+    let len = rc.borrow().chars().count();
     std::mem::forget(rc);
-    val as f64
+    if let Some(message) = index_error("string", idx, len, end) {
+        runtime_error(message, line, column)
+    }
+}
+
+/// Aborts unless `idx` is the position of a character of the string.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_check_get(
+    ptr: *const RefCell<String>,
+    idx: f64,
+    line: i64,
+    column: i64,
+) {
+    string_check(ptr, idx, 0, line, column);
+}
+
+/// Aborts unless `idx` is the position of a character of the string or the
+/// one right after the last, where the string is appended to.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn string_check_set(
+    ptr: *const RefCell<String>,
+    idx: f64,
+    line: i64,
+    column: i64,
+) {
+    string_check(ptr, idx, 1, line, column);
+}
+
+/// Aborts unless the slice ending at `end` doesn't start after it.
+pub extern "C" fn vec_check_slice(start: f64, end: f64, line: i64, column: i64) {
+    if end < start {
+        runtime_error(
+            format!("slice end {} is before its start {}", end, start),
+            line,
+            column,
+        )
+    }
+}
+
+/// Aborts unless dividing `lhs` by `rhs` is defined for ints, which it isn't
+/// for a zero divisor or when the quotient overflows.
+pub extern "C" fn int_check_div(lhs: i64, rhs: i64, line: i64, column: i64) {
+    if rhs == 0 {
+        runtime_error("division by zero".to_string(), line, column)
+    }
+    if lhs == i64::MIN && rhs == -1 {
+        runtime_error(format!("overflow dividing {} by -1", lhs), line, column)
+    }
+}
+
+unsafe fn vec_insert(ptr: *const Vector, idx: f64, element: Capture) {
+    let rc = Rc::from_raw(ptr);
+    {
+        let mut elements = rc.elements.try_borrow_mut().unwrap();
+        index_guard(idx, elements.len(), 1);
+        elements.insert(idx as usize, element);
+    }
+    std::mem::forget(rc);
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_insert_numeric(ptr: *const Vector, idx: f64, val: f64) {
+    vec_insert(ptr, idx, Capture::Numeric(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_insert_int(ptr: *const Vector, idx: f64, val: i64) {
+    vec_insert(ptr, idx, Capture::Int(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_insert_bool(ptr: *const Vector, idx: f64, val: bool) {
+    vec_insert(ptr, idx, Capture::Bool(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_insert_string(
+    ptr: *const Vector,
+    idx: f64,
+    val: *const RefCell<String>,
+) {
+    inc_string_reference(val);
+    vec_insert(ptr, idx, Capture::String(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_insert_vec(ptr: *const Vector, idx: f64, val: *const Vector) {
+    inc_vec_reference(val);
+    vec_insert(ptr, idx, Capture::Vec(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_insert_env(ptr: *const Vector, idx: f64, val: *const Env) {
+    inc_env_reference(val);
+    vec_insert(ptr, idx, Capture::Env(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_insert_struct(ptr: *const Vector, idx: f64, val: *const Struct) {
+    inc_struct_reference(val);
+    vec_insert(ptr, idx, Capture::Struct(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_insert_ptr(ptr: *const Vector, idx: f64, val: *const c_void) {
+    vec_insert(ptr, idx, Capture::Ptr(val));
+}
+
+unsafe fn vec_remove(ptr: *const Vector, idx: f64) -> Capture {
+    let rc = Rc::from_raw(ptr);
+    let element = {
+        let mut elements = rc.elements.try_borrow_mut().unwrap();
+        index_guard(idx, elements.len(), 0);
+        elements.remove(idx as usize)
+    };
+    std::mem::forget(rc);
+    element
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_remove_numeric(ptr: *const Vector, idx: f64) -> f64 {
+    match vec_remove(ptr, idx) {
+        Capture::Numeric(val) => val,
+        _ => 0.,
+    }
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_remove_int(ptr: *const Vector, idx: f64) -> i64 {
+    match vec_remove(ptr, idx) {
+        Capture::Int(val) => val,
+        _ => 0,
+    }
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_remove_bool(ptr: *const Vector, idx: f64) -> bool {
+    match vec_remove(ptr, idx) {
+        Capture::Bool(val) => val,
+        _ => false,
+    }
+}
+
+/// The reference held by the vector is handed over to the caller.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_remove_ptr(ptr: *const Vector, idx: f64) -> *const c_void {
+    let element = vec_remove(ptr, idx);
+    let val = capture_ptr(&element);
+    std::mem::forget(element);
+    val
+}
+
+/// Elements from `start` up to, but not including, `end`. Both are clamped to
+/// the length of the vector, for calls compiled without bounds checks.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_slice(ptr: *const Vector, start: f64, end: f64) -> *const Vector {
+    let rc = Rc::from_raw(ptr);
+    let elements = {
+        let elements = rc.elements.borrow();
+        let end = (end as usize).min(elements.len());
+        let start = (start as usize).min(end);
+        elements[start..end].to_vec()
+    };
+    std::mem::forget(rc);
+    Rc::into_raw(Rc::new(Vector {
+        elements: RefCell::new(elements),
+    }))
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_concat(lhs: *const Vector, rhs: *const Vector) -> *const Vector {
+    let lhs = Rc::from_raw(lhs);
+    let rhs = Rc::from_raw(rhs);
+    let elements = [&lhs.elements.borrow()[..], &rhs.elements.borrow()[..]].concat();
+    std::mem::forget(lhs);
+    std::mem::forget(rhs);
+    Rc::into_raw(Rc::new(Vector {
+        elements: RefCell::new(elements),
+    }))
+}
+
+fn compare_captures(lhs: &Capture, rhs: &Capture) -> Ordering {
+    match (lhs, rhs) {
+        (Capture::Numeric(lhs), Capture::Numeric(rhs)) => lhs.total_cmp(rhs),
+        (Capture::Int(lhs), Capture::Int(rhs)) => lhs.cmp(rhs),
+        (Capture::Bool(lhs), Capture::Bool(rhs)) => lhs.cmp(rhs),
+        (Capture::String(lhs), Capture::String(rhs)) => unsafe {
+            (**lhs).borrow().cmp(&(**rhs).borrow())
+        },
+        _ => Ordering::Equal,
+    }
+}
+
+/// Sorts numbers, ints, bools and strings in ascending order.
+///
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_sort(ptr: *const Vector) {
+    let rc = Rc::from_raw(ptr);
+    rc.elements
+        .try_borrow_mut()
+        .unwrap()
+        .sort_by(compare_captures);
+    std::mem::forget(rc);
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_reverse(ptr: *const Vector) {
+    let rc = Rc::from_raw(ptr);
+    rc.elements.try_borrow_mut().unwrap().reverse();
+    std::mem::forget(rc);
+}
+
+unsafe fn vec_index_of(ptr: *const Vector, element: Capture) -> f64 {
+    let rc = Rc::from_raw(ptr);
+    let idx = rc
+        .elements
+        .borrow()
+        .iter()
+        .position(|capture| compare_captures(capture, &element) == Ordering::Equal);
+    std::mem::forget(rc);
+    // the element is only borrowed from the caller
+    std::mem::forget(element);
+    idx.map_or(-1., |idx| idx as f64)
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_index_of_numeric(ptr: *const Vector, val: f64) -> f64 {
+    vec_index_of(ptr, Capture::Numeric(val))
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_index_of_int(ptr: *const Vector, val: i64) -> f64 {
+    vec_index_of(ptr, Capture::Int(val))
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_index_of_bool(ptr: *const Vector, val: bool) -> f64 {
+    vec_index_of(ptr, Capture::Bool(val))
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn vec_index_of_string(
+    ptr: *const Vector,
+    val: *const RefCell<String>,
+) -> f64 {
+    vec_index_of(ptr, Capture::String(val))
+}
+
+pub enum Capture {
+    Numeric(f64),
+    Int(i64),
+    Bool(bool),
+    String(*const RefCell<String>),
+    Vec(*const Vector),
+    Env(*const Env),
+    Struct(*const Struct),
+    Ptr(*const c_void),
+}
+
+impl Clone for Capture {
+    fn clone(&self) -> Self {
+        unsafe {
+            match *self {
+                Capture::Numeric(val) => Capture::Numeric(val),
+                Capture::Int(val) => Capture::Int(val),
+                Capture::Bool(val) => Capture::Bool(val),
+                Capture::String(ptr) => {
+                    inc_string_reference(ptr);
+                    Capture::String(ptr)
+                }
+                Capture::Vec(ptr) => {
+                    inc_vec_reference(ptr);
+                    Capture::Vec(ptr)
+                }
+                Capture::Env(ptr) => {
+                    inc_env_reference(ptr);
+                    Capture::Env(ptr)
+                }
+                Capture::Struct(ptr) => {
+                    inc_struct_reference(ptr);
+                    Capture::Struct(ptr)
+                }
+                Capture::Ptr(ptr) => Capture::Ptr(ptr),
+            }
+        }
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        unsafe {
+            match *self {
+                Capture::String(ptr) => release_string_reference(ptr),
+                Capture::Vec(ptr) => release_vec_reference(ptr),
+                Capture::Env(ptr) => release_env_reference(ptr),
+                Capture::Struct(ptr) => release_struct_reference(ptr),
+                Capture::Numeric(_) | Capture::Int(_) | Capture::Bool(_) | Capture::Ptr(_) => {}
+            }
+        }
+    }
+}
+
+pub struct Env {
+    fun: *const c_void,
+    captures: RefCell<Vec<Capture>>,
+}
+
+pub extern "C" fn env_new(fun: *const c_void) -> *const Env {
+    let rc = Rc::new(Env {
+        fun,
+        captures: RefCell::new(Vec::new()),
+    });
+    Rc::into_raw(rc)
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_function(ptr: *const Env) -> *const c_void {
+    (*ptr).fun
+}
+
+fn slot_set(slots: &RefCell<Vec<Capture>>, idx: f64, capture: Capture) {
+    let mut slots = slots.try_borrow_mut().unwrap();
+    if (idx as usize) < slots.len() {
+        slots[idx as usize] = capture;
+    } else {
+        slots.push(capture);
+    }
+}
+
+fn slot_get<T>(slots: &RefCell<Vec<Capture>>, idx: f64, get: impl FnOnce(&Capture) -> T) -> T {
+    get(&slots.borrow()[idx as usize])
+}
+
+fn capture_ptr(capture: &Capture) -> *const c_void {
+    match *capture {
+        Capture::String(val) => val as *const c_void,
+        Capture::Vec(val) => val as *const c_void,
+        Capture::Env(val) => val as *const c_void,
+        Capture::Struct(val) => val as *const c_void,
+        Capture::Ptr(val) => val,
+        Capture::Numeric(_) | Capture::Int(_) | Capture::Bool(_) => unreachable!(),
+    }
+}
+
+unsafe fn env_set(ptr: *const Env, idx: f64, capture: Capture) {
+    let rc = Rc::from_raw(ptr);
+    slot_set(&rc.captures, idx, capture);
+    std::mem::forget(rc);
+}
+
+unsafe fn env_get<T>(ptr: *const Env, idx: f64, get: impl FnOnce(&Capture) -> T) -> T {
+    let rc = Rc::from_raw(ptr);
+    let val = slot_get(&rc.captures, idx, get);
+    std::mem::forget(rc);
+    val
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_set_numeric(ptr: *const Env, idx: f64, val: f64) {
+    env_set(ptr, idx, Capture::Numeric(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_set_int(ptr: *const Env, idx: f64, val: i64) {
+    env_set(ptr, idx, Capture::Int(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_set_bool(ptr: *const Env, idx: f64, val: bool) {
+    env_set(ptr, idx, Capture::Bool(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_set_string(ptr: *const Env, idx: f64, val: *const RefCell<String>) {
+    inc_string_reference(val);
+    env_set(ptr, idx, Capture::String(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_set_vec(ptr: *const Env, idx: f64, val: *const Vector) {
+    inc_vec_reference(val);
+    env_set(ptr, idx, Capture::Vec(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_set_env(ptr: *const Env, idx: f64, val: *const Env) {
+    inc_env_reference(val);
+    env_set(ptr, idx, Capture::Env(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_set_struct(ptr: *const Env, idx: f64, val: *const Struct) {
+    inc_struct_reference(val);
+    env_set(ptr, idx, Capture::Struct(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_set_ptr(ptr: *const Env, idx: f64, val: *const c_void) {
+    env_set(ptr, idx, Capture::Ptr(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_get_numeric(ptr: *const Env, idx: f64) -> f64 {
+    env_get(ptr, idx, |capture| match capture {
+        Capture::Numeric(val) => *val,
+        _ => unreachable!(),
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_get_int(ptr: *const Env, idx: f64) -> i64 {
+    env_get(ptr, idx, |capture| match capture {
+        Capture::Int(val) => *val,
+        _ => unreachable!(),
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_get_bool(ptr: *const Env, idx: f64) -> bool {
+    env_get(ptr, idx, |capture| match capture {
+        Capture::Bool(val) => *val,
+        _ => unreachable!(),
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn env_get_ptr(ptr: *const Env, idx: f64) -> *const c_void {
+    env_get(ptr, idx, capture_ptr)
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn inc_env_reference(ptr: *const Env) {
+    Rc::increment_strong_count(ptr);
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn release_env_reference(ptr: *const Env) {
+    Rc::decrement_strong_count(ptr);
+}
+
+pub struct Struct {
+    fields: RefCell<Vec<Capture>>,
+}
+
+pub extern "C" fn struct_new() -> *const Struct {
+    let rc = Rc::new(Struct {
+        fields: RefCell::new(Vec::new()),
+    });
+    Rc::into_raw(rc)
+}
+
+unsafe fn struct_set(ptr: *const Struct, idx: f64, field: Capture) {
+    let rc = Rc::from_raw(ptr);
+    slot_set(&rc.fields, idx, field);
+    std::mem::forget(rc);
+}
+
+unsafe fn struct_get<T>(ptr: *const Struct, idx: f64, get: impl FnOnce(&Capture) -> T) -> T {
+    let rc = Rc::from_raw(ptr);
+    let val = slot_get(&rc.fields, idx, get);
+    std::mem::forget(rc);
+    val
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_set_numeric(ptr: *const Struct, idx: f64, val: f64) {
+    struct_set(ptr, idx, Capture::Numeric(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_set_int(ptr: *const Struct, idx: f64, val: i64) {
+    struct_set(ptr, idx, Capture::Int(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_set_bool(ptr: *const Struct, idx: f64, val: bool) {
+    struct_set(ptr, idx, Capture::Bool(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_set_string(
+    ptr: *const Struct,
+    idx: f64,
+    val: *const RefCell<String>,
+) {
+    inc_string_reference(val);
+    struct_set(ptr, idx, Capture::String(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_set_vec(ptr: *const Struct, idx: f64, val: *const Vector) {
+    inc_vec_reference(val);
+    struct_set(ptr, idx, Capture::Vec(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_set_env(ptr: *const Struct, idx: f64, val: *const Env) {
+    inc_env_reference(val);
+    struct_set(ptr, idx, Capture::Env(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_set_struct(ptr: *const Struct, idx: f64, val: *const Struct) {
+    inc_struct_reference(val);
+    struct_set(ptr, idx, Capture::Struct(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_set_ptr(ptr: *const Struct, idx: f64, val: *const c_void) {
+    struct_set(ptr, idx, Capture::Ptr(val));
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_get_numeric(ptr: *const Struct, idx: f64) -> f64 {
+    struct_get(ptr, idx, |field| match field {
+        Capture::Numeric(val) => *val,
+        _ => unreachable!(),
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_get_int(ptr: *const Struct, idx: f64) -> i64 {
+    struct_get(ptr, idx, |field| match field {
+        Capture::Int(val) => *val,
+        _ => unreachable!(),
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_get_bool(ptr: *const Struct, idx: f64) -> bool {
+    struct_get(ptr, idx, |field| match field {
+        Capture::Bool(val) => *val,
+        _ => unreachable!(),
+    })
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn struct_get_ptr(ptr: *const Struct, idx: f64) -> *const c_void {
+    struct_get(ptr, idx, capture_ptr)
+}
+
+/// # Safety
+///
+/// loads raw ptr
+pub unsafe extern "C" fn inc_struct_reference(ptr: *const Struct) {
+    Rc::increment_strong_count(ptr);
 }
 
 /// # Safety
 ///
 /// loads raw ptr
-pub unsafe extern "C" fn release_vec_reference(ptr: *const RefCell<Vec<f64>>) {
+pub unsafe extern "C" fn release_struct_reference(ptr: *const Struct) {
     Rc::decrement_strong_count(ptr);
 }
@@ -48,7 +48,7 @@ macro_rules! in_main_function {
         main_fun.verify_function().unwrap_or_else(|_x| {
             println!("IR Dump:");
             println!("{}", $module);
-            assert!(false, "Function verification failed")
+            panic!("Function verification failed")
         });
     };
 }
@@ -66,6 +66,10 @@ macro_rules! mock_compiler {
                 fn visit_numeric(&mut self, expr: &f64) -> CompilerResult<Value>;
             }
 
+            impl IntegerVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_integer(&mut self, expr: &i64) -> CompilerResult<Value>;
+            }
+
             impl BinaryVisitor<CompilerResult<Value>> for Compiler {
                 fn visit_binary(&mut self, expr: &expression::Binary, span: Span) -> CompilerResult<Value>;
             }
@@ -79,7 +83,7 @@ macro_rules! mock_compiler {
             }
 
             impl FuncDeclVisitor<CompilerResult<Value>> for Compiler {
-                fn visit_func_decl(&mut self, body: &expression::FuncDecl) -> CompilerResult<Value>;
+                fn visit_func_decl(&mut self, body: &expression::FuncDecl, span: Span) -> CompilerResult<Value>;
             }
 
             impl StringVisitor<CompilerResult<Value>> for Compiler {
@@ -110,12 +114,51 @@ macro_rules! mock_compiler {
                 fn visit_while(&mut self, expr: &expression::While, span: Span) -> CompilerResult<Value>;
             }
 
+            impl ForVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_for(&mut self, expr: &expression::For, span: Span) -> CompilerResult<Value>;
+            }
+            impl MatchVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_match(&mut self, expr: &expression::Match, span: Span) -> CompilerResult<Value>;
+            }
+            impl StructDeclVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_struct_decl(&mut self, expr: &expression::StructDecl) -> CompilerResult<Value>;
+            }
+            impl StructLiteralVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_struct_literal(&mut self, expr: &expression::StructLiteral, span: Span) -> CompilerResult<Value>;
+            }
+            impl FieldAccessVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_field_access(&mut self, expr: &expression::FieldAccess, span: Span) -> CompilerResult<Value>;
+            }
+            impl IndexVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_index(&mut self, expr: &expression::Index, span: Span) -> CompilerResult<Value>;
+            }
+            impl VecLiteralVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_vec_literal(&mut self, expr: &expression::VecLiteral, span: Span) -> CompilerResult<Value>;
+            }
+            impl InterpolationVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_interpolation(&mut self, expr: &expression::Interpolation, span: Span) -> CompilerResult<Value>;
+            }
+            impl EnumDeclVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_enum_decl(&mut self, expr: &expression::EnumDecl) -> CompilerResult<Value>;
+            }
+            impl EnumVariantVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_enum_variant(&mut self, expr: &expression::EnumVariant, span: Span) -> CompilerResult<Value>;
+            }
+
             impl BoolVisitor<CompilerResult<Value>> for Compiler {
                 fn visit_bool(&mut self, expr: &bool) -> CompilerResult<Value>;
             }
 
             impl BreakVisitor<CompilerResult<Value>> for Compiler {
-                fn visit_break(&mut self) -> CompilerResult<Value>;
+                fn visit_break(&mut self, label: &Option<String>, span: Span) -> CompilerResult<Value>;
+            }
+
+            impl ContinueVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_continue(&mut self, label: &Option<String>, span: Span) -> CompilerResult<Value>;
+            }
+
+            impl ReturnVisitor<CompilerResult<Value>> for Compiler {
+                fn visit_return(&mut self, expr: &Option<Box<expression::Node>>, span: Span) -> CompilerResult<Value>;
             }
 
             impl LoadVisitor<CompilerResult<Value>> for Compiler {
@@ -137,15 +180,27 @@ macro_rules! mock_compiler {
                 fn enter_scope(&mut self);
                 fn exit_scope(&mut self) -> CompilerResult<()>;
                 fn get_var(&self, name: &str) -> Option<Variable>;
-                fn after_loop_blocks(&self) -> &Vec<llvm::BasicBlock>;
+                fn walk_expecting(&mut self, node: &expression::Node, typ: &parser::Type) -> CompilerResult<Value>;
+                fn build_loop_exit(&self, jump: &Value) -> CompilerResult<llvm::BasicBlock>;
                 fn track_maybe_orphaned(&mut self, val: Value);
                 fn release_maybe_orphaned(&mut self);
+                fn maybe_orphaned_len(&self) -> usize;
+                fn release_maybe_orphaned_since(&mut self, len: usize);
+                fn suspend_maybe_orphaned(&mut self);
+                fn resume_maybe_orphaned(&mut self);
+                fn assign_field(&mut self, expr: &expression::FieldAccess, right: &expression::Node, span: Span) -> CompilerResult<Value>;
+                fn assign_index(&mut self, expr: &expression::Index, right: &expression::Node, span: Span) -> CompilerResult<Value>;
+                fn compile_vec_call(&mut self, name: &str, expr: &expression::FuncCall, span: Span) -> CompilerResult<Value>;
                 fn get_builtin(&self, name: &str) -> Option<Variable>;
                 fn set_var(&mut self, name: &str, val: Variable);
+                fn take_prototype(&mut self, name: &str) -> Option<Value>;
+                fn build_closure(&mut self, val: Value) -> Value;
+                fn declare_function(&mut self, expr: &expression::FuncDecl) -> Value;
                 fn build_function(
                     &mut self,
                     fun_compiler_val: Value,
                     expr: &expression::FuncDecl,
+                    span: Span,
                 ) -> Result<(), CompilerError>;
             }
         }
@@ -1,3 +1,6 @@
+// the error tests keep the `matches!` form they were written in
+#![allow(clippy::redundant_pattern_matching)]
+
 #[macro_use]
 extern crate test_utils;
 
@@ -6,11 +9,12 @@ use pretty_assertions::assert_eq;
 
 use std::error::Error;
 
-use rocklang::compiler::{Compile, Compiler};
+use rocklang::compiler::{Compile, Compiler, CompilerError};
 
 use rocklang::expression::{
-    self, Assignment, Binary, Conditional, Expression, FuncCall, FuncDecl, Node, Operator, Unary,
-    While,
+    self, Assignment, Binary, Conditional, EnumDecl, EnumVariant, Expression, FieldAccess,
+    FieldValue, For, FuncCall, FuncDecl, Index, Interpolation, Iterable, Match, MatchArm, Node,
+    Operator, Pattern, StructDecl, StructLiteral, Unary, VariantDecl, VecLiteral, While,
 };
 use rocklang::parser::{Param, Program, Span, Type};
 
@@ -34,6 +38,10 @@ fn it_compiles_numeric_asignment() -> Result<(), Box<dyn Error>> {
 
         declare void* @string(double)
 
+        declare i64 @int(double)
+
+        declare double @number(i64)
+
         declare void @print(void*)
 
         declare void @release_string_reference(void*)
@@ -48,21 +56,247 @@ fn it_compiles_numeric_asignment() -> Result<(), Box<dyn Error>> {
 
         declare void* @string_from_c_string(i8*)
 
-        declare void* @vec_new()
+        declare i1 @string_equal(void*, void*)
+
+        declare void* @string_get(void*, double)
+
+        declare void* @string_set(void*, double, void*, i64, i64)
+
+        declare void* @string_from_int(i64)
+
+        declare void* @string_from_bool(i1)
+
+        declare void* @string_new()
+
+        declare void @string_append(void*, void*)
+
+        declare void* @string_concat(void*, void*)
+
+        declare i64 @string_compare(void*, void*)
+
+        declare double @string_len(void*)
+
+        declare void* @string_substring(void*, double, double)
+
+        declare double @string_find(void*, void*)
+
+        declare void* @string_split(void*, void*)
 
-        declare void @vec_set(void*, double, double)
+        declare void* @string_join(void*, void*)
 
-        declare double @vec_get(void*, double)
+        declare void* @string_trim(void*)
+
+        declare void* @string_replace(void*, void*, void*)
+
+        declare void* @string_upper(void*)
+
+        declare void* @string_lower(void*)
+
+        declare void* @string_char_at(void*, double)
+
+        declare double @string_to_number(void*)
+
+        declare i1 @is_nan(double)
+
+        declare void* @format_fixed(double, double)
+
+        declare void* @format_scientific(double, double)
+
+        declare void* @string_pad_left(void*, double, void*)
+
+        declare void* @string_pad_right(void*, double, void*)
+
+        declare void* @vec_new()
 
         declare double @vec_len(void*)
 
-        declare double @sqrt(double)
+        declare void @vec_set_numeric(void*, double, double)
+
+        declare void @vec_set_int(void*, double, i64)
+
+        declare void @vec_set_bool(void*, double, i1)
+
+        declare void @vec_set_string(void*, double, void*)
+
+        declare void @vec_set_vec(void*, double, void*)
+
+        declare void @vec_set_env(void*, double, void*)
+
+        declare void @vec_set_struct(void*, double, void*)
+
+        declare void @vec_set_ptr(void*, double, void*)
+
+        declare double @vec_get_numeric(void*, double)
+
+        declare i64 @vec_get_int(void*, double)
+
+        declare i1 @vec_get_bool(void*, double)
+
+        declare void* @vec_get_ptr(void*, double)
+
+        declare void @vec_check_get(void*, double, i64, i64)
+
+        declare void @vec_check_set(void*, double, i64, i64)
+
+        declare void @string_check_get(void*, double, i64, i64)
+
+        declare void @string_check_set(void*, double, i64, i64)
+
+        declare void @vec_check_slice(double, double, i64, i64)
+
+        declare void @int_check_div(i64, i64, i64, i64)
+
+        declare void @vec_insert_numeric(void*, double, double)
+
+        declare void @vec_insert_int(void*, double, i64)
+
+        declare void @vec_insert_bool(void*, double, i1)
+
+        declare void @vec_insert_string(void*, double, void*)
+
+        declare void @vec_insert_vec(void*, double, void*)
+
+        declare void @vec_insert_env(void*, double, void*)
+
+        declare void @vec_insert_struct(void*, double, void*)
+
+        declare void @vec_insert_ptr(void*, double, void*)
+
+        declare double @vec_remove_numeric(void*, double)
+
+        declare i64 @vec_remove_int(void*, double)
+
+        declare i1 @vec_remove_bool(void*, double)
+
+        declare void* @vec_remove_ptr(void*, double)
+
+        declare void* @vec_slice(void*, double, double)
+
+        declare void* @vec_concat(void*, void*)
+
+        declare void @vec_sort(void*)
+
+        declare void @vec_reverse(void*)
+
+        declare double @vec_index_of_numeric(void*, double)
+
+        declare double @vec_index_of_int(void*, i64)
+
+        declare double @vec_index_of_bool(void*, i1)
+
+        declare double @vec_index_of_string(void*, void*)
+
+        declare void* @env_new(void*)
+
+        declare void @env_set_numeric(void*, double, double)
+
+        declare void @env_set_int(void*, double, i64)
+
+        declare void @env_set_bool(void*, double, i1)
+
+        declare void @env_set_string(void*, double, void*)
+
+        declare void @env_set_vec(void*, double, void*)
+
+        declare void @env_set_env(void*, double, void*)
+
+        declare void @env_set_struct(void*, double, void*)
+
+        declare void @env_set_ptr(void*, double, void*)
+
+        declare double @env_get_numeric(void*, double)
+
+        declare i64 @env_get_int(void*, double)
+
+        declare i1 @env_get_bool(void*, double)
+
+        declare void* @env_get_ptr(void*, double)
+
+        declare void* @env_function(void*)
+
+        declare void @inc_env_reference(void*)
+
+        declare void @release_env_reference(void*)
+
+        declare void* @struct_new()
+
+        declare void @struct_set_numeric(void*, double, double)
+
+        declare void @struct_set_int(void*, double, i64)
+
+        declare void @struct_set_bool(void*, double, i1)
+
+        declare void @struct_set_string(void*, double, void*)
+
+        declare void @struct_set_vec(void*, double, void*)
+
+        declare void @struct_set_env(void*, double, void*)
+
+        declare void @struct_set_struct(void*, double, void*)
+
+        declare void @struct_set_ptr(void*, double, void*)
+
+        declare double @struct_get_numeric(void*, double)
+
+        declare i64 @struct_get_int(void*, double)
+
+        declare i1 @struct_get_bool(void*, double)
+
+        declare void* @struct_get_ptr(void*, double)
+
+        declare void @inc_struct_reference(void*)
+
+        declare void @release_struct_reference(void*)
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.sqrt.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.sin.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.cos.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.exp.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.log.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.pow.f64(double, double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.floor.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.ceil.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.round.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.fabs.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.minnum.f64(double, double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.maxnum.f64(double, double) #0
+
+        declare double @tan(double)
+
+        declare double @atan2(double, double)
+
+        declare double @fmod(double, double)
 
         define void @main() {
           %1 = alloca double, align 8
           store double 5.000000e+00, double* %1, align 8
           ret void
         }
+
+        attributes #0 = { nofree nosync nounwind readnone speculatable willreturn }
         "#
     );
     Ok(())
@@ -94,6 +328,10 @@ fn it_compiles_numeric_to_numeric_asignment() -> Result<(), Box<dyn Error>> {
 
         declare void* @string(double)
 
+        declare i64 @int(double)
+
+        declare double @number(i64)
+
         declare void @print(void*)
 
         declare void @release_string_reference(void*)
@@ -108,15 +346,239 @@ fn it_compiles_numeric_to_numeric_asignment() -> Result<(), Box<dyn Error>> {
 
         declare void* @string_from_c_string(i8*)
 
-        declare void* @vec_new()
+        declare i1 @string_equal(void*, void*)
+
+        declare void* @string_get(void*, double)
+
+        declare void* @string_set(void*, double, void*, i64, i64)
+
+        declare void* @string_from_int(i64)
+
+        declare void* @string_from_bool(i1)
+
+        declare void* @string_new()
+
+        declare void @string_append(void*, void*)
+
+        declare void* @string_concat(void*, void*)
+
+        declare i64 @string_compare(void*, void*)
+
+        declare double @string_len(void*)
+
+        declare void* @string_substring(void*, double, double)
+
+        declare double @string_find(void*, void*)
+
+        declare void* @string_split(void*, void*)
+
+        declare void* @string_join(void*, void*)
 
-        declare void @vec_set(void*, double, double)
+        declare void* @string_trim(void*)
 
-        declare double @vec_get(void*, double)
+        declare void* @string_replace(void*, void*, void*)
+
+        declare void* @string_upper(void*)
+
+        declare void* @string_lower(void*)
+
+        declare void* @string_char_at(void*, double)
+
+        declare double @string_to_number(void*)
+
+        declare i1 @is_nan(double)
+
+        declare void* @format_fixed(double, double)
+
+        declare void* @format_scientific(double, double)
+
+        declare void* @string_pad_left(void*, double, void*)
+
+        declare void* @string_pad_right(void*, double, void*)
+
+        declare void* @vec_new()
 
         declare double @vec_len(void*)
 
-        declare double @sqrt(double)
+        declare void @vec_set_numeric(void*, double, double)
+
+        declare void @vec_set_int(void*, double, i64)
+
+        declare void @vec_set_bool(void*, double, i1)
+
+        declare void @vec_set_string(void*, double, void*)
+
+        declare void @vec_set_vec(void*, double, void*)
+
+        declare void @vec_set_env(void*, double, void*)
+
+        declare void @vec_set_struct(void*, double, void*)
+
+        declare void @vec_set_ptr(void*, double, void*)
+
+        declare double @vec_get_numeric(void*, double)
+
+        declare i64 @vec_get_int(void*, double)
+
+        declare i1 @vec_get_bool(void*, double)
+
+        declare void* @vec_get_ptr(void*, double)
+
+        declare void @vec_check_get(void*, double, i64, i64)
+
+        declare void @vec_check_set(void*, double, i64, i64)
+
+        declare void @string_check_get(void*, double, i64, i64)
+
+        declare void @string_check_set(void*, double, i64, i64)
+
+        declare void @vec_check_slice(double, double, i64, i64)
+
+        declare void @int_check_div(i64, i64, i64, i64)
+
+        declare void @vec_insert_numeric(void*, double, double)
+
+        declare void @vec_insert_int(void*, double, i64)
+
+        declare void @vec_insert_bool(void*, double, i1)
+
+        declare void @vec_insert_string(void*, double, void*)
+
+        declare void @vec_insert_vec(void*, double, void*)
+
+        declare void @vec_insert_env(void*, double, void*)
+
+        declare void @vec_insert_struct(void*, double, void*)
+
+        declare void @vec_insert_ptr(void*, double, void*)
+
+        declare double @vec_remove_numeric(void*, double)
+
+        declare i64 @vec_remove_int(void*, double)
+
+        declare i1 @vec_remove_bool(void*, double)
+
+        declare void* @vec_remove_ptr(void*, double)
+
+        declare void* @vec_slice(void*, double, double)
+
+        declare void* @vec_concat(void*, void*)
+
+        declare void @vec_sort(void*)
+
+        declare void @vec_reverse(void*)
+
+        declare double @vec_index_of_numeric(void*, double)
+
+        declare double @vec_index_of_int(void*, i64)
+
+        declare double @vec_index_of_bool(void*, i1)
+
+        declare double @vec_index_of_string(void*, void*)
+
+        declare void* @env_new(void*)
+
+        declare void @env_set_numeric(void*, double, double)
+
+        declare void @env_set_int(void*, double, i64)
+
+        declare void @env_set_bool(void*, double, i1)
+
+        declare void @env_set_string(void*, double, void*)
+
+        declare void @env_set_vec(void*, double, void*)
+
+        declare void @env_set_env(void*, double, void*)
+
+        declare void @env_set_struct(void*, double, void*)
+
+        declare void @env_set_ptr(void*, double, void*)
+
+        declare double @env_get_numeric(void*, double)
+
+        declare i64 @env_get_int(void*, double)
+
+        declare i1 @env_get_bool(void*, double)
+
+        declare void* @env_get_ptr(void*, double)
+
+        declare void* @env_function(void*)
+
+        declare void @inc_env_reference(void*)
+
+        declare void @release_env_reference(void*)
+
+        declare void* @struct_new()
+
+        declare void @struct_set_numeric(void*, double, double)
+
+        declare void @struct_set_int(void*, double, i64)
+
+        declare void @struct_set_bool(void*, double, i1)
+
+        declare void @struct_set_string(void*, double, void*)
+
+        declare void @struct_set_vec(void*, double, void*)
+
+        declare void @struct_set_env(void*, double, void*)
+
+        declare void @struct_set_struct(void*, double, void*)
+
+        declare void @struct_set_ptr(void*, double, void*)
+
+        declare double @struct_get_numeric(void*, double)
+
+        declare i64 @struct_get_int(void*, double)
+
+        declare i1 @struct_get_bool(void*, double)
+
+        declare void* @struct_get_ptr(void*, double)
+
+        declare void @inc_struct_reference(void*)
+
+        declare void @release_struct_reference(void*)
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.sqrt.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.sin.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.cos.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.exp.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.log.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.pow.f64(double, double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.floor.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.ceil.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.round.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.fabs.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.minnum.f64(double, double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.maxnum.f64(double, double) #0
+
+        declare double @tan(double)
+
+        declare double @atan2(double, double)
+
+        declare double @fmod(double, double)
 
         define void @main() {
           %1 = alloca double, align 8
@@ -126,6 +588,8 @@ fn it_compiles_numeric_to_numeric_asignment() -> Result<(), Box<dyn Error>> {
           store double %2, double* %3, align 8
           ret void
         }
+
+        attributes #0 = { nofree nosync nounwind readnone speculatable willreturn }
         "#
     );
 
@@ -158,14 +622,16 @@ fn it_compiles_new_vec_being_passed_as_fun_arg() -> Result<(), Box<dyn Error>> {
                     return_type: Type::Void,
                     params: vec![Param {
                         name: "v".to_string(),
-                        typ: Type::Vector,
+                        typ: Type::Vector(Box::new(Type::Numeric)),
                     }],
                 })),
             })),
             node!(Expression::FuncCall(FuncCall {
                 calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
                 args: vec![node!(Expression::FuncCall(FuncCall {
                     calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                    type_args: vec![],
                     args: vec![],
                 }))],
             })),
@@ -177,27 +643,141 @@ fn it_compiles_new_vec_being_passed_as_fun_arg() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Passes `arg` to a function taking a vector of `element`.
+fn vec_arg_program(element: Type, arg: Expression) -> Program {
+    Program {
+        body: vec![
+            complex_decl(),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    body: vec![],
+                    return_type: Type::Void,
+                    params: vec![Param {
+                        name: "v".to_string(),
+                        typ: Type::Vector(Box::new(element)),
+                    }],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
+                args: vec![node!(arg)],
+            })),
+        ],
+    }
+}
+
+#[test]
+fn it_compiles_empty_vecs_of_expected_element_type() -> Result<(), Box<dyn Error>> {
+    let elements = [
+        Type::Numeric,
+        Type::Int,
+        Type::Bool,
+        Type::String,
+        Type::Vector(Box::new(Type::String)),
+        Type::Struct("Complex".to_string()),
+    ];
+    for element in elements {
+        let empty = [
+            Expression::VecLiteral(VecLiteral { elements: vec![] }),
+            Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                type_args: vec![],
+                args: vec![],
+            }),
+        ];
+        for arg in empty {
+            let mut compiler = Compiler::new(vec_arg_program(element.clone(), arg))?;
+            compiler.compile().unwrap();
+        }
+    }
+    Ok(())
+}
+
 #[test]
-fn it_compiles_print_function_with_global_string() -> Result<(), Box<dyn Error>> {
-    let program = Program {
-        body: vec![node!(Expression::FuncCall(FuncCall {
-            calee: boxed_node!(Expression::Identifier("print".to_string())),
-            args: vec![node!(Expression::String("name".to_string()))],
-        }))],
-    };
+fn it_compiles_int_vec_literal_passed_as_vec_of_int() -> Result<(), Box<dyn Error>> {
+    let arg = Expression::VecLiteral(VecLiteral {
+        elements: vec![node!(Expression::Integer(1)), node!(Expression::Integer(2))],
+    });
 
-    let mut compiler = Compiler::new(program)?;
+    let mut compiler = Compiler::new(vec_arg_program(Type::Int, arg))?;
     compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_compiles_print_function_with_string() -> Result<(), Box<dyn Error>> {
+fn it_compiles_vec_literals_of_struct_fields() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::StructDecl(StructDecl {
+                name: "N".to_string(),
+                fields: vec![Param {
+                    name: "next".to_string(),
+                    typ: Type::Vector(Box::new(Type::Struct("N".to_string()))),
+                }],
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("n".to_string())),
+                right: boxed_node!(Expression::StructLiteral(StructLiteral {
+                    name: "N".to_string(),
+                    fields: vec![FieldValue {
+                        name: "next".to_string(),
+                        value: node!(Expression::VecLiteral(VecLiteral { elements: vec![] })),
+                    }],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_vec_literal_elements_are_not_of_expected_type(
+) -> Result<(), Box<dyn Error>> {
+    let arg = Expression::VecLiteral(VecLiteral {
+        elements: vec![node!(Expression::Numeric(1.5))],
+    });
+
+    let mut compiler = Compiler::new(vec_arg_program(Type::Int, arg))?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Int,
+            actual: Type::Numeric,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_print_function_with_global_string() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("print".to_string())),
+            type_args: vec![],
+            args: vec![node!(Expression::String("name".to_string()))],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_print_function_with_string() -> Result<(), Box<dyn Error>> {
     let program = Program {
         body: vec![node!(Expression::FuncCall(FuncCall {
             calee: boxed_node!(Expression::Identifier("print".to_string())),
+            type_args: vec![],
             args: vec![node!(Expression::FuncCall(FuncCall {
                 calee: boxed_node!(Expression::Identifier("string".to_string())),
+                type_args: vec![],
                 args: vec![node!(Expression::Numeric(10.0))],
             }))],
         }))],
@@ -213,8 +793,10 @@ fn it_compiles_len_function_when_pass_new_vec() -> Result<(), Box<dyn Error>> {
     let program = Program {
         body: vec![node!(Expression::FuncCall(FuncCall {
             calee: boxed_node!(Expression::Identifier("vec_len".to_string())),
+            type_args: vec![],
             args: vec![node!(Expression::FuncCall(FuncCall {
                 calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                type_args: vec![],
                 args: vec![],
             }))],
         }))],
@@ -233,11 +815,13 @@ fn it_compiles_vec_get_function() -> Result<(), Box<dyn Error>> {
                 left: boxed_node!(Expression::Identifier("z".to_string())),
                 right: boxed_node!(Expression::FuncCall(FuncCall {
                     calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                    type_args: vec![],
                     args: vec![],
                 })),
             })),
             node!(Expression::FuncCall(FuncCall {
                 calee: boxed_node!(Expression::Identifier("vec_get".to_string())),
+                type_args: vec![],
                 args: vec![
                     node!(Expression::Identifier("z".to_string())),
                     node!(Expression::Numeric(0.0)),
@@ -252,12 +836,39 @@ fn it_compiles_vec_get_function() -> Result<(), Box<dyn Error>> {
 }
 
 #[test]
-fn it_compiles_sqrt_funcion() -> Result<(), Box<dyn Error>> {
+fn it_compiles_vec_of_strings() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::FuncCall(FuncCall {
-            calee: boxed_node!(Expression::Identifier("sqrt".to_string())),
-            args: vec![node!(Expression::Numeric(4.0))],
-        }))],
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("z".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                    type_args: vec![Type::String],
+                    args: vec![],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_set".to_string())),
+                type_args: vec![],
+                args: vec![
+                    node!(Expression::Identifier("z".to_string())),
+                    node!(Expression::Numeric(0.0)),
+                    node!(Expression::String("name".to_string())),
+                ],
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("print".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_get".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("z".to_string())),
+                        node!(Expression::Integer(0)),
+                    ],
+                }))],
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
@@ -266,89 +877,148 @@ fn it_compiles_sqrt_funcion() -> Result<(), Box<dyn Error>> {
 }
 
 #[test]
-fn it_returns_err_when_more_then_one_arg_pass_to_print_funcion() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_setting_element_of_other_type() -> Result<(), Box<dyn Error>> {
     let program = Program {
         body: vec![node!(Expression::FuncCall(FuncCall {
-            calee: boxed_node!(Expression::Identifier("print".to_string())),
+            calee: boxed_node!(Expression::Identifier("vec_set".to_string())),
+            type_args: vec![],
             args: vec![
-                node!(Expression::String("name".to_string())),
-                node!(Expression::String("foo".to_string())),
+                node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                    type_args: vec![Type::Vector(Box::new(Type::String))],
+                    args: vec![],
+                })),
+                node!(Expression::Numeric(0.0)),
+                node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                    type_args: vec![Type::Numeric],
+                    args: vec![],
+                })),
             ],
         }))],
     };
 
     let mut compiler = Compiler::new(program)?;
-    assert!(matches!(compiler.compile(), Err(_)));
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Vector(_),
+            actual: Type::Vector(_),
+            ..
+        })
+    ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_non_sring_type_pass_to_print_funcions() -> Result<(), Box<dyn Error>> {
+fn it_returns_err_when_passing_type_args_to_non_generic_function() -> Result<(), Box<dyn Error>> {
     let program = Program {
         body: vec![node!(Expression::FuncCall(FuncCall {
             calee: boxed_node!(Expression::Identifier("print".to_string())),
-            args: vec![node!(Expression::Numeric(10.0))],
+            type_args: vec![Type::String],
+            args: vec![node!(Expression::String("name".to_string()))],
         }))],
     };
 
     let mut compiler = Compiler::new(program)?;
-    assert!(matches!(compiler.compile(), Err(_)));
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::WrongTypeArgumentCount {
+            expected: 0,
+            actual: 1,
+            ..
+        })
+    ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_zero_args_pass_to_string_funcion() -> Result<(), Box<dyn Error>> {
+fn it_compiles_index_of_vec_and_string() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::FuncCall(FuncCall {
-            calee: boxed_node!(Expression::Identifier("string".to_string())),
-            args: vec![],
-        }))],
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("z".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                    type_args: vec![Type::String],
+                    args: vec![],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::Identifier("z".to_string())),
+                    index: boxed_node!(Expression::Integer(0)),
+                })),
+                right: boxed_node!(Expression::String("name".to_string())),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::Index(Index {
+                        object: boxed_node!(Expression::Identifier("z".to_string())),
+                        index: boxed_node!(Expression::Integer(0)),
+                    })),
+                    index: boxed_node!(Expression::Numeric(1.0)),
+                })),
+                right: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::String("other".to_string())),
+                    index: boxed_node!(Expression::Integer(2)),
+                })),
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
-    assert!(matches!(compiler.compile(), Err(_)));
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_bool_arg_pass_to_string_funcion() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_indexing_non_vec() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::FuncCall(FuncCall {
-            calee: boxed_node!(Expression::Identifier("string".to_string())),
-            args: vec![node!(Expression::Bool(true))],
+        body: vec![node!(Expression::Index(Index {
+            object: boxed_node!(Expression::Bool(true)),
+            index: boxed_node!(Expression::Integer(0)),
         }))],
     };
 
     let mut compiler = Compiler::new(program)?;
-    assert!(matches!(compiler.compile(), Err(_)));
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Vector(_),
+            actual: Type::Bool,
+            ..
+        })
+    ));
     Ok(())
 }
 
 #[test]
-fn it_compiles_new_vec_being_passed_as_variable() -> Result<(), Box<dyn Error>> {
+fn it_compiles_vec_literal() -> Result<(), Box<dyn Error>> {
     let program = Program {
         body: vec![
             node!(Expression::Assignment(Assignment {
-                left: boxed_node!(Expression::Identifier("f".to_string())),
-                right: boxed_node!(Expression::FuncDecl(FuncDecl {
-                    body: vec![],
-                    return_type: Type::Void,
-                    params: vec![Param {
-                        name: "v".to_string(),
-                        typ: Type::Vector,
-                    }],
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
                 })),
             })),
             node!(Expression::Assignment(Assignment {
-                left: boxed_node!(Expression::Identifier("vecinvar".to_string())),
-                right: boxed_node!(Expression::FuncCall(FuncCall {
-                    calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
-                    args: vec![],
-                })),
+                left: boxed_node!(Expression::Identifier("w".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral { elements: vec![] })),
             })),
-            node!(Expression::FuncCall(FuncCall {
-                calee: boxed_node!(Expression::Identifier("f".to_string())),
-                args: vec![node!(Expression::Identifier("vecinvar".to_string()))],
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("z".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::String("a".to_string())),
+                        node!(Expression::String("b".to_string())),
+                    ],
+                })),
             })),
         ],
     };
@@ -359,33 +1029,54 @@ fn it_compiles_new_vec_being_passed_as_variable() -> Result<(), Box<dyn Error>>
 }
 
 #[test]
-fn it_compiles_recursive_fun() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_vec_literal_elements_differ() -> Result<(), Box<dyn Error>> {
     let program = Program {
         body: vec![node!(Expression::Assignment(Assignment {
-            left: boxed_node!(Expression::Identifier("f".to_string())),
-            right: boxed_node!(Expression::FuncDecl(FuncDecl {
-                return_type: Type::Void,
-                params: vec![],
-                body: vec![node!(Expression::FuncCall(FuncCall {
-                    calee: boxed_node!(Expression::Identifier("f".to_string())),
-                    args: vec![],
-                }))],
+            left: boxed_node!(Expression::Identifier("v".to_string())),
+            right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                elements: vec![
+                    node!(Expression::Integer(1)),
+                    node!(Expression::String("a".to_string())),
+                ],
             })),
         }))],
     };
 
     let mut compiler = Compiler::new(program)?;
-    compiler.compile().unwrap();
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Numeric,
+            actual: Type::String,
+            ..
+        })
+    ));
     Ok(())
 }
 
 #[test]
-fn it_compiles_while_statment() -> Result<(), Box<dyn Error>> {
+fn it_compiles_vec_push() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::While(While {
-            predicate: boxed_node!(Expression::Bool(false)),
-            body: vec![],
-        }))],
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_push".to_string())),
+                type_args: vec![],
+                args: vec![
+                    node!(Expression::Identifier("v".to_string())),
+                    node!(Expression::Integer(4)),
+                ],
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
@@ -394,24 +1085,26 @@ fn it_compiles_while_statment() -> Result<(), Box<dyn Error>> {
 }
 
 #[test]
-fn it_test_visit_func_decl() -> Result<(), Box<dyn Error>> {
+fn it_compiles_vec_pop() -> Result<(), Box<dyn Error>> {
     let program = Program {
         body: vec![
-            node!(Expression::FuncDecl(FuncDecl {
-                params: vec![Param {
-                    typ: Type::Numeric,
-                    name: "n".to_string(),
-                }],
-                body: vec![node!(Expression::String("n".to_string()))],
-                return_type: Type::Numeric,
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
             })),
-            node!(Expression::FuncDecl(FuncDecl {
-                params: vec![Param {
-                    typ: Type::Vector,
-                    name: "n".to_string(),
-                }],
-                body: vec![node!(Expression::String("n".to_string()))],
-                return_type: Type::Vector,
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_pop".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Identifier("v".to_string()))],
+                })),
             })),
         ],
     };
@@ -422,18 +1115,29 @@ fn it_test_visit_func_decl() -> Result<(), Box<dyn Error>> {
 }
 
 #[test]
-fn it_compiles_grouping_expressions() -> Result<(), Box<dyn Error>> {
-    let grouping = expression::Grouping(boxed_node!(Expression::Binary(Binary {
-        left: boxed_node!(Expression::Numeric(10.0)),
-        operator: Operator::NotEqual,
-        right: boxed_node!(Expression::Numeric(2.0)),
-    })));
-
+fn it_compiles_vec_insert() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::Assignment(Assignment {
-            left: boxed_node!(Expression::Identifier("x".to_string())),
-            right: boxed_node!(Expression::Grouping(grouping)),
-        }))],
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_insert".to_string())),
+                type_args: vec![],
+                args: vec![
+                    node!(Expression::Identifier("v".to_string())),
+                    node!(Expression::Integer(0)),
+                    node!(Expression::Numeric(4.0)),
+                ],
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
@@ -442,17 +1146,31 @@ fn it_compiles_grouping_expressions() -> Result<(), Box<dyn Error>> {
 }
 
 #[test]
-fn it_compiles_coditional() -> Result<(), Box<dyn Error>> {
+fn it_compiles_vec_remove() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::Conditional(Conditional {
-            predicate: boxed_node!(Expression::Binary(Binary {
-                left: boxed_node!(Expression::Numeric(10.0)),
-                operator: Operator::NotEqual,
-                right: boxed_node!(Expression::Numeric(2.0)),
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
             })),
-            body: vec![node!(Expression::Numeric(10.0))],
-            else_body: vec![node!(Expression::Numeric(20.0))],
-        }))],
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_remove".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("v".to_string())),
+                        node!(Expression::Integer(1)),
+                    ],
+                })),
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
@@ -460,561 +1178,3535 @@ fn it_compiles_coditional() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-macro_rules! compile_operator {
-    ($left_operator:expr, $operator:expr, $rigth_operator:expr) => {{
-        let program = Program {
-            body: vec![node!(Expression::Assignment(Assignment {
-                left: boxed_node!(Expression::Identifier("b".to_string())),
-                right: boxed_node!(Expression::Binary(Binary {
-                    left: $left_operator,
-                    operator: $operator,
-                    right: $rigth_operator,
+#[test]
+fn it_compiles_vec_slice() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
                 })),
-            }))],
-        };
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_slice".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("v".to_string())),
+                        node!(Expression::Integer(1)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+        ],
+    };
 
-        let mut compiler = Compiler::new(program)?;
-        compiler.compile()
-    }};
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
 }
 
 #[test]
-fn it_compiles_operators() -> Result<(), Box<dyn Error>> {
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::Plus,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
-
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::Minus,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+fn it_compiles_vec_concat() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_concat".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("v".to_string())),
+                        node!(Expression::Identifier("v".to_string())),
+                    ],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_concatenating_vecs_of_other_types() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_concat".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("v".to_string())),
+                        node!(Expression::VecLiteral(VecLiteral {
+                            elements: vec![node!(Expression::Bool(true))],
+                        })),
+                    ],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Vector(_),
+            actual: Type::Vector(_),
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_vec_sort() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_sort".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::Identifier("v".to_string()))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_sorting_vec_of_vecs() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![node!(Expression::VecLiteral(VecLiteral {
+                        elements: vec![node!(Expression::Integer(1))],
+                    }))],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_sort".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::Identifier("v".to_string()))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Vector(_),
+            actual: Type::Vector(_),
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_vec_reverse() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_reverse".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::Identifier("v".to_string()))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_vec_contains() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_contains".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("v".to_string())),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_vec_index_of() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![
+                        node!(Expression::Integer(3)),
+                        node!(Expression::Numeric(1.5)),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_index_of".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("v".to_string())),
+                        node!(Expression::Numeric(1.5)),
+                    ],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_checks_bounds_of_vec_index() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![node!(Expression::Integer(1))],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::Identifier("v".to_string())),
+                    index: boxed_node!(Expression::Integer(1)),
+                })),
+                right: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::Identifier("v".to_string())),
+                    index: boxed_node!(Expression::Integer(0)),
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(ir.contains("call void @vec_check_get("));
+    assert!(ir.contains("call void @vec_check_set("));
+    Ok(())
+}
+
+fn string_indexing() -> Program {
+    Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("s".to_string())),
+                right: boxed_node!(Expression::String("ab".to_string())),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::Identifier("s".to_string())),
+                    index: boxed_node!(Expression::Integer(2)),
+                })),
+                right: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::Identifier("s".to_string())),
+                    index: boxed_node!(Expression::Integer(0)),
+                })),
+            })),
+        ],
+    }
+}
+
+#[test]
+fn it_checks_bounds_of_string_index() -> Result<(), Box<dyn Error>> {
+    let mut compiler = Compiler::new(string_indexing())?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(ir.contains("call void @string_check_get("));
+    assert!(ir.contains("call void @string_check_set("));
+    Ok(())
+}
+
+#[test]
+fn it_does_not_check_bounds_of_string_index_when_turned_off() -> Result<(), Box<dyn Error>> {
+    let mut compiler = Compiler::new(string_indexing())?;
+    compiler.turn_off_optimization();
+    compiler.turn_off_bounds_checks();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(!ir.contains("call void @string_check_get("));
+    assert!(!ir.contains("call void @string_check_set("));
+    Ok(())
+}
+
+#[test]
+fn it_does_not_check_bounds_when_turned_off() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("v".to_string())),
+                right: boxed_node!(Expression::VecLiteral(VecLiteral {
+                    elements: vec![node!(Expression::Integer(1))],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::Identifier("v".to_string())),
+                    index: boxed_node!(Expression::Integer(1)),
+                })),
+                right: boxed_node!(Expression::Index(Index {
+                    object: boxed_node!(Expression::Identifier("v".to_string())),
+                    index: boxed_node!(Expression::Integer(0)),
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.turn_off_bounds_checks();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(!ir.contains("call void @vec_check_get("));
+    assert!(!ir.contains("call void @vec_check_set("));
+    Ok(())
+}
+
+fn int_call(value: f64) -> Expression {
+    Expression::FuncCall(FuncCall {
+        calee: boxed_node!(Expression::Identifier("int".to_string())),
+        type_args: vec![],
+        args: vec![node!(Expression::Numeric(value))],
+    })
+}
+
+#[test]
+fn it_compiles_interpolation() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("s".to_string())),
+            right: boxed_node!(Expression::Interpolation(Interpolation {
+                parts: vec![
+                    node!(Expression::String("x = ".to_string())),
+                    node!(Expression::Numeric(1.5)),
+                    node!(int_call(2.0)),
+                    node!(Expression::Bool(true)),
+                ],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(ir.contains("call void* @string_new()"));
+    assert!(ir.contains("call void* @string(double 1.500000e+00)"));
+    assert!(ir.contains("call void* @string_from_int(i64 %"));
+    assert!(ir.contains("call void* @string_from_bool(i1 true)"));
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_interpolating_value_without_string_form() -> Result<(), Box<dyn Error>>
+{
+    let program = Program {
+        body: vec![node!(Expression::Interpolation(Interpolation {
+            parts: vec![node!(Expression::VecLiteral(VecLiteral {
+                elements: vec![]
+            }))],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::String,
+            actual: Type::Vector(_),
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_string_concatenation_and_ordering() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("s".to_string())),
+                right: boxed_node!(Expression::Binary(Binary {
+                    left: boxed_node!(Expression::String("a".to_string())),
+                    operator: Operator::Plus,
+                    right: boxed_node!(Expression::String("b".to_string())),
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("b".to_string())),
+                right: boxed_node!(Expression::Binary(Binary {
+                    left: boxed_node!(Expression::Identifier("s".to_string())),
+                    operator: Operator::LessOrEqual,
+                    right: boxed_node!(Expression::String("c".to_string())),
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(ir.contains("call void* @string_concat("));
+    assert!(ir.contains("call i64 @string_compare("));
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_adding_string_and_number() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Binary(Binary {
+            left: boxed_node!(Expression::String("a".to_string())),
+            operator: Operator::Plus,
+            right: boxed_node!(Expression::Numeric(1.0)),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::String,
+            actual: Type::Numeric,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_adding_int_and_number() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Binary(Binary {
+            left: boxed_node!(int_call(1.0)),
+            operator: Operator::Plus,
+            right: boxed_node!(Expression::Numeric(0.5)),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Int,
+            actual: Type::Numeric,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_passing_int_as_number() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    body: vec![],
+                    return_type: Type::Void,
+                    params: vec![Param {
+                        name: "x".to_string(),
+                        typ: Type::Numeric,
+                    }],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
+                args: vec![node!(int_call(1.0))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Numeric,
+            actual: Type::Int,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_string_builtins() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("s".to_string())),
+                right: boxed_node!(Expression::String(" a,b ".to_string())),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("len".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_len".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Identifier("s".to_string())),],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("substring".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_substring".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("s".to_string())),
+                        node!(Expression::Integer(1)),
+                        node!(Expression::Numeric(3.0)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("find".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_find".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("s".to_string())),
+                        node!(Expression::String("b".to_string())),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("parts".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_split".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("s".to_string())),
+                        node!(Expression::String(",".to_string())),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("join".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_join".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("parts".to_string())),
+                        node!(Expression::String(", ".to_string())),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("trim".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_trim".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Identifier("s".to_string())),],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("replace".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_replace".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("s".to_string())),
+                        node!(Expression::String("a".to_string())),
+                        node!(Expression::String("b".to_string())),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("upper".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_upper".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Identifier("s".to_string())),],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("lower".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_lower".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Identifier("s".to_string())),],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("char_at".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_char_at".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("s".to_string())),
+                        node!(Expression::Integer(0)),
+                    ],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_number_parsing_and_formatting() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_to_number".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::String("1.5".to_string())),],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("nan".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("is_nan".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Identifier("x".to_string())),],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("fixed".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("format_fixed".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("x".to_string())),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("scientific".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("format_scientific".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("x".to_string())),
+                        node!(Expression::Integer(3)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("left".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_pad_left".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("fixed".to_string())),
+                        node!(Expression::Integer(8)),
+                        node!(Expression::String(" ".to_string())),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("right".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("string_pad_right".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::Identifier("fixed".to_string())),
+                        node!(Expression::Integer(8)),
+                        node!(Expression::String("0".to_string())),
+                    ],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_sqrt_funcion() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("sqrt".to_string())),
+            type_args: vec![],
+            args: vec![node!(Expression::Numeric(4.0))],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_math_functions_to_intrinsics() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("pow".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::FuncCall(FuncCall {
+                            calee: boxed_node!(Expression::Identifier("sin".to_string())),
+                            type_args: vec![],
+                            args: vec![node!(Expression::Identifier("PI".to_string()))],
+                        })),
+                        node!(Expression::Integer(2)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("y".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("fmod".to_string())),
+                    type_args: vec![],
+                    args: vec![
+                        node!(Expression::FuncCall(FuncCall {
+                            calee: boxed_node!(Expression::Identifier("max".to_string())),
+                            type_args: vec![],
+                            args: vec![
+                                node!(Expression::Identifier("E".to_string())),
+                                node!(Expression::Identifier("x".to_string())),
+                            ],
+                        })),
+                        node!(Expression::Numeric(2.0)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    return_type: Type::Numeric,
+                    params: vec![Param {
+                        typ: Type::Numeric,
+                        name: "z".to_string(),
+                    }],
+                    body: vec![node!(Expression::FuncCall(FuncCall {
+                        calee: boxed_node!(Expression::Identifier("floor".to_string())),
+                        type_args: vec![],
+                        args: vec![node!(Expression::Identifier("z".to_string()))],
+                    }))],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    // the intrinsics are folded, leaving max(E, sin(PI) ^ 2) = E
+    assert!(ir.contains("call double @fmod(double 0x4005BF0A8B145769, double 2.000000e+00)"));
+    assert!(ir.contains("call double @llvm.floor.f64"));
+    assert!(!ir.contains("call double @llvm.sin.f64"));
+    Ok(())
+}
+
+#[test]
+fn it_lets_variables_shadow_math_constants() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("E".to_string())),
+                right: boxed_node!(Expression::String("e".to_string())),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("print".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::Identifier("E".to_string()))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_more_then_one_arg_pass_to_print_funcion() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("print".to_string())),
+            type_args: vec![],
+            args: vec![
+                node!(Expression::String("name".to_string())),
+                node!(Expression::String("foo".to_string())),
+            ],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(compiler.compile(), Err(_)));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_non_sring_type_pass_to_print_funcions() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("print".to_string())),
+            type_args: vec![],
+            args: vec![node!(Expression::Numeric(10.0))],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(compiler.compile(), Err(_)));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_zero_args_pass_to_string_funcion() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("string".to_string())),
+            type_args: vec![],
+            args: vec![],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(compiler.compile(), Err(_)));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_bool_arg_pass_to_string_funcion() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("string".to_string())),
+            type_args: vec![],
+            args: vec![node!(Expression::Bool(true))],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(compiler.compile(), Err(_)));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_new_vec_being_passed_as_variable() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    body: vec![],
+                    return_type: Type::Void,
+                    params: vec![Param {
+                        name: "v".to_string(),
+                        typ: Type::Vector(Box::new(Type::Numeric)),
+                    }],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("vecinvar".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                    type_args: vec![],
+                    args: vec![],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::Identifier("vecinvar".to_string()))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_recursive_fun() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("f".to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Void,
+                params: vec![],
+                body: vec![node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("f".to_string())),
+                    type_args: vec![],
+                    args: vec![],
+                }))],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_while_statment() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::While(While {
+            predicate: boxed_node!(Expression::Bool(false)),
+            body: vec![],
+            label: None,
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_test_visit_func_decl() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::FuncDecl(FuncDecl {
+                params: vec![Param {
+                    typ: Type::Numeric,
+                    name: "n".to_string(),
+                }],
+                body: vec![node!(Expression::Identifier("n".to_string()))],
+                return_type: Type::Numeric,
+            })),
+            node!(Expression::FuncDecl(FuncDecl {
+                params: vec![Param {
+                    typ: Type::Vector(Box::new(Type::Numeric)),
+                    name: "n".to_string(),
+                }],
+                body: vec![node!(Expression::Identifier("n".to_string()))],
+                return_type: Type::Vector(Box::new(Type::Numeric)),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_grouping_expressions() -> Result<(), Box<dyn Error>> {
+    let grouping = expression::Grouping(boxed_node!(Expression::Binary(Binary {
+        left: boxed_node!(Expression::Numeric(10.0)),
+        operator: Operator::NotEqual,
+        right: boxed_node!(Expression::Numeric(2.0)),
+    })));
+
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("x".to_string())),
+            right: boxed_node!(Expression::Grouping(grouping)),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_coditional() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Conditional(Conditional {
+            predicate: boxed_node!(Expression::Binary(Binary {
+                left: boxed_node!(Expression::Numeric(10.0)),
+                operator: Operator::NotEqual,
+                right: boxed_node!(Expression::Numeric(2.0)),
+            })),
+            body: vec![node!(Expression::Numeric(10.0))],
+            else_body: vec![node!(Expression::Numeric(20.0))],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_conditional_as_value() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("s".to_string())),
+            right: boxed_node!(Expression::Conditional(Conditional {
+                predicate: boxed_node!(Expression::Bool(true)),
+                body: vec![node!(Expression::String("yes".to_string()))],
+                else_body: vec![node!(Expression::Conditional(Conditional {
+                    predicate: boxed_node!(Expression::Bool(false)),
+                    body: vec![node!(Expression::String("maybe".to_string()))],
+                    else_body: vec![node!(Expression::String("no".to_string()))],
+                }))],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_conditional_branches_disagree() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("x".to_string())),
+            right: boxed_node!(Expression::Conditional(Conditional {
+                predicate: boxed_node!(Expression::Bool(true)),
+                body: vec![node!(Expression::Integer(1))],
+                else_body: vec![node!(Expression::Bool(false))],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Numeric,
+            actual: Type::Bool,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_void_assignment_err_when_conditional_has_no_else() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("x".to_string())),
+            right: boxed_node!(Expression::Conditional(Conditional {
+                predicate: boxed_node!(Expression::Bool(true)),
+                body: vec![node!(Expression::Integer(1))],
+                else_body: vec![],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::VoidAssignment)
+    ));
+    Ok(())
+}
+
+fn match_arm(pattern: Pattern, guard: Option<Box<Node>>, body: Expression) -> MatchArm {
+    MatchArm {
+        pattern,
+        guard,
+        body: vec![node!(body)],
+    }
+}
+
+#[test]
+fn it_compiles_match_on_int_to_switch() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("x".to_string())),
+            right: boxed_node!(Expression::Match(Match {
+                subject: boxed_node!(int_call(2.0)),
+                arms: vec![
+                    match_arm(
+                        Pattern::Literal(boxed_node!(Expression::Integer(1))),
+                        None,
+                        Expression::String("one".to_string()),
+                    ),
+                    match_arm(
+                        Pattern::Literal(boxed_node!(Expression::Integer(2))),
+                        None,
+                        Expression::String("two".to_string()),
+                    ),
+                    match_arm(
+                        Pattern::Wildcard,
+                        None,
+                        Expression::String("many".to_string()),
+                    ),
+                ],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    assert!(compiler.ir_string().contains("switch i64 %"));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_match_with_ranges_and_guards() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("x".to_string())),
+            right: boxed_node!(Expression::Match(Match {
+                subject: boxed_node!(Expression::Numeric(2.5)),
+                arms: vec![
+                    match_arm(
+                        Pattern::Range {
+                            start: boxed_node!(Expression::Integer(0)),
+                            end: boxed_node!(Expression::Integer(2)),
+                        },
+                        None,
+                        Expression::Integer(1),
+                    ),
+                    match_arm(
+                        Pattern::Wildcard,
+                        Some(boxed_node!(Expression::Bool(false))),
+                        Expression::Integer(2),
+                    ),
+                    match_arm(Pattern::Wildcard, None, Expression::Integer(3)),
+                ],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    assert!(!compiler.ir_string().contains("switch"));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_for_non_exhaustive_bool_match() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Match(Match {
+            subject: boxed_node!(Expression::Bool(true)),
+            arms: vec![
+                match_arm(
+                    Pattern::Literal(boxed_node!(Expression::Bool(true))),
+                    None,
+                    Expression::Integer(1),
+                ),
+                match_arm(
+                    Pattern::Literal(boxed_node!(Expression::Bool(false))),
+                    Some(boxed_node!(Expression::Bool(true))),
+                    Expression::Integer(0),
+                ),
+            ],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::NonExhaustiveMatch { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_match_arms_disagree() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Match(Match {
+            subject: boxed_node!(Expression::String("a".to_string())),
+            arms: vec![
+                match_arm(
+                    Pattern::Literal(boxed_node!(Expression::String("a".to_string()))),
+                    None,
+                    int_call(1.0),
+                ),
+                match_arm(Pattern::Wildcard, None, Expression::Numeric(0.5)),
+            ],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Int,
+            actual: Type::Numeric,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+macro_rules! compile_operator {
+    ($left_operator:expr, $operator:expr, $rigth_operator:expr) => {{
+        let program = Program {
+            body: vec![node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("b".to_string())),
+                right: boxed_node!(Expression::Binary(Binary {
+                    left: $left_operator,
+                    operator: $operator,
+                    right: $rigth_operator,
+                })),
+            }))],
+        };
+
+        let mut compiler = Compiler::new(program)?;
+        compiler.compile()
+    }};
+}
+
+#[test]
+fn it_compiles_operators() -> Result<(), Box<dyn Error>> {
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Plus,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Minus,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Asterisk,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::LessOrEqual,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Less,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Greater,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::GreaterOrEqual,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Equal,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Slash,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Minus,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::NotEqual,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn it_compiles_int_operators() -> Result<(), Box<dyn Error>> {
+    for operator in [
+        Operator::Plus,
+        Operator::Minus,
+        Operator::Asterisk,
+        Operator::Slash,
+        Operator::Mod,
+        Operator::Less,
+        Operator::LessOrEqual,
+        Operator::Greater,
+        Operator::GreaterOrEqual,
+        Operator::Equal,
+        Operator::NotEqual,
+        Operator::BitAnd,
+        Operator::BitOr,
+        Operator::BitXor,
+        Operator::ShiftLeft,
+        Operator::ShiftRight,
+    ] {
+        compile_operator!(
+            boxed_node!(Expression::Integer(10)),
+            operator,
+            boxed_node!(Expression::Integer(2))
+        )
+        .unwrap();
+    }
+
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_for_bitwise_operators_on_numeric() -> Result<(), Box<dyn Error>> {
+    for operator in [
+        Operator::BitAnd,
+        Operator::BitOr,
+        Operator::BitXor,
+        Operator::ShiftLeft,
+        Operator::ShiftRight,
+    ] {
+        assert!(matches!(
+            compile_operator!(
+                boxed_node!(Expression::Integer(10)),
+                operator,
+                boxed_node!(Expression::Numeric(2.0))
+            ),
+            Err(CompilerError::TypeError {
+                expected: Type::Int,
+                actual: Type::Numeric,
+                ..
+            })
+        ));
+    }
+
+    let program = Program {
+        body: vec![node!(Expression::Unary(Unary {
+            operator: Operator::BitNot,
+            right: boxed_node!(Expression::Numeric(2.0)),
+        }))],
+    };
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Int,
+            actual: Type::Numeric,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn it_widens_int_in_numeric_operators() -> Result<(), Box<dyn Error>> {
+    compile_operator!(
+        boxed_node!(Expression::Integer(10)),
+        Operator::Plus,
+        boxed_node!(Expression::Numeric(2.0))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::Less,
+        boxed_node!(Expression::Integer(2))
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn it_compiles_logical_operators() -> Result<(), Box<dyn Error>> {
+    compile_operator!(
+        boxed_node!(Expression::Bool(true)),
+        Operator::And,
+        boxed_node!(Expression::Bool(false))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Bool(true)),
+        Operator::Or,
+        boxed_node!(Expression::Bool(false))
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_and_numeric_and_bool() -> Result<(), Box<dyn Error>> {
+    assert!(compile_operator!(
+        boxed_node!(Expression::Numeric(10.0)),
+        Operator::And,
+        boxed_node!(Expression::Bool(true))
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_or_bool_and_string() -> Result<(), Box<dyn Error>> {
+    assert!(compile_operator!(
+        boxed_node!(Expression::Bool(true)),
+        Operator::Or,
+        boxed_node!(Expression::String("test".to_string()))
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn it_compiles_bool_equality_operators() -> Result<(), Box<dyn Error>> {
+    compile_operator!(
+        boxed_node!(Expression::Bool(true)),
+        Operator::Equal,
+        boxed_node!(Expression::Bool(false))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::Bool(true)),
+        Operator::NotEqual,
+        boxed_node!(Expression::Bool(false))
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn it_compiles_string_equality_operators() -> Result<(), Box<dyn Error>> {
+    compile_operator!(
+        boxed_node!(Expression::String("foo".to_string())),
+        Operator::Equal,
+        boxed_node!(Expression::String("bar".to_string()))
+    )
+    .unwrap();
+
+    compile_operator!(
+        boxed_node!(Expression::String("foo".to_string())),
+        Operator::NotEqual,
+        boxed_node!(Expression::String("bar".to_string()))
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn it_does_not_expose_string_equal_to_programs() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("string_equal".to_string())),
+            type_args: vec![],
+            args: vec![
+                node!(Expression::String("a".to_string())),
+                node!(Expression::String("a".to_string())),
+            ],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::UndefinedIdentifier(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_equal_bool_and_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Bool(true)),
+            Operator::Equal,
+            boxed_node!(Expression::Numeric(1.0))
+        ),
+        Err(CompilerError::TypeError {
+            expected: Type::Bool,
+            actual: Type::Numeric,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_adding_bools() -> Result<(), Box<dyn Error>> {
+    assert!(compile_operator!(
+        boxed_node!(Expression::Bool(true)),
+        Operator::Plus,
+        boxed_node!(Expression::Bool(false))
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_adding_numeric_to_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::Plus,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_plus_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::Plus,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_substract_string_from_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::Minus,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_substract_numeric_from_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::Minus,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_multiple_numeric_by_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::Asterisk,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_multiple_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::Asterisk,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_less_or_equal_numeric_and_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::LessOrEqual,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_less_or_equal_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::LessOrEqual,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_less_numeric_and_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::Less,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_less_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::Less,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_greater_numeric_and_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::Greater,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_greater_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::Greater,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_greater_or_equal_numeric_and_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::GreaterOrEqual,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_greater_or_equal_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::GreaterOrEqual,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_equal_numeric_and_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::Equal,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_equal_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::Equal,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_slash_numeric_and_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::Slash,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_slash_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::Slash,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_not_equal_numeric_and_string() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::Numeric(10.0)),
+            Operator::NotEqual,
+            boxed_node!(Expression::String("test".to_string()))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_not_equal_string_to_numeric() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(
+        compile_operator!(
+            boxed_node!(Expression::String("test".to_string())),
+            Operator::NotEqual,
+            boxed_node!(Expression::Numeric(10.0))
+        ),
+        Err(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_unary_operator() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Unary(Unary {
+            operator: Operator::Minus,
+            right: boxed_node!(Expression::Numeric(2.0)),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_pass_string_to_unary() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Unary(Unary {
+            operator: Operator::Minus,
+            right: boxed_node!(Expression::String("foo".to_string())),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+
+    assert!(matches!(compiler.compile(), Err(_)));
+
+    Ok(())
+}
+
+#[test]
+fn it_compiles_unary_not_operator() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Unary(Unary {
+            operator: Operator::Not,
+            right: boxed_node!(Expression::Bool(true)),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_pass_numeric_to_unary_not() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Unary(Unary {
+            operator: Operator::Not,
+            right: boxed_node!(Expression::Numeric(2.0)),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(compiler.compile().is_err());
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_when_wrong_unary_operator() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Unary(Unary {
+            operator: Operator::Plus,
+            right: boxed_node!(Expression::Numeric(2.0)),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(compiler.compile(), Err(_)));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_break_in_while() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::While(While {
+            predicate: boxed_node!(Expression::Bool(true)),
+            body: vec![node!(Expression::Break(None))],
+            label: None,
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_for_loop_over_range() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::For(For {
+            variable: "i".to_string(),
+            iterable: Iterable::Range {
+                start: boxed_node!(Expression::Integer(0)),
+                end: boxed_node!(Expression::Integer(10)),
+            },
+            body: vec![
+                node!(Expression::Assignment(Assignment {
+                    left: boxed_node!(Expression::Identifier("x".to_string())),
+                    right: boxed_node!(Expression::Identifier("i".to_string())),
+                })),
+                node!(Expression::Break(None)),
+            ],
+            label: None,
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_for_loop_over_vec() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::For(For {
+            variable: "x".to_string(),
+            iterable: Iterable::Collection(boxed_node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                type_args: vec![],
+                args: vec![],
+            }))),
+            body: vec![node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("sqrt".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::Identifier("x".to_string()))],
+            }))],
+            label: None,
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_type_err_when_iterating_over_non_vec() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::For(For {
+            variable: "x".to_string(),
+            iterable: Iterable::Collection(boxed_node!(Expression::Bool(true))),
+            body: vec![],
+            label: None,
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Vector(_),
+            actual: Type::Bool,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_scopes_for_loop_variable_to_body() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::For(For {
+                variable: "i".to_string(),
+                iterable: Iterable::Range {
+                    start: boxed_node!(Expression::Integer(0)),
+                    end: boxed_node!(Expression::Integer(10)),
+                },
+                body: vec![],
+                label: None,
+            })),
+            node!(Expression::Identifier("i".to_string())),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::UndefinedIdentifier(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_continue_in_while() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::While(While {
+            predicate: boxed_node!(Expression::Bool(true)),
+            body: vec![
+                node!(Expression::Conditional(Conditional {
+                    predicate: boxed_node!(Expression::Bool(false)),
+                    body: vec![node!(Expression::Continue(None))],
+                    else_body: vec![node!(Expression::Break(None))],
+                })),
+                node!(Expression::Continue(None)),
+            ],
+            label: None,
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_continue_in_for_loop() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::For(For {
+            variable: "i".to_string(),
+            iterable: Iterable::Range {
+                start: boxed_node!(Expression::Integer(0)),
+                end: boxed_node!(Expression::Integer(10)),
+            },
+            body: vec![
+                node!(Expression::Assignment(Assignment {
+                    left: boxed_node!(Expression::Identifier("s".to_string())),
+                    right: boxed_node!(Expression::String("skipped".to_string())),
+                })),
+                node!(Expression::Conditional(Conditional {
+                    predicate: boxed_node!(Expression::Bool(true)),
+                    body: vec![node!(Expression::Continue(None))],
+                    else_body: vec![],
+                })),
+            ],
+            label: None,
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_labeled_break_from_inner_loop() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::While(While {
+            predicate: boxed_node!(Expression::Bool(true)),
+            body: vec![node!(Expression::For(For {
+                variable: "i".to_string(),
+                iterable: Iterable::Range {
+                    start: boxed_node!(Expression::Integer(0)),
+                    end: boxed_node!(Expression::Integer(10)),
+                },
+                body: vec![
+                    node!(Expression::Conditional(Conditional {
+                        predicate: boxed_node!(Expression::Bool(false)),
+                        body: vec![node!(Expression::Continue(Some("outer".to_string())))],
+                        else_body: vec![],
+                    })),
+                    node!(Expression::Break(Some("outer".to_string()))),
+                ],
+                label: None,
+            }))],
+            label: Some("outer".to_string()),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_for_undefined_label() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::While(While {
+            predicate: boxed_node!(Expression::Bool(true)),
+            body: vec![node!(Expression::Break(Some("outer".to_string())))],
+            label: Some("inner".to_string()),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::UndefinedLabel { label, .. }) if label == "outer"
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_returns_err_for_break_outside_loop() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Break(None))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::OutsideLoop { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_compiles_ffi_calls() -> Result<(), Box<dyn Error>> {
+    let c_string = node!(Expression::FuncCall(FuncCall {
+        calee: boxed_node!(Expression::Identifier("c_string_from_string".to_string())),
+        type_args: vec![],
+        args: vec![node!(Expression::String("foo".to_string()))],
+    }));
+
+    let program = Program {
+        body: vec![
+            node!(Expression::Load(String::from("./tests/rockffitestlib.so"))),
+            node!(Expression::Assignment(expression::Assignment {
+                left: boxed_node!(Expression::Identifier(String::from("sum"))),
+                right: boxed_node!(Expression::Extern(expression::Extern {
+                    types: [Type::Numeric, Type::Numeric].to_vec(),
+                    return_type: Type::Numeric,
+                    name: String::from("rockffitest"),
+                })),
+            })),
+            node!(Expression::Assignment(expression::Assignment {
+                left: boxed_node!(Expression::Identifier(String::from("getptr"))),
+                right: boxed_node!(Expression::Extern(expression::Extern {
+                    types: [].to_vec(),
+                    return_type: Type::Ptr,
+                    name: String::from("getpr"),
+                })),
+            })),
+            node!(Expression::Assignment(expression::Assignment {
+                left: boxed_node!(Expression::Identifier(String::from("passptr"))),
+                right: boxed_node!(Expression::Extern(expression::Extern {
+                    types: [Type::Ptr].to_vec(),
+                    return_type: Type::Void,
+                    name: String::from("passptr"),
+                })),
+            })),
+            node!(Expression::Assignment(expression::Assignment {
+                left: boxed_node!(Expression::Identifier(String::from("passstr"))),
+                right: boxed_node!(Expression::Extern(expression::Extern {
+                    types: [Type::CString].to_vec(),
+                    return_type: Type::Void,
+                    name: String::from("passstr"),
+                })),
+            })),
+            node!(Expression::FuncCall(expression::FuncCall {
+                calee: boxed_node!(Expression::Identifier(String::from("passptr"))),
+                type_args: vec![],
+                args: [node!(Expression::FuncCall(expression::FuncCall {
+                    calee: boxed_node!(Expression::Identifier(String::from("getptr"))),
+                    type_args: vec![],
+                    args: [].to_vec(),
+                }))]
+                .to_vec(),
+            })),
+            node!(Expression::FuncCall(expression::FuncCall {
+                calee: boxed_node!(Expression::Identifier(String::from("sum"))),
+                type_args: vec![],
+                args: [
+                    node!(Expression::Numeric(2.0)),
+                    node!(Expression::Numeric(3.0))
+                ]
+                .to_vec(),
+            })),
+            node!(Expression::FuncCall(expression::FuncCall {
+                calee: boxed_node!(Expression::Identifier(String::from("passstr"))),
+                type_args: vec![],
+                args: [c_string].to_vec(),
+            })),
+        ],
+    };
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::Asterisk,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::LessOrEqual,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+    assert_eq_ir!(
+        &compiler.ir_string(),
+        r#"target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::Less,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+        @0 = private unnamed_addr constant [4 x i8] c"foo\00", align 1
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::Greater,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+        declare void* @string(double)
+
+        declare i64 @int(double)
+
+        declare double @number(i64)
+
+        declare void @print(void*)
+
+        declare void @release_string_reference(void*)
+
+        declare void @inc_string_reference(void*)
+
+        declare void @inc_vec_reference(void*)
+
+        declare void @release_vec_reference(void*)
+
+        declare i8* @c_string_from_string(void*)
+
+        declare void* @string_from_c_string(i8*)
+
+        declare i1 @string_equal(void*, void*)
+
+        declare void* @string_get(void*, double)
+
+        declare void* @string_set(void*, double, void*, i64, i64)
+
+        declare void* @string_from_int(i64)
+
+        declare void* @string_from_bool(i1)
+
+        declare void* @string_new()
+
+        declare void @string_append(void*, void*)
+
+        declare void* @string_concat(void*, void*)
+
+        declare i64 @string_compare(void*, void*)
+
+        declare double @string_len(void*)
+
+        declare void* @string_substring(void*, double, double)
+
+        declare double @string_find(void*, void*)
+
+        declare void* @string_split(void*, void*)
+
+        declare void* @string_join(void*, void*)
+
+        declare void* @string_trim(void*)
+
+        declare void* @string_replace(void*, void*, void*)
+
+        declare void* @string_upper(void*)
+
+        declare void* @string_lower(void*)
+
+        declare void* @string_char_at(void*, double)
+
+        declare double @string_to_number(void*)
+
+        declare i1 @is_nan(double)
+
+        declare void* @format_fixed(double, double)
+
+        declare void* @format_scientific(double, double)
+
+        declare void* @string_pad_left(void*, double, void*)
+
+        declare void* @string_pad_right(void*, double, void*)
+
+        declare void* @vec_new()
+
+        declare double @vec_len(void*)
+
+        declare void @vec_set_numeric(void*, double, double)
+
+        declare void @vec_set_int(void*, double, i64)
+
+        declare void @vec_set_bool(void*, double, i1)
+
+        declare void @vec_set_string(void*, double, void*)
+
+        declare void @vec_set_vec(void*, double, void*)
+
+        declare void @vec_set_env(void*, double, void*)
+
+        declare void @vec_set_struct(void*, double, void*)
+
+        declare void @vec_set_ptr(void*, double, void*)
+
+        declare double @vec_get_numeric(void*, double)
+
+        declare i64 @vec_get_int(void*, double)
+
+        declare i1 @vec_get_bool(void*, double)
+
+        declare void* @vec_get_ptr(void*, double)
+
+        declare void @vec_check_get(void*, double, i64, i64)
+
+        declare void @vec_check_set(void*, double, i64, i64)
+
+        declare void @string_check_get(void*, double, i64, i64)
+
+        declare void @string_check_set(void*, double, i64, i64)
+
+        declare void @vec_check_slice(double, double, i64, i64)
+
+        declare void @int_check_div(i64, i64, i64, i64)
+
+        declare void @vec_insert_numeric(void*, double, double)
+
+        declare void @vec_insert_int(void*, double, i64)
+
+        declare void @vec_insert_bool(void*, double, i1)
+
+        declare void @vec_insert_string(void*, double, void*)
+
+        declare void @vec_insert_vec(void*, double, void*)
+
+        declare void @vec_insert_env(void*, double, void*)
+
+        declare void @vec_insert_struct(void*, double, void*)
+
+        declare void @vec_insert_ptr(void*, double, void*)
+
+        declare double @vec_remove_numeric(void*, double)
+
+        declare i64 @vec_remove_int(void*, double)
+
+        declare i1 @vec_remove_bool(void*, double)
+
+        declare void* @vec_remove_ptr(void*, double)
+
+        declare void* @vec_slice(void*, double, double)
+
+        declare void* @vec_concat(void*, void*)
+
+        declare void @vec_sort(void*)
+
+        declare void @vec_reverse(void*)
+
+        declare double @vec_index_of_numeric(void*, double)
+
+        declare double @vec_index_of_int(void*, i64)
+
+        declare double @vec_index_of_bool(void*, i1)
+
+        declare double @vec_index_of_string(void*, void*)
+
+        declare void* @env_new(void*)
+
+        declare void @env_set_numeric(void*, double, double)
+
+        declare void @env_set_int(void*, double, i64)
+
+        declare void @env_set_bool(void*, double, i1)
+
+        declare void @env_set_string(void*, double, void*)
+
+        declare void @env_set_vec(void*, double, void*)
+
+        declare void @env_set_env(void*, double, void*)
+
+        declare void @env_set_struct(void*, double, void*)
+
+        declare void @env_set_ptr(void*, double, void*)
+
+        declare double @env_get_numeric(void*, double)
+
+        declare i64 @env_get_int(void*, double)
+
+        declare i1 @env_get_bool(void*, double)
+
+        declare void* @env_get_ptr(void*, double)
+
+        declare void* @env_function(void*)
+
+        declare void @inc_env_reference(void*)
+
+        declare void @release_env_reference(void*)
+
+        declare void* @struct_new()
+
+        declare void @struct_set_numeric(void*, double, double)
+
+        declare void @struct_set_int(void*, double, i64)
+
+        declare void @struct_set_bool(void*, double, i1)
+
+        declare void @struct_set_string(void*, double, void*)
+
+        declare void @struct_set_vec(void*, double, void*)
+
+        declare void @struct_set_env(void*, double, void*)
+
+        declare void @struct_set_struct(void*, double, void*)
+
+        declare void @struct_set_ptr(void*, double, void*)
+
+        declare double @struct_get_numeric(void*, double)
+
+        declare i64 @struct_get_int(void*, double)
+
+        declare i1 @struct_get_bool(void*, double)
+
+        declare void* @struct_get_ptr(void*, double)
+
+        declare void @inc_struct_reference(void*)
+
+        declare void @release_struct_reference(void*)
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.sqrt.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.sin.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.cos.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.exp.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.log.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.pow.f64(double, double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.floor.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.ceil.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.round.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.fabs.f64(double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.minnum.f64(double, double) #0
+
+        ; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+        declare double @llvm.maxnum.f64(double, double) #0
+
+        declare double @tan(double)
+
+        declare double @atan2(double, double)
+
+        declare double @fmod(double, double)
+
+        define void @main() {
+          %1 = call void* @getpr()
+          call void @passptr(void* %1)
+          %2 = call double @rockffitest(double 2.000000e+00, double 3.000000e+00)
+          %3 = call void* @string_from_c_string(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @0, i64 0, i64 0))
+          %4 = call i8* @c_string_from_string(void* %3)
+          call void @passstr(i8* %4)
+          call void @release_string_reference(void* %3)
+          ret void
+        }
+
+        declare double @rockffitest(double, double)
+
+        declare void* @getpr()
+
+        declare void @passptr(void*)
+
+        declare void @passstr(i8*)
+
+        attributes #0 = { nofree nosync nounwind readnone speculatable willreturn }
+        "#
+    );
+    Ok(())
+}
+
+#[test]
+fn it_compile_break_in_while_and_if() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::While(While {
+            predicate: boxed_node!(Expression::Bool(true)),
+            body: vec![node!(Expression::Conditional(Conditional {
+                predicate: boxed_node!(Expression::Bool(true)),
+                body: vec![
+                    node!(Expression::Break(None)),
+                    node!(Expression::Numeric(1.0))
+                ],
+                else_body: vec![],
+            }))],
+            label: None,
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::GreaterOrEqual,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+#[test]
+fn it_compiles_early_return_from_function() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("f".to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Numeric,
+                params: vec![Param {
+                    name: "v".to_string(),
+                    typ: Type::Vector(Box::new(Type::Numeric)),
+                }],
+                body: vec![
+                    node!(Expression::While(While {
+                        predicate: boxed_node!(Expression::Bool(true)),
+                        body: vec![node!(Expression::Conditional(Conditional {
+                            predicate: boxed_node!(Expression::Bool(true)),
+                            body: vec![node!(Expression::Return(Some(boxed_node!(
+                                Expression::Numeric(1.0)
+                            ))))],
+                            else_body: vec![],
+                        }))],
+                        label: None,
+                    })),
+                    node!(Expression::Numeric(2.0)),
+                ],
+            })),
+        }))],
+    };
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::Equal,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::Slash,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+#[test]
+fn it_releases_iterated_vec_when_returning_from_for_loop() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("first".to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Numeric,
+                params: vec![Param {
+                    name: "v".to_string(),
+                    typ: Type::Vector(Box::new(Type::Numeric)),
+                }],
+                body: vec![
+                    node!(Expression::For(For {
+                        variable: "x".to_string(),
+                        iterable: Iterable::Collection(boxed_node!(Expression::Identifier(
+                            "v".to_string()
+                        ))),
+                        body: vec![node!(Expression::Return(Some(boxed_node!(
+                            Expression::Identifier("x".to_string())
+                        ))))],
+                        label: None,
+                    })),
+                    node!(Expression::Numeric(0.0)),
+                ],
+            })),
+        }))],
+    };
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::Minus,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    let fun = &ir[ir.find("define double").unwrap()..];
+    let early_return = &fun[..fun.find("ret double").unwrap()];
+    // the parameter and the reference held by the loop
+    assert_eq!(
+        2,
+        early_return.matches("call void @inc_vec_reference").count()
+    );
+    assert_eq!(
+        2,
+        early_return
+            .matches("call void @release_vec_reference")
+            .count()
+    );
+    Ok(())
+}
 
-    compile_operator!(
-        boxed_node!(Expression::Numeric(10.0)),
-        Operator::NotEqual,
-        boxed_node!(Expression::Numeric(2.0))
-    )
-    .unwrap();
+#[test]
+fn it_compiles_function_returning_from_all_branches() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("f".to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Numeric,
+                params: vec![],
+                body: vec![node!(Expression::Conditional(Conditional {
+                    predicate: boxed_node!(Expression::Bool(true)),
+                    body: vec![node!(Expression::Return(Some(boxed_node!(
+                        Expression::Numeric(1.0)
+                    ))))],
+                    else_body: vec![node!(Expression::Return(Some(boxed_node!(
+                        Expression::Numeric(2.0)
+                    ))))],
+                }))],
+            })),
+        }))],
+    };
 
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_adding_numeric_to_string() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_return_value_does_not_match_return_type() -> Result<(), Box<dyn Error>>
+{
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("f".to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Numeric,
+                params: vec![],
+                body: vec![node!(Expression::Return(Some(boxed_node!(
+                    Expression::String("foo".to_string())
+                ))))],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
     assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::Plus,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Numeric,
+            actual: Type::String,
+            ..
+        })
     ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_plus_string_to_numeric() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::Plus,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
-    ));
+fn it_compiles_return_from_main() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Return(None)),
+            node!(Expression::Numeric(1.0)),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_substract_string_from_numeric() -> Result<(), Box<dyn Error>> {
+fn it_returns_arity_err_when_calling_function_with_wrong_arg_count() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("vec_get".to_string())),
+            type_args: vec![],
+            args: vec![node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("vec_new".to_string())),
+                type_args: vec![],
+                args: vec![],
+            }))],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
     assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::Minus,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
+        compiler.compile(),
+        Err(CompilerError::WrongArgumentCount {
+            expected: 2,
+            actual: 1,
+            ..
+        })
     ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_substract_numeric_from_string() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_arg_does_not_match_param_type() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    body: vec![],
+                    return_type: Type::Void,
+                    params: vec![Param {
+                        name: "v".to_string(),
+                        typ: Type::Vector(Box::new(Type::Numeric)),
+                    }],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::String("foo".to_string()))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
     assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::Minus,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Vector(_),
+            actual: Type::String,
+            ..
+        })
     ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_multiple_numeric_by_string() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_last_expression_does_not_match_return_type(
+) -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("f".to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Vector(Box::new(Type::Numeric)),
+                params: vec![],
+                body: vec![node!(Expression::Numeric(1.0))],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
     assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::Asterisk,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Vector(_),
+            actual: Type::Numeric,
+            ..
+        })
     ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_multiple_string_to_numeric() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::Asterisk,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
-    ));
+fn it_compiles_function_returning_bool() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("f".to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Bool,
+                params: vec![],
+                body: vec![node!(Expression::Bool(true))],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_less_or_equal_numeric_and_string() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::LessOrEqual,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
-    ));
+fn it_compiles_mutually_recursive_funs() -> Result<(), Box<dyn Error>> {
+    let fun = |name: &str, calee: &str| {
+        node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier(name.to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Void,
+                params: vec![],
+                body: vec![node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier(calee.to_string())),
+                    type_args: vec![],
+                    args: vec![],
+                }))],
+            })),
+        }))
+    };
+    let program = Program {
+        body: vec![fun("f", "g"), fun("g", "f")],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_less_or_equal_string_to_numeric() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::LessOrEqual,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
-    ));
+fn it_calls_fun_declared_later() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
+                args: vec![],
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    return_type: Type::Void,
+                    params: vec![],
+                    body: vec![],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
+    Ok(())
+}
+
+#[test]
+fn it_compiles_fun_capturing_outer_variable() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::Numeric(1.0)),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    return_type: Type::Numeric,
+                    params: vec![],
+                    body: vec![node!(Expression::Identifier("x".to_string()))],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
+                args: vec![],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_less_numeric_and_string() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::Less,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
-    ));
+fn it_compiles_fun_local_shadowing_outer_variable() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::String("outer".to_string())),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    return_type: Type::Numeric,
+                    params: vec![],
+                    body: vec![
+                        node!(Expression::Assignment(Assignment {
+                            left: boxed_node!(Expression::Identifier("x".to_string())),
+                            right: boxed_node!(Expression::Numeric(1.0)),
+                        })),
+                        node!(Expression::Identifier("x".to_string())),
+                    ],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
+                args: vec![],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    assert!(!compiler.ir_string().contains("call void* @env_new("));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_less_string_to_numeric() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::Less,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
-    ));
+fn it_compiles_nested_closure() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::Assignment(Assignment {
+            left: boxed_node!(Expression::Identifier("f".to_string())),
+            right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                return_type: Type::Numeric,
+                params: vec![Param {
+                    typ: Type::Numeric,
+                    name: "n".to_string(),
+                }],
+                body: vec![
+                    node!(Expression::Assignment(Assignment {
+                        left: boxed_node!(Expression::Identifier("g".to_string())),
+                        right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                            return_type: Type::Numeric,
+                            params: vec![],
+                            body: vec![node!(Expression::Identifier("n".to_string()))],
+                        })),
+                    })),
+                    node!(Expression::FuncCall(FuncCall {
+                        calee: boxed_node!(Expression::Identifier("g".to_string())),
+                        type_args: vec![],
+                        args: vec![],
+                    })),
+                ],
+            })),
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_greater_numeric_and_string() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::Greater,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
-    ));
+fn it_compiles_fun_passed_as_arg_and_returned() -> Result<(), Box<dyn Error>> {
+    let fun_type = Type::Function {
+        params: vec![Type::Numeric],
+        return_type: Box::new(Type::Numeric),
+    };
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("id".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    return_type: fun_type.clone(),
+                    params: vec![Param {
+                        typ: fun_type.clone(),
+                        name: "f".to_string(),
+                    }],
+                    body: vec![node!(Expression::Identifier("f".to_string()))],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("id".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Identifier("sqrt".to_string()))],
+                })),
+                type_args: vec![],
+                args: vec![node!(Expression::Numeric(4.0))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_greater_string_to_numeric() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_passing_fun_with_wrong_signature() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("apply".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    return_type: Type::Void,
+                    params: vec![Param {
+                        typ: Type::Function {
+                            params: vec![Type::Bool],
+                            return_type: Box::new(Type::Void),
+                        },
+                        name: "f".to_string(),
+                    }],
+                    body: vec![],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("apply".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::Identifier("sqrt".to_string()))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
     assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::Greater,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Function { .. },
+            actual: Type::Function { .. },
+            ..
+        })
     ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_greater_or_equal_numeric_and_string() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_calling_non_function() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::Numeric(1.0)),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("x".to_string())),
+                type_args: vec![],
+                args: vec![],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
     assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::GreaterOrEqual,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            actual: Type::Numeric,
+            ..
+        })
     ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_greater_or_equal_string_to_numeric() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::GreaterOrEqual,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
-    ));
+fn it_widens_int_args_and_return_values() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("f".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    return_type: Type::Numeric,
+                    params: vec![Param {
+                        typ: Type::Numeric,
+                        name: "n".to_string(),
+                    }],
+                    body: vec![node!(Expression::Integer(1))],
+                })),
+            })),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("f".to_string())),
+                type_args: vec![],
+                args: vec![node!(Expression::Integer(2))],
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_equal_numeric_and_string() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::Equal,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
-    ));
+fn it_converts_between_int_and_numeric() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("i".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("int".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Numeric(2.5))],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("n".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("number".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Identifier("i".to_string()))],
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.compile().unwrap();
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_equal_string_to_numeric() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::Equal,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
-    ));
+fn it_compiles_integer_literals_as_numbers() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::Binary(Binary {
+                    left: boxed_node!(Expression::Integer(7)),
+                    operator: Operator::Slash,
+                    right: boxed_node!(Expression::Integer(2)),
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("sum".to_string())),
+                right: boxed_node!(Expression::Integer(0)),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("sum".to_string())),
+                right: boxed_node!(Expression::Binary(Binary {
+                    left: boxed_node!(Expression::Identifier("sum".to_string())),
+                    operator: Operator::Plus,
+                    right: boxed_node!(Expression::Numeric(0.5)),
+                })),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(ir.contains("store double 3.500000e+00"));
+    assert!(!ir.contains("sdiv"));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_slash_numeric_and_string() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::Slash,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
-    ));
+fn it_compiles_integer_literals_as_ints_where_int_is_expected() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("half".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
+                    body: vec![node!(Expression::Binary(Binary {
+                        left: boxed_node!(Expression::Identifier("n".to_string())),
+                        operator: Operator::Slash,
+                        right: boxed_node!(Expression::Integer(2)),
+                    }))],
+                    return_type: Type::Int,
+                    params: vec![Param {
+                        name: "n".to_string(),
+                        typ: Type::Int,
+                    }],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("i".to_string())),
+                right: boxed_node!(Expression::FuncCall(FuncCall {
+                    calee: boxed_node!(Expression::Identifier("half".to_string())),
+                    type_args: vec![],
+                    args: vec![node!(Expression::Integer(9))],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("i".to_string())),
+                right: boxed_node!(Expression::Integer(1)),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(ir.contains("sdiv i64 %"));
+    assert!(ir.contains("i64 9)"));
+    assert!(ir.contains("store i64 1"));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_slash_string_to_numeric() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::Slash,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
+fn it_returns_type_err_when_assigning_numeric_to_int_variable() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("i".to_string())),
+                right: boxed_node!(int_call(1.0)),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("i".to_string())),
+                right: boxed_node!(Expression::Numeric(0.5)),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Int,
+            actual: Type::Numeric,
+            ..
+        })
     ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_not_equal_numeric_and_string() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_when_passing_numeric_as_int() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![node!(Expression::FuncCall(FuncCall {
+            calee: boxed_node!(Expression::Identifier("number".to_string())),
+            type_args: vec![],
+            args: vec![node!(Expression::Numeric(1.5))],
+        }))],
+    };
+
+    let mut compiler = Compiler::new(program)?;
     assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::Numeric(10.0)),
-            Operator::NotEqual,
-            boxed_node!(Expression::String("test".to_string()))
-        ),
-        Err(_)
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Int,
+            actual: Type::Numeric,
+            ..
+        })
     ));
     Ok(())
 }
 
+fn complex_decl() -> Node {
+    node!(Expression::StructDecl(StructDecl {
+        name: "Complex".to_string(),
+        fields: vec![
+            Param {
+                name: "re".to_string(),
+                typ: Type::Numeric,
+            },
+            Param {
+                name: "im".to_string(),
+                typ: Type::Numeric,
+            },
+        ],
+    }))
+}
+
+fn field_value(name: &str, value: Expression) -> FieldValue {
+    FieldValue {
+        name: name.to_string(),
+        value: node!(value),
+    }
+}
+
+fn field_access(object: &str, field: &str) -> Expression {
+    Expression::FieldAccess(FieldAccess {
+        object: boxed_node!(Expression::Identifier(object.to_string())),
+        field: field.to_string(),
+    })
+}
+
 #[test]
-fn it_returns_err_when_not_equal_string_to_numeric() -> Result<(), Box<dyn Error>> {
-    assert!(matches!(
-        compile_operator!(
-            boxed_node!(Expression::String("test".to_string())),
-            Operator::NotEqual,
-            boxed_node!(Expression::Numeric(10.0))
-        ),
-        Err(_)
+fn it_compiles_struct_literal_and_field_access() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            complex_decl(),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("c".to_string())),
+                right: boxed_node!(Expression::StructLiteral(StructLiteral {
+                    name: "Complex".to_string(),
+                    fields: vec![
+                        field_value("im", Expression::Integer(2)),
+                        field_value("re", Expression::Numeric(1.5)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(field_access("c", "im")),
+            })),
+        ],
+    };
+
+    let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
+    compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(ir.contains(
+        "call void @struct_set_numeric(void* %1, double 0.000000e+00, double 1.500000e+00)"
     ));
+    assert!(ir.contains(
+        "call void @struct_set_numeric(void* %1, double 1.000000e+00, double 2.000000e+00)"
+    ));
+    assert!(ir.contains("call double @struct_get_numeric(void* %"));
+    assert!(ir.contains("call void @release_struct_reference("));
     Ok(())
 }
 
 #[test]
-fn it_compiles_unary_operator() -> Result<(), Box<dyn Error>> {
+fn it_compiles_field_assignment() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::Unary(Unary {
-            operator: Operator::Minus,
-            right: boxed_node!(Expression::Numeric(2.0)),
-        }))],
+        body: vec![
+            complex_decl(),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("c".to_string())),
+                right: boxed_node!(Expression::StructLiteral(StructLiteral {
+                    name: "Complex".to_string(),
+                    fields: vec![
+                        field_value("re", Expression::Numeric(1.0)),
+                        field_value("im", Expression::Numeric(2.0)),
+                    ],
+                })),
+            })),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(field_access("c", "re")),
+                right: boxed_node!(Expression::Integer(3)),
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
     compiler.compile().unwrap();
+    assert!(compiler
+        .ir_string()
+        .contains("double 0.000000e+00, double 3.000000e+00)"));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_pass_string_to_unary() -> Result<(), Box<dyn Error>> {
-    let program = Program {
-        body: vec![node!(Expression::Unary(Unary {
-            operator: Operator::Minus,
-            right: boxed_node!(Expression::String("foo".to_string())),
-        }))],
+fn it_returns_err_for_missing_and_undefined_fields() -> Result<(), Box<dyn Error>> {
+    let literal = |fields| {
+        node!(Expression::StructLiteral(StructLiteral {
+            name: "Complex".to_string(),
+            fields,
+        }))
     };
 
+    let program = Program {
+        body: vec![
+            complex_decl(),
+            literal(vec![field_value("re", Expression::Numeric(1.0))]),
+        ],
+    };
     let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::MissingField { field, .. }) if field == "im"
+    ));
 
-    assert!(matches!(compiler.compile(), Err(_)));
-
+    let program = Program {
+        body: vec![
+            complex_decl(),
+            literal(vec![
+                field_value("re", Expression::Numeric(1.0)),
+                field_value("im", Expression::Numeric(1.0)),
+                field_value("abs", Expression::Numeric(1.0)),
+            ]),
+        ],
+    };
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::UndefinedField { field, .. }) if field == "abs"
+    ));
     Ok(())
 }
 
 #[test]
-fn it_returns_err_when_wrong_unary_operator() -> Result<(), Box<dyn Error>> {
+fn it_returns_type_err_for_mismatched_field() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::Unary(Unary {
-            operator: Operator::Plus,
-            right: boxed_node!(Expression::Numeric(2.0)),
-        }))],
+        body: vec![
+            complex_decl(),
+            node!(Expression::StructLiteral(StructLiteral {
+                name: "Complex".to_string(),
+                fields: vec![
+                    field_value("re", Expression::Numeric(1.0)),
+                    field_value("im", Expression::Bool(true)),
+                ],
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
-    assert!(matches!(compiler.compile(), Err(_)));
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::TypeError {
+            expected: Type::Numeric,
+            actual: Type::Bool,
+            ..
+        })
+    ));
     Ok(())
 }
 
+fn shape_decl() -> Node {
+    node!(Expression::EnumDecl(EnumDecl {
+        name: "Shape".to_string(),
+        variants: vec![
+            VariantDecl {
+                name: "Circle".to_string(),
+                fields: vec![Type::Numeric],
+            },
+            VariantDecl {
+                name: "Rect".to_string(),
+                fields: vec![Type::Numeric, Type::Numeric],
+            },
+        ],
+    }))
+}
+
+fn variant_pattern(variant: &str, bindings: &[&str]) -> Pattern {
+    Pattern::Variant {
+        name: "Shape".to_string(),
+        variant: variant.to_string(),
+        bindings: bindings.iter().map(|name| name.to_string()).collect(),
+    }
+}
+
+fn circle(radius: f64) -> Box<Node> {
+    boxed_node!(Expression::EnumVariant(EnumVariant {
+        name: "Shape".to_string(),
+        variant: "Circle".to_string(),
+        args: vec![node!(Expression::Numeric(radius))],
+    }))
+}
+
 #[test]
-fn it_compiles_break_in_while() -> Result<(), Box<dyn Error>> {
+fn it_compiles_enum_match_to_switch_on_tag() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::While(While {
-            predicate: boxed_node!(Expression::Bool(true)),
-            body: vec![node!(Expression::Break)],
-        }))],
+        body: vec![
+            shape_decl(),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("x".to_string())),
+                right: boxed_node!(Expression::Match(Match {
+                    subject: circle(2.0),
+                    arms: vec![
+                        match_arm(
+                            variant_pattern("Circle", &["r"]),
+                            None,
+                            Expression::Identifier("r".to_string()),
+                        ),
+                        match_arm(
+                            variant_pattern("Rect", &["w", "_"]),
+                            None,
+                            Expression::Identifier("w".to_string()),
+                        ),
+                    ],
+                })),
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
+    compiler.turn_off_optimization();
     compiler.compile().unwrap();
+    let ir = compiler.ir_string();
+    assert!(ir.contains("call void @struct_set_int(void* %1, double 0.000000e+00, i64 0)"));
+    assert!(ir.contains(
+        "call void @struct_set_numeric(void* %1, double 1.000000e+00, double 2.000000e+00)"
+    ));
+    assert!(ir.contains("switch i64 %2"));
+    assert_eq!(2, ir.matches("call double @struct_get_numeric").count());
     Ok(())
 }
 
 #[test]
-fn it_compiles_ffi_calls() -> Result<(), Box<dyn Error>> {
-    let c_string = node!(Expression::FuncCall(FuncCall {
-        calee: boxed_node!(Expression::Identifier("c_string_from_string".to_string())),
-        args: vec![node!(Expression::String("foo".to_string()))],
-    }));
-
+fn it_compiles_fun_using_enum_declared_after_it() -> Result<(), Box<dyn Error>> {
     let program = Program {
         body: vec![
-            node!(Expression::Load(String::from("./tests/rockffitestlib.so"))),
-            node!(Expression::Assignment(expression::Assignment {
-                left: boxed_node!(Expression::Identifier(String::from("sum"))),
-                right: boxed_node!(Expression::Extern(expression::Extern {
-                    types: [Type::Numeric, Type::Numeric].to_vec(),
+            node!(Expression::Assignment(Assignment {
+                left: boxed_node!(Expression::Identifier("radius".to_string())),
+                right: boxed_node!(Expression::FuncDecl(FuncDecl {
                     return_type: Type::Numeric,
-                    name: String::from("rockffitest"),
-                })),
-            })),
-            node!(Expression::Assignment(expression::Assignment {
-                left: boxed_node!(Expression::Identifier(String::from("getptr"))),
-                right: boxed_node!(Expression::Extern(expression::Extern {
-                    types: [].to_vec(),
-                    return_type: Type::Ptr,
-                    name: String::from("getpr"),
-                })),
-            })),
-            node!(Expression::Assignment(expression::Assignment {
-                left: boxed_node!(Expression::Identifier(String::from("passptr"))),
-                right: boxed_node!(Expression::Extern(expression::Extern {
-                    types: [Type::Ptr].to_vec(),
-                    return_type: Type::Void,
-                    name: String::from("passptr"),
-                })),
-            })),
-            node!(Expression::Assignment(expression::Assignment {
-                left: boxed_node!(Expression::Identifier(String::from("passstr"))),
-                right: boxed_node!(Expression::Extern(expression::Extern {
-                    types: [Type::CString].to_vec(),
-                    return_type: Type::Void,
-                    name: String::from("passstr"),
+                    params: vec![Param {
+                        name: "s".to_string(),
+                        typ: Type::Enum("Shape".to_string()),
+                    }],
+                    body: vec![node!(Expression::Match(Match {
+                        subject: boxed_node!(Expression::Identifier("s".to_string())),
+                        arms: vec![
+                            match_arm(
+                                variant_pattern("Circle", &["r"]),
+                                None,
+                                Expression::Identifier("r".to_string()),
+                            ),
+                            match_arm(Pattern::Wildcard, None, Expression::Numeric(0.0)),
+                        ],
+                    }))],
                 })),
             })),
-            node!(Expression::FuncCall(expression::FuncCall {
-                calee: boxed_node!(Expression::Identifier(String::from("passptr"))),
-                args: [node!(Expression::FuncCall(expression::FuncCall {
-                    calee: boxed_node!(Expression::Identifier(String::from("getptr"))),
-                    args: [].to_vec(),
-                }))]
-                .to_vec(),
-            })),
-            node!(Expression::FuncCall(expression::FuncCall {
-                calee: boxed_node!(Expression::Identifier(String::from("sum"))),
-                args: [
-                    node!(Expression::Numeric(2.0)),
-                    node!(Expression::Numeric(3.0))
-                ]
-                .to_vec(),
-            })),
-            node!(Expression::FuncCall(expression::FuncCall {
-                calee: boxed_node!(Expression::Identifier(String::from("passstr"))),
-                args: [c_string].to_vec(),
+            node!(Expression::FuncCall(FuncCall {
+                calee: boxed_node!(Expression::Identifier("radius".to_string())),
+                type_args: vec![],
+                args: vec![*circle(2.0)],
             })),
+            shape_decl(),
         ],
     };
 
     let mut compiler = Compiler::new(program)?;
     compiler.compile().unwrap();
+    Ok(())
+}
 
-    assert_eq_ir!(
-        &compiler.ir_string(),
-        r#"target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
-
-        @0 = private unnamed_addr constant [4 x i8] c"foo\00", align 1
-
-        declare void* @string(double)
-
-        declare void @print(void*)
-
-        declare void @release_string_reference(void*)
-
-        declare void @inc_string_reference(void*)
-
-        declare void @inc_vec_reference(void*)
-
-        declare void @release_vec_reference(void*)
-
-        declare i8* @c_string_from_string(void*)
-
-        declare void* @string_from_c_string(i8*)
-
-        declare void* @vec_new()
-
-        declare void @vec_set(void*, double, double)
-
-        declare double @vec_get(void*, double)
-
-        declare double @vec_len(void*)
-
-        declare double @sqrt(double)
-
-        define void @main() {
-          %1 = call void* @getpr()
-          call void @passptr(void* %1)
-          %2 = call double @rockffitest(double 2.000000e+00, double 3.000000e+00)
-          %3 = call void* @string_from_c_string(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @0, i64 0, i64 0))
-          %4 = call i8* @c_string_from_string(void* %3)
-          call void @passstr(i8* %4)
-          call void @release_string_reference(void* %3)
-          ret void
-        }
-
-        declare double @rockffitest(double, double)
+#[test]
+fn it_returns_err_for_non_exhaustive_enum_match() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            shape_decl(),
+            node!(Expression::Match(Match {
+                subject: circle(1.0),
+                arms: vec![match_arm(
+                    variant_pattern("Circle", &["r"]),
+                    None,
+                    Expression::Integer(1),
+                )],
+            })),
+        ],
+    };
 
-        declare void* @getpr()
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::NonExhaustiveMatch { .. })
+    ));
+    Ok(())
+}
 
-        declare void @passptr(void*)
+#[test]
+fn it_returns_err_for_undefined_variant() -> Result<(), Box<dyn Error>> {
+    let program = Program {
+        body: vec![
+            shape_decl(),
+            node!(Expression::EnumVariant(EnumVariant {
+                name: "Shape".to_string(),
+                variant: "Square".to_string(),
+                args: vec![],
+            })),
+        ],
+    };
 
-        declare void @passstr(i8*)
-        "#
-    );
+    let mut compiler = Compiler::new(program)?;
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::UndefinedVariant { variant, .. }) if variant == "Square"
+    ));
     Ok(())
 }
 
 #[test]
-fn it_compile_break_in_while_and_if() -> Result<(), Box<dyn Error>> {
+fn it_returns_err_for_wrong_number_of_bindings() -> Result<(), Box<dyn Error>> {
     let program = Program {
-        body: vec![node!(Expression::While(While {
-            predicate: boxed_node!(Expression::Bool(true)),
-            body: vec![node!(Expression::Conditional(Conditional {
-                predicate: boxed_node!(Expression::Bool(true)),
-                body: vec![node!(Expression::Break), node!(Expression::Numeric(1.0))],
-                else_body: vec![],
-            }))],
-        }))],
+        body: vec![
+            shape_decl(),
+            node!(Expression::Match(Match {
+                subject: circle(1.0),
+                arms: vec![
+                    match_arm(
+                        variant_pattern("Rect", &["w"]),
+                        None,
+                        Expression::Integer(1),
+                    ),
+                    match_arm(Pattern::Wildcard, None, Expression::Integer(0)),
+                ],
+            })),
+        ],
     };
 
     let mut compiler = Compiler::new(program)?;
-    compiler.compile().unwrap();
+    assert!(matches!(
+        compiler.compile(),
+        Err(CompilerError::WrongArgumentCount {
+            expected: 2,
+            actual: 1,
+            ..
+        })
+    ));
     Ok(())
 }
@@ -73,3 +73,308 @@ fn ffi() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn closures() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("examples/closures.rck");
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("tick 1\ntick 2\ntock 1\ntick 3\n2\n13\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn closure_capture() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("tests/closure_capture.rck");
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("1\n1\n2\n0\n2\nouter\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn higher_order() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("examples/higher_order.rck");
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("2 8 18 \n4 7 12 \n8 14 24 \n1 2 3 \n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn out_of_bounds() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("tests/out_of_bounds.rck");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq("3\n").normalize())
+        .stderr(
+            predicate::eq("Runtime error: index 3 out of bounds for vector of length 3 at 4:16\n")
+                .normalize(),
+        );
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn division_by_zero() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("tests/division_by_zero.rck");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq("3\n").normalize())
+        .stderr(predicate::eq("Runtime error: division by zero at 4:13\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn number_literals() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("tests/number_literals.rck");
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("3.5\n0.25\n0.5\n3\n4\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn unchecked_vec_operations() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        (
+            "tests/unchecked_vec_insert.rck",
+            "",
+            "Runtime error: index 3 out of bounds for vector of length 2\n",
+        ),
+        (
+            "tests/unchecked_vec_remove.rck",
+            "2\n",
+            "Runtime error: index -1 out of bounds for vector of length 1\n",
+        ),
+        (
+            "tests/unchecked_vec_pop.rck",
+            "1\n",
+            "Runtime error: index -1 out of bounds for vector of length 0\n",
+        ),
+        (
+            "tests/unchecked_vec_set.rck",
+            "",
+            "Runtime error: index 5 out of bounds for vector of length 2\n",
+        ),
+        (
+            "tests/unchecked_vec_get.rck",
+            "b\n",
+            "Runtime error: index 2 out of bounds for vector of length 2\n",
+        ),
+    ];
+    for (file, stdout, stderr) in cases {
+        let mut cmd = Command::cargo_bin("rocklang")?;
+
+        cmd.arg(file).arg("--no-bounds-check");
+        cmd.assert()
+            .failure()
+            .stdout(predicate::eq(stdout).normalize())
+            .stderr(predicate::eq(stderr).normalize());
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn vec_slice_out_of_bounds() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        (
+            "tests/vec_slice_out_of_bounds.rck",
+            "2\n",
+            "Runtime error: index 4 out of bounds for vector of length 3 at 3:21\n",
+        ),
+        (
+            "tests/vec_slice_reversed.rck",
+            "0\n",
+            "Runtime error: slice end 1 is before its start 2 at 3:21\n",
+        ),
+    ];
+    for (file, stdout, stderr) in cases {
+        let mut cmd = Command::cargo_bin("rocklang")?;
+
+        cmd.arg(file);
+        cmd.assert()
+            .failure()
+            .stdout(predicate::eq(stdout).normalize())
+            .stderr(predicate::eq(stderr).normalize());
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn string_index_out_of_bounds() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        (
+            "tests/string_index_negative.rck",
+            "c\n",
+            "Runtime error: index -1 out of bounds for string of length 3 at 4:4\n",
+        ),
+        (
+            "tests/string_index_fractional.rck",
+            "b\n",
+            "Runtime error: invalid index 0.5 at 4:11\n",
+        ),
+        (
+            "tests/string_index_out_of_bounds.rck",
+            "abcd\n",
+            "Runtime error: index 10 out of bounds for string of length 4 at 5:10\n",
+        ),
+    ];
+    for (file, stdout, stderr) in cases {
+        let mut cmd = Command::cargo_bin("rocklang")?;
+
+        cmd.arg(file);
+        cmd.assert()
+            .failure()
+            .stdout(predicate::eq(stdout).normalize())
+            .stderr(predicate::eq(stderr).normalize());
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn math_values() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("tests/math_values.rck");
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("4\n0\n1\n1\n0\n2\n3\n3\n2.5\n0\n1024\n2\n3\n0\n1\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn string_set() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("tests/string_set.rck");
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("abc zbc\nXbc abc\naQc abc abc\nabW abc\nSolo!\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn string_to_number() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("rocklang")?;
+
+    cmd.arg("tests/string_to_number.rck");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq("1.5\ntrue\n-2000\n").normalize())
+        .stderr(predicate::eq("Runtime error: invalid number \"1.5x\"\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(tarpaulin, ignore)]
+fn string_set_invalid_value() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        (
+            "tests/string_set_long_value.rck",
+            "Runtime error: expected a single character, got \"xyz\" at 3:12\n",
+        ),
+        (
+            "tests/string_set_empty_value.rck",
+            "Runtime error: expected a single character, got \"\" at 3:9\n",
+        ),
+    ];
+    for (file, stderr) in cases {
+        let mut cmd = Command::cargo_bin("rocklang")?;
+
+        cmd.arg(file);
+        cmd.assert()
+            .failure()
+            .stdout(predicate::eq("abc\n").normalize())
+            .stderr(predicate::eq(stderr).normalize());
+    }
+
+    Ok(())
+}
+
+/// Runs `file` with the address space of the process limited to 400 MB, which
+/// programs leaking in their loops run out of.
+#[cfg(unix)]
+fn memory_limited(file: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let rocklang = Command::cargo_bin("rocklang")?;
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg("ulimit -v 400000 && exec \"$0\" \"$1\"")
+        .arg(rocklang.get_program())
+        .arg(file);
+    Ok(cmd)
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(tarpaulin, ignore)]
+fn branch_exits() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = memory_limited("tests/branch_exits.rck")?;
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("a negative 1\n1 2\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(tarpaulin, ignore)]
+fn match_exits() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = memory_limited("tests/match_exits.rck")?;
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("a negative 1\n1 2\n").normalize());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(tarpaulin, ignore)]
+fn for_return() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = memory_limited("tests/for_return.rck")?;
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::eq("1 -1\n").normalize());
+
+    Ok(())
+}
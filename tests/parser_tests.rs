@@ -1,8 +1,11 @@
+// the tests keep the forms they were written in
+#![allow(clippy::useless_vec, clippy::assertions_on_constants)]
+
 use assert_json_diff::assert_json_eq;
 use backtrace::Backtrace;
 use rocklang::parser::ParserError;
 use rocklang::parser::{Parse, Parser, Span};
-use rocklang::token::{Token, TokenKind};
+use rocklang::token::{StringPart, Token, TokenKind};
 use serde_json::json;
 
 macro_rules! token {
@@ -175,6 +178,7 @@ fn it_parses_while_loop() {
                     },
                     "expression": {
                         "While": {
+                            "label": null,
                             "predicate": {
                                 "span": {
                                     "column": 0,
@@ -245,6 +249,147 @@ fn it_parses_while_loop() {
     )
 }
 
+#[test]
+fn it_parses_for_loop_over_range() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::For),
+        token!(TokenKind::Identifier("i".to_string())),
+        token!(TokenKind::In),
+        token!(TokenKind::Integer(0)),
+        token!(TokenKind::DotDot),
+        token!(TokenKind::Identifier("n".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Break),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "For": {
+                            "label": null,
+                            "variable": "i",
+                            "iterable": {
+                                "Range": {
+                                    "start": {
+                                        "span": {
+                                            "column": 0,
+                                            "line": 0
+                                        },
+                                        "expression": {
+                                            "Integer": 0
+                                        }
+                                    },
+                                    "end": {
+                                        "span": {
+                                            "column": 0,
+                                            "line": 0
+                                        },
+                                        "expression": {
+                                            "Identifier": "n"
+                                        }
+                                    }
+                                }
+                            },
+                            "body": [
+                                {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": { "Break": null }
+                                }
+                            ]
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_for_loop_over_collection() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::For),
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::In),
+        token!(TokenKind::Identifier("v".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "For": {
+                            "label": null,
+                            "variable": "x",
+                            "iterable": {
+                                "Collection": {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Identifier": "v"
+                                    }
+                                }
+                            },
+                            "body": []
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_returns_error_when_no_in_after_for_variable() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::For),
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::Identifier("v".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    assert!(matches!(
+        parser.parse(),
+        Err(ParserError::SyntaxError {
+            token: Token {
+                kind: TokenKind::Identifier(_),
+                ..
+            },
+            ..
+        })
+    ));
+}
+
 #[test]
 fn it_returns_error_when_no_curly_after_while_predicate_in_while() {
     let mut parser = Parser::new(&[
@@ -510,6 +655,181 @@ fn it_parses_conditionals_with_else() {
     )
 }
 
+#[test]
+fn it_parses_else_if_chain() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::If),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Integer(1)),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Else),
+        token!(TokenKind::If),
+        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Integer(2)),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Else),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Integer(3)),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "Conditional": {
+                    "predicate": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Identifier": "a"
+                        }
+                    },
+                    "body": [{
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Integer": 1
+                        }
+                    }],
+                    "else_body": [{
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Conditional": {
+                                "predicate": {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Identifier": "b"
+                                    }
+                                },
+                                "body": [{
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Integer": 2
+                                    }
+                                }],
+                                "else_body": [{
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Integer": 3
+                                    }
+                                }]
+                            }
+                        }
+                    }]
+                }
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_parses_conditional_as_assigned_value() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::Equal),
+        token!(TokenKind::If),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Integer(1)),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Else),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Integer(2)),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "Assignment": {
+                    "left": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Identifier": "x"
+                        }
+                    },
+                    "right": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Conditional": {
+                                "predicate": {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Identifier": "a"
+                                    }
+                                },
+                                "body": [{
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Integer": 1
+                                    }
+                                }],
+                                "else_body": [{
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Integer": 2
+                                    }
+                                }]
+                            }
+                        }
+                    }
+                }
+            }
+        }]),
+        json
+    )
+}
+
 #[test]
 fn it_returns_error_when_no_curly_after_while_predicate_in_if() {
     let mut parser = Parser::new(&vec![
@@ -1136,10 +1456,13 @@ fn it_parses_division() {
 }
 
 #[test]
-fn it_parses_unary_minus() {
+fn it_parses_logical_and() {
     let mut parser = Parser::new(&[
-        token!(TokenKind::Minus),
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::Less),
         token!(TokenKind::Numeric(10.0)),
+        token!(TokenKind::And),
+        token!(TokenKind::True),
         token!(TokenKind::Eof),
     ]);
 
@@ -1155,20 +1478,49 @@ fn it_parses_unary_minus() {
                         "line": 0
                     },
                     "expression": {
-                        "Unary": {
-                            "operator": "Minus",
-                            "right": {
+                        "Binary": {
+                            "left": {
                                 "span": {
                                     "column": 0,
                                     "line": 0
                                 },
                                 "expression": {
-                                    "Numeric": 10.0
-                                }
-                            }
-                        }
-                    }
-                }
+                                    "Binary": {
+                                        "left": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Identifier": "x"
+                                            }
+                                        },
+                                        "operator": "Less",
+                                        "right": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Numeric": 10.0
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            "operator": "And",
+                            "right": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Bool": true
+                                }
+                            }
+                        }
+                    }
+                }
             ]
         ),
         json
@@ -1176,19 +1528,13 @@ fn it_parses_unary_minus() {
 }
 
 #[test]
-fn it_parses_func_declaration_with_no_params() {
-    let mut parser = Parser::new(&vec![
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::RightParen),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("void".to_string())),
-        token!(TokenKind::Arrow),
-        token!(TokenKind::LCurly),
-        token!(TokenKind::Identifier("print".to_string())),
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::RightParen),
-        token!(TokenKind::RCurly),
+fn it_parses_logical_and_with_higher_precedence_than_or() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Or),
+        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::And),
+        token!(TokenKind::Identifier("c".to_string())),
         token!(TokenKind::Eof),
     ]);
 
@@ -1204,42 +1550,46 @@ fn it_parses_func_declaration_with_no_params() {
                         "line": 0
                     },
                     "expression": {
-                        "FuncDecl": {
-                            "return_type": "Void",
-                            "params": [],
-                            "body": [
-                                {
-                                    "span": {
-                                        "column": 0,
-                                        "line": 0
-                                    },
-                                    "expression": {
-                                        "FuncCall": {
-                                            "args": [
-                                                {
-                                                    "span": {
-                                                        "column": 0,
-                                                        "line": 0
-                                                    },
-                                                    "expression": {
-                                                        "String": "hello"
-                                                    }
-                                                }
-                                            ],
-                                            "calee": {
-                                                "span": {
-                                                    "column": 0,
-                                                    "line": 0
-                                                },
-                                                "expression": {
-                                                    "Identifier": "print"
-                                                }
+                        "Binary": {
+                            "left": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Identifier": "a"
+                                }
+                            },
+                            "operator": "Or",
+                            "right": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Binary": {
+                                        "left": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Identifier": "b"
+                                            }
+                                        },
+                                        "operator": "And",
+                                        "right": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Identifier": "c"
                                             }
-
                                         }
                                     }
                                 }
-                            ]
+                            }
                         }
                     }
                 }
@@ -1250,22 +1600,13 @@ fn it_parses_func_declaration_with_no_params() {
 }
 
 #[test]
-fn it_parses_func_declaration_with_one_number_param() {
-    let mut parser = Parser::new(&vec![
-        token!(TokenKind::LeftParen),
+fn it_parses_bitwise_and_with_higher_precedence_than_bitwise_or() {
+    let mut parser = Parser::new(&[
         token!(TokenKind::Identifier("a".to_string())),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("number".to_string())),
-        token!(TokenKind::RightParen),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("void".to_string())),
-        token!(TokenKind::Arrow),
-        token!(TokenKind::LCurly),
-        token!(TokenKind::Identifier("print".to_string())),
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::RightParen),
-        token!(TokenKind::RCurly),
+        token!(TokenKind::Pipe),
+        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::Ampersand),
+        token!(TokenKind::Identifier("c".to_string())),
         token!(TokenKind::Eof),
     ]);
 
@@ -1281,47 +1622,46 @@ fn it_parses_func_declaration_with_one_number_param() {
                         "line": 0
                     },
                     "expression": {
-                        "FuncDecl": {
-                            "return_type": "Void",
-                            "params": [
-                                {
-                                    "typ": "Numeric",
-                                    "name": "a"
+                        "Binary": {
+                            "left": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Identifier": "a"
                                 }
-                            ],
-                            "body": [
-                                {
-                                    "span": {
-                                        "column": 0,
-                                        "line": 0
-                                    },
-                                    "expression": {
-                                        "FuncCall": {
-                                            "args": [
-                                                {
-                                                    "span": {
-                                                        "column": 0,
-                                                        "line": 0
-                                                    },
-                                                    "expression": {
-                                                        "String": "hello"
-                                                    }
-                                                }
-                                            ],
-                                            "calee": {
-                                                "span": {
-                                                    "column": 0,
-                                                    "line": 0
-                                                },
-                                                "expression": {
-                                                    "Identifier": "print"
-                                                }
+                            },
+                            "operator": "BitOr",
+                            "right": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Binary": {
+                                        "left": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Identifier": "b"
+                                            }
+                                        },
+                                        "operator": "BitAnd",
+                                        "right": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Identifier": "c"
                                             }
-
                                         }
                                     }
                                 }
-                            ]
+                            }
                         }
                     }
                 }
@@ -1332,22 +1672,13 @@ fn it_parses_func_declaration_with_one_number_param() {
 }
 
 #[test]
-fn it_parses_func_declaration_with_one_vec_param() {
-    let mut parser = Parser::new(&vec![
-        token!(TokenKind::LeftParen),
+fn it_parses_shift_with_higher_precedence_than_equality() {
+    let mut parser = Parser::new(&[
         token!(TokenKind::Identifier("a".to_string())),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("vec".to_string())),
-        token!(TokenKind::RightParen),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("void".to_string())),
-        token!(TokenKind::Arrow),
-        token!(TokenKind::LCurly),
-        token!(TokenKind::Identifier("print".to_string())),
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::RightParen),
-        token!(TokenKind::RCurly),
+        token!(TokenKind::DoubleEqual),
+        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::ShiftRight),
+        token!(TokenKind::Identifier("c".to_string())),
         token!(TokenKind::Eof),
     ]);
 
@@ -1363,49 +1694,48 @@ fn it_parses_func_declaration_with_one_vec_param() {
                         "line": 0
                     },
                     "expression": {
-                        "FuncDecl": {
-                            "return_type": "Void",
-                            "params": [
-                                {
-                                    "typ": "Vector",
-                                    "name": "a"
-                                }
-                            ],
-                            "body": [
-                                {
-                                    "span": {
-                                        "column": 0,
-                                        "line": 0
-                                    },
-                                    "expression": {
-                                        "FuncCall": {
-                                            "args": [
-                                                {
-                                                    "span": {
-                                                        "column": 0,
-                                                        "line": 0
-                                                    },
-                                                    "expression": {
-                                                        "String": "hello"
-                                                    }
-                                                }
-                                            ],
-                                            "calee": {
-                                                "span": {
-                                                    "column": 0,
-                                                    "line": 0
-                                                },
-                                                "expression": {
-                                                    "Identifier": "print"
-                                                }
-                                            }
-
-                                        }
-                                    }
+                        "Binary": {
+                            "left": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Identifier": "a"
                                 }
-                            ]
-                        }
-                    }
+                            },
+                            "operator": "Equal",
+                            "right": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Binary": {
+                                        "left": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Identifier": "b"
+                                            }
+                                        },
+                                        "operator": "ShiftRight",
+                                        "right": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Identifier": "c"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
             ]
         ),
@@ -1414,12 +1744,129 @@ fn it_parses_func_declaration_with_one_vec_param() {
 }
 
 #[test]
-fn it_parses_func_declaration_with_one_fun_param() {
+fn it_parses_unary_bitwise_not() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Tilde),
+        token!(TokenKind::Integer(10)),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "Unary": {
+                            "operator": "BitNot",
+                            "right": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Integer": 10
+                                }
+                            }
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_unary_minus() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Minus),
+        token!(TokenKind::Numeric(10.0)),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "Unary": {
+                            "operator": "Minus",
+                            "right": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Numeric": 10.0
+                                }
+                            }
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_unary_not() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Exclamation),
+        token!(TokenKind::Identifier("done".to_string())),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "Unary": {
+                            "operator": "Not",
+                            "right": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Identifier": "done"
+                                }
+                            }
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_func_declaration_with_no_params() {
     let mut parser = Parser::new(&vec![
         token!(TokenKind::LeftParen),
-        token!(TokenKind::Identifier("a".to_string())),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("fun".to_string())),
         token!(TokenKind::RightParen),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("void".to_string())),
@@ -1447,12 +1894,7 @@ fn it_parses_func_declaration_with_one_fun_param() {
                     "expression": {
                         "FuncDecl": {
                             "return_type": "Void",
-                            "params": [
-                                {
-                                    "typ": "Function",
-                                    "name": "a"
-                                }
-                            ],
+                            "params": [],
                             "body": [
                                 {
                                     "span": {
@@ -1468,7 +1910,6 @@ fn it_parses_func_declaration_with_one_fun_param() {
                                                         "line": 0
                                                     },
                                                     "expression": {
-
                                                         "String": "hello"
                                                     }
                                                 }
@@ -1482,6 +1923,7 @@ fn it_parses_func_declaration_with_one_fun_param() {
                                                     "Identifier": "print"
                                                 }
                                             }
+
                                         }
                                     }
                                 }
@@ -1496,15 +1938,11 @@ fn it_parses_func_declaration_with_one_fun_param() {
 }
 
 #[test]
-fn it_parses_func_declaration_with_multiple_params() {
+fn it_parses_func_declaration_with_one_number_param() {
     let mut parser = Parser::new(&vec![
         token!(TokenKind::LeftParen),
         token!(TokenKind::Identifier("a".to_string())),
         token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("fun".to_string())),
-        token!(TokenKind::Comma),
-        token!(TokenKind::Identifier("b".to_string())),
-        token!(TokenKind::Colon),
         token!(TokenKind::Identifier("number".to_string())),
         token!(TokenKind::RightParen),
         token!(TokenKind::Colon),
@@ -1534,13 +1972,9 @@ fn it_parses_func_declaration_with_multiple_params() {
                         "FuncDecl": {
                             "return_type": "Void",
                             "params": [
-                                {
-                                    "typ": "Function",
-                                    "name": "a"
-                                },
                                 {
                                     "typ": "Numeric",
-                                    "name": "b"
+                                    "name": "a"
                                 }
                             ],
                             "body": [
@@ -1586,16 +2020,12 @@ fn it_parses_func_declaration_with_multiple_params() {
 }
 
 #[test]
-fn it_returns_an_error_when_func_has_unknown_arg_type() {
+fn it_parses_func_declaration_with_one_vec_param() {
     let mut parser = Parser::new(&vec![
         token!(TokenKind::LeftParen),
         token!(TokenKind::Identifier("a".to_string())),
         token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("fun".to_string())),
-        token!(TokenKind::Comma),
-        token!(TokenKind::Identifier("b".to_string())),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("wrongtype".to_string())),
+        token!(TokenKind::Identifier("vec".to_string())),
         token!(TokenKind::RightParen),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("void".to_string())),
@@ -1609,34 +2039,75 @@ fn it_returns_an_error_when_func_has_unknown_arg_type() {
         token!(TokenKind::Eof),
     ]);
 
-    match parser.parse() {
-        Ok(_) => assert!(false, "should return an error"),
-        Err(e) => {
-            assert!(matches!(
-                e,
-                ParserError::SyntaxError {
-                    token: Token {
-                        kind: TokenKind::Identifier(_),
-                        ..
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
                     },
-                    ..
+                    "expression": {
+                        "FuncDecl": {
+                            "return_type": "Void",
+                            "params": [
+                                {
+                                    "typ": { "Vector": "Numeric" },
+                                    "name": "a"
+                                }
+                            ],
+                            "body": [
+                                {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "FuncCall": {
+                                            "args": [
+                                                {
+                                                    "span": {
+                                                        "column": 0,
+                                                        "line": 0
+                                                    },
+                                                    "expression": {
+                                                        "String": "hello"
+                                                    }
+                                                }
+                                            ],
+                                            "calee": {
+                                                "span": {
+                                                    "column": 0,
+                                                    "line": 0
+                                                },
+                                                "expression": {
+                                                    "Identifier": "print"
+                                                }
+                                            }
+
+                                        }
+                                    }
+                                }
+                            ]
+                        }
+                    }
                 }
-            ));
-        }
-    };
+            ]
+        ),
+        json
+    )
 }
 
 #[test]
-fn it_returns_an_error_when_func_has_non_type_expression_as_arg_type() {
+fn it_parses_func_declaration_with_one_fun_param() {
     let mut parser = Parser::new(&vec![
         token!(TokenKind::LeftParen),
         token!(TokenKind::Identifier("a".to_string())),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("fun".to_string())),
-        token!(TokenKind::Comma),
-        token!(TokenKind::Identifier("b".to_string())),
-        token!(TokenKind::Colon),
-        token!(TokenKind::String("string".to_string())),
         token!(TokenKind::RightParen),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("void".to_string())),
@@ -1650,144 +2121,273 @@ fn it_returns_an_error_when_func_has_non_type_expression_as_arg_type() {
         token!(TokenKind::Eof),
     ]);
 
-    match parser.parse() {
-        Ok(_) => assert!(false, "should return an error"),
-        Err(e) => {
-            assert!(matches!(
-                e,
-                ParserError::SyntaxError {
-                    token: Token {
-                        kind: TokenKind::String(_),
-                        ..
-                    },
-                    ..
-                },
-            ));
-        }
-    };
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "FuncDecl": {
+                            "return_type": "Void",
+                            "params": [
+                                {
+                                    "typ": {
+                                        "Function": {
+                                            "params": [],
+                                            "return_type": "Void"
+                                        }
+                                    },
+                                    "name": "a"
+                                }
+                            ],
+                            "body": [
+                                {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "FuncCall": {
+                                            "args": [
+                                                {
+                                                    "span": {
+                                                        "column": 0,
+                                                        "line": 0
+                                                    },
+                                                    "expression": {
+
+                                                        "String": "hello"
+                                                    }
+                                                }
+                                            ],
+                                            "calee": {
+                                                "span": {
+                                                    "column": 0,
+                                                    "line": 0
+                                                },
+                                                "expression": {
+                                                    "Identifier": "print"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            ]
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
 }
 
 #[test]
-fn it_returns_an_error_when_func_has_no_arg_type_after_arg_name() {
-    let mut parser = Parser::new(&vec![
+fn it_parses_func_declaration_with_typed_fun_param() {
+    let mut parser = Parser::new(&[
         token!(TokenKind::LeftParen),
-        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Identifier("f".to_string())),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::Less),
+        token!(TokenKind::Identifier("number".to_string())),
         token!(TokenKind::Comma),
-        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::Identifier("bool".to_string())),
+        token!(TokenKind::Greater),
         token!(TokenKind::RightParen),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("void".to_string())),
         token!(TokenKind::Arrow),
         token!(TokenKind::LCurly),
-        token!(TokenKind::Identifier("print".to_string())),
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::RightParen),
         token!(TokenKind::RCurly),
         token!(TokenKind::Eof),
     ]);
 
-    match parser.parse() {
-        Ok(_) => assert!(false, "should return an error"),
-        Err(e) => {
-            assert!(matches!(
-                e,
-                ParserError::SyntaxError {
-                    token: Token {
-                        kind: TokenKind::Colon,
-                        ..
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
                     },
-                    ..
-                },
-            ));
-        }
-    };
+                    "expression": {
+                        "FuncDecl": {
+                            "return_type": "Void",
+                            "params": [
+                                {
+                                    "typ": {
+                                        "Function": {
+                                            "params": ["Numeric"],
+                                            "return_type": "Bool"
+                                        }
+                                    },
+                                    "name": "f"
+                                }
+                            ],
+                            "body": []
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
 }
 
 #[test]
-fn it_returns_an_error_when_func_has_no_return_type() {
-    let mut parser = Parser::new(&vec![
+fn it_parses_func_declaration_with_nested_fun_param() {
+    let mut parser = Parser::new(&[
         token!(TokenKind::LeftParen),
-        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Identifier("f".to_string())),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::Less),
+        token!(TokenKind::Identifier("int".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::Less),
+        token!(TokenKind::Identifier("int".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("int".to_string())),
+        token!(TokenKind::ShiftRight),
         token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("void".to_string())),
         token!(TokenKind::Arrow),
         token!(TokenKind::LCurly),
-        token!(TokenKind::Identifier("print".to_string())),
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::RightParen),
         token!(TokenKind::RCurly),
         token!(TokenKind::Eof),
     ]);
 
-    match parser.parse() {
-        Ok(_) => assert!(false, "should return an error"),
-        Err(e) => {
-            assert!(matches!(
-                e,
-                ParserError::SyntaxError {
-                    token: Token {
-                        kind: TokenKind::Arrow,
-                        ..
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
                     },
-                    ..
-                },
-            ));
-        }
-    };
+                    "expression": {
+                        "FuncDecl": {
+                            "return_type": "Void",
+                            "params": [
+                                {
+                                    "typ": {
+                                        "Function": {
+                                            "params": ["Int"],
+                                            "return_type": {
+                                                "Function": {
+                                                    "params": ["Int"],
+                                                    "return_type": "Int"
+                                                }
+                                            }
+                                        }
+                                    },
+                                    "name": "f"
+                                }
+                            ],
+                            "body": []
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
 }
 
 #[test]
-fn it_returns_an_error_when_func_has_unknown_return_type() {
-    let mut parser = Parser::new(&vec![
+fn it_parses_call_of_returned_function() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Identifier("f".to_string())),
         token!(TokenKind::LeftParen),
-        token!(TokenKind::Identifier("a".to_string())),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("fun".to_string())),
         token!(TokenKind::RightParen),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("wrongtype".to_string())),
-        token!(TokenKind::Arrow),
-        token!(TokenKind::LCurly),
-        token!(TokenKind::Identifier("print".to_string())),
         token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::Numeric(1.0)),
         token!(TokenKind::RightParen),
-        token!(TokenKind::RCurly),
         token!(TokenKind::Eof),
     ]);
 
-    match parser.parse() {
-        Ok(_) => assert!(false, "should return an error"),
-        Err(e) => {
-            assert!(matches!(
-                e,
-                ParserError::SyntaxError {
-                    token: Token {
-                        kind: TokenKind::Identifier(_),
-                        ..
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
                     },
-                    ..
-                },
-            ));
-        }
-    };
+                    "expression": {
+                        "FuncCall": {
+                            "args": [
+                                {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Numeric": 1.0
+                                    }
+                                }
+                            ],
+                            "calee": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "FuncCall": {
+                                        "args": [],
+                                        "calee": {
+                                            "span": {
+                                                "column": 0,
+                                                "line": 0
+                                            },
+                                            "expression": {
+                                                "Identifier": "f"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
 }
 
 #[test]
-fn it_returns_an_error_when_func_has_non_type_expression_as_return_type() {
+fn it_parses_func_declaration_with_multiple_params() {
     let mut parser = Parser::new(&vec![
         token!(TokenKind::LeftParen),
         token!(TokenKind::Identifier("a".to_string())),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("number".to_string())),
         token!(TokenKind::RightParen),
         token!(TokenKind::Colon),
-        token!(TokenKind::String("string".to_string())),
+        token!(TokenKind::Identifier("void".to_string())),
         token!(TokenKind::Arrow),
         token!(TokenKind::LCurly),
         token!(TokenKind::Identifier("print".to_string())),
@@ -1798,8 +2398,144 @@ fn it_returns_an_error_when_func_has_non_type_expression_as_return_type() {
         token!(TokenKind::Eof),
     ]);
 
-    match parser.parse() {
-        Ok(_) => assert!(false, "should return an error"),
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "FuncDecl": {
+                            "return_type": "Void",
+                            "params": [
+                                {
+                                    "typ": {
+                                        "Function": {
+                                            "params": [],
+                                            "return_type": "Void"
+                                        }
+                                    },
+                                    "name": "a"
+                                },
+                                {
+                                    "typ": "Numeric",
+                                    "name": "b"
+                                }
+                            ],
+                            "body": [
+                                {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "FuncCall": {
+                                            "args": [
+                                                {
+                                                    "span": {
+                                                        "column": 0,
+                                                        "line": 0
+                                                    },
+                                                    "expression": {
+                                                        "String": "hello"
+                                                    }
+                                                }
+                                            ],
+                                            "calee": {
+                                                "span": {
+                                                    "column": 0,
+                                                    "line": 0
+                                                },
+                                                "expression": {
+                                                    "Identifier": "print"
+                                                }
+                                            }
+
+                                        }
+                                    }
+                                }
+                            ]
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_returns_an_error_when_func_has_unknown_arg_type() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("wrongtype".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("void".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("print".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    match parser.parse() {
+        Ok(_) => assert!(false, "should return an error"),
+        Err(e) => {
+            assert!(matches!(
+                e,
+                ParserError::UndefinedType {
+                    token: Token {
+                        kind: TokenKind::Identifier(ref name),
+                        ..
+                    },
+                    ..
+                } if name == "wrongtype"
+            ));
+        }
+    };
+}
+
+#[test]
+fn it_returns_an_error_when_func_has_non_type_expression_as_arg_type() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::String("string".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("void".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("print".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    match parser.parse() {
+        Ok(_) => assert!(false, "should return an error"),
         Err(e) => {
             assert!(matches!(
                 e,
@@ -1816,15 +2552,18 @@ fn it_returns_an_error_when_func_has_non_type_expression_as_return_type() {
 }
 
 #[test]
-fn it_returns_error_when_func_decl_has_no_arrow() {
+fn it_returns_an_error_when_func_has_no_arg_type_after_arg_name() {
     let mut parser = Parser::new(&vec![
         token!(TokenKind::LeftParen),
         token!(TokenKind::Identifier("a".to_string())),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("b".to_string())),
         token!(TokenKind::RightParen),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("void".to_string())),
+        token!(TokenKind::Arrow),
         token!(TokenKind::LCurly),
         token!(TokenKind::Identifier("print".to_string())),
         token!(TokenKind::LeftParen),
@@ -1841,7 +2580,7 @@ fn it_returns_error_when_func_decl_has_no_arrow() {
                 e,
                 ParserError::SyntaxError {
                     token: Token {
-                        kind: TokenKind::LCurly,
+                        kind: TokenKind::Colon,
                         ..
                     },
                     ..
@@ -1852,17 +2591,20 @@ fn it_returns_error_when_func_decl_has_no_arrow() {
 }
 
 #[test]
-fn it_returns_error_when_func_decl_has_no_body() {
+fn it_returns_an_error_when_func_has_no_return_type() {
     let mut parser = Parser::new(&vec![
         token!(TokenKind::LeftParen),
         token!(TokenKind::Identifier("a".to_string())),
         token!(TokenKind::Colon),
         token!(TokenKind::Identifier("fun".to_string())),
         token!(TokenKind::RightParen),
-        token!(TokenKind::Colon),
-        token!(TokenKind::Identifier("void".to_string())),
         token!(TokenKind::Arrow),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("print".to_string())),
+        token!(TokenKind::LeftParen),
         token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::RCurly),
         token!(TokenKind::Eof),
     ]);
 
@@ -1873,7 +2615,7 @@ fn it_returns_error_when_func_decl_has_no_body() {
                 e,
                 ParserError::SyntaxError {
                     token: Token {
-                        kind: TokenKind::String(_),
+                        kind: TokenKind::Arrow,
                         ..
                     },
                     ..
@@ -1884,54 +2626,1002 @@ fn it_returns_error_when_func_decl_has_no_body() {
 }
 
 #[test]
-fn it_parses_func_call() {
-    let mut parser = Parser::new(&[
+fn it_returns_an_error_when_func_has_unknown_return_type() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("wrongtype".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::LCurly),
         token!(TokenKind::Identifier("print".to_string())),
         token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
         token!(TokenKind::RightParen),
+        token!(TokenKind::RCurly),
         token!(TokenKind::Eof),
     ]);
 
-    let ast = parser.parse().unwrap().body;
-    let json = serde_json::to_value(&ast).unwrap();
+    match parser.parse() {
+        Ok(_) => assert!(false, "should return an error"),
+        Err(e) => {
+            assert!(matches!(
+                e,
+                ParserError::UndefinedType {
+                    token: Token {
+                        kind: TokenKind::Identifier(ref name),
+                        ..
+                    },
+                    ..
+                } if name == "wrongtype",
+            ));
+        }
+    };
+}
 
-    assert_json_eq!(
-        json!(
-            [
-                {
-                    "span": {
-                        "column": 0,
-                        "line": 0
+#[test]
+fn it_returns_an_error_when_func_has_non_type_expression_as_return_type() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::String("string".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("print".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    match parser.parse() {
+        Ok(_) => assert!(false, "should return an error"),
+        Err(e) => {
+            assert!(matches!(
+                e,
+                ParserError::SyntaxError {
+                    token: Token {
+                        kind: TokenKind::String(_),
+                        ..
                     },
-                    "expression": {
-                        "FuncCall": {
-                            "args": [],
-                            "calee": {
-                                "span": {
-                                    "column": 0,
-                                    "line": 0
-                                },
-                                "expression": {
-                                    "Identifier": "print"
-                                }
-                            }
+                    ..
+                },
+            ));
+        }
+    };
+}
 
-                        }
-                    }
-                }
-            ]
-        ),
+#[test]
+fn it_returns_error_when_func_decl_has_no_arrow() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("void".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("print".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    match parser.parse() {
+        Ok(_) => assert!(false, "should return an error"),
+        Err(e) => {
+            assert!(matches!(
+                e,
+                ParserError::SyntaxError {
+                    token: Token {
+                        kind: TokenKind::LCurly,
+                        ..
+                    },
+                    ..
+                },
+            ));
+        }
+    };
+}
+
+#[test]
+fn it_returns_error_when_func_decl_has_no_body() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("fun".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("void".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::Eof),
+    ]);
+
+    match parser.parse() {
+        Ok(_) => assert!(false, "should return an error"),
+        Err(e) => {
+            assert!(matches!(
+                e,
+                ParserError::SyntaxError {
+                    token: Token {
+                        kind: TokenKind::String(_),
+                        ..
+                    },
+                    ..
+                },
+            ));
+        }
+    };
+}
+
+#[test]
+fn it_parses_func_call() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Identifier("print".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "FuncCall": {
+                            "args": [],
+                            "calee": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Identifier": "print"
+                                }
+                            }
+
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_func_call_with_one_arg() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Identifier("print".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "FuncCall": {
+                            "args": [
+                                {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "String": "hello"
+                                    }
+                                }
+                            ],
+                            "calee": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Identifier": "print"
+                                }
+                            }
+
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_func_call_with_two_args() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::Identifier("print".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Numeric(10.0)),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "FuncCall": {
+                            "args": [
+                                {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "String": "hello"
+                                    }
+                                },
+                                {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Numeric": 10.0
+                                    }
+                                }
+                            ],
+                            "calee": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Identifier": "print"
+                                }
+                            }
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_returns_error_for_call_syntax_on_non_identifiers() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Numeric(10.0)),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Eof),
+    ]);
+
+    match parser.parse() {
+        Ok(_) => assert!(false, "should return an error"),
+        Err(e) => {
+            assert!(matches!(
+                e,
+                ParserError::SyntaxError {
+                    token: Token {
+                        kind: TokenKind::LeftParen,
+                        ..
+                    },
+                    ..
+                },
+            ));
+        }
+    };
+}
+
+#[test]
+fn it_parses_grouping_expression() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "Grouping": {
+                            "span": {
+                                "column": 0,
+                                "line": 0
+                            },
+                            "expression": {
+                                "String": "hello"
+                            }
+                        }
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_returns_error_for_unterminated_grouping_expresions() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::Eof),
+    ]);
+
+    match parser.parse() {
+        Ok(_) => assert!(false, "should return an error"),
+        Err(e) => {
+            assert!(matches!(
+                e,
+                ParserError::SyntaxError {
+                    token: Token {
+                        kind: TokenKind::String(_),
+                        ..
+                    },
+                    ..
+                },
+            ));
+        }
+    };
+}
+
+#[test]
+fn it_parses_true_bool_literal() {
+    let mut parser = Parser::new(&[token!(TokenKind::True), token!(TokenKind::Eof)]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "Bool": true
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_false_bool_literal() {
+    let mut parser = Parser::new(&[token!(TokenKind::False), token!(TokenKind::Eof)]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "Bool": false
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_integer_literal() {
+    let mut parser = Parser::new(&[token!(TokenKind::Integer(42)), token!(TokenKind::Eof)]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!(
+            [
+                {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "Integer": 42
+                    }
+                }
+            ]
+        ),
+        json
+    )
+}
+
+#[test]
+fn it_parses_break_expression() {
+    let mut parser = Parser::new(&[token!(TokenKind::Break), token!(TokenKind::Eof)]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "expression": { "Break": null },
+            "span": {
+                "column": 0,
+                "line": 0
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_parses_continue_expression() {
+    let mut parser = Parser::new(&[token!(TokenKind::Continue), token!(TokenKind::Eof)]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "expression": { "Continue": null },
+            "span": {
+                "column": 0,
+                "line": 0
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_parses_labeled_loop_with_labeled_break() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Label("outer".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::While),
+        token!(TokenKind::True),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Break),
+        token!(TokenKind::Label("outer".to_string())),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "While": {
+                    "label": "outer",
+                    "predicate": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Bool": true
+                        }
+                    },
+                    "body": [{
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Break": "outer"
+                        }
+                    }]
+                }
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_returns_error_for_label_without_loop() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Label("outer".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::Eof),
+    ]);
+
+    assert!(matches!(
+        parser.parse(),
+        Err(ParserError::SyntaxError {
+            token: Token {
+                kind: TokenKind::Identifier(_),
+                ..
+            },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn it_parses_match_expression() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Match),
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Integer(1)),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::True),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Integer(2)),
+        token!(TokenKind::DotDot),
+        token!(TokenKind::Integer(5)),
+        token!(TokenKind::If),
+        token!(TokenKind::Identifier("y".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::False),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Identifier("_".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::False),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "Match": {
+                    "subject": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Identifier": "x"
+                        }
+                    },
+                    "arms": [
+                        {
+                            "pattern": {
+                                "Literal": {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Integer": 1
+                                    }
+                                }
+                            },
+                            "guard": null,
+                            "body": [{
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Bool": true
+                                }
+                            }]
+                        },
+                        {
+                            "pattern": {
+                                "Range": {
+                                    "start": {
+                                        "span": {
+                                            "column": 0,
+                                            "line": 0
+                                        },
+                                        "expression": {
+                                            "Integer": 2
+                                        }
+                                    },
+                                    "end": {
+                                        "span": {
+                                            "column": 0,
+                                            "line": 0
+                                        },
+                                        "expression": {
+                                            "Integer": 5
+                                        }
+                                    }
+                                }
+                            },
+                            "guard": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Identifier": "y"
+                                }
+                            },
+                            "body": [{
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Bool": false
+                                }
+                            }]
+                        },
+                        {
+                            "pattern": "Wildcard",
+                            "guard": null,
+                            "body": [{
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Bool": false
+                                }
+                            }]
+                        }
+                    ]
+                }
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_returns_error_for_match_arm_without_arrow() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Match),
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Integer(1)),
+        token!(TokenKind::True),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    assert!(matches!(
+        parser.parse(),
+        Err(ParserError::SyntaxError {
+            token: Token {
+                kind: TokenKind::True,
+                ..
+            },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn it_parses_return_expression_without_value() {
+    let mut parser = Parser::new(&[token!(TokenKind::Return), token!(TokenKind::Eof)]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "expression": {
+                "Return": null
+            },
+            "span": {
+                "column": 0,
+                "line": 0
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_parses_return_expression_with_value() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Return),
+        token!(TokenKind::Numeric(1.0)),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "expression": {
+                "Return": {
+                    "expression": {
+                        "Numeric": 1.0
+                    },
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    }
+                }
+            },
+            "span": {
+                "column": 0,
+                "line": 0
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_parses_return_value_only_on_the_same_line() {
+    let mut parser = Parser::new(&[
+        Token {
+            kind: TokenKind::Return,
+            span: Span { line: 1, column: 1 },
+        },
+        Token {
+            kind: TokenKind::Numeric(1.0),
+            span: Span { line: 2, column: 1 },
+        },
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    assert_eq!(2, ast.len());
+}
+
+#[test]
+fn it_parses_grouping_expression_with_identifiers() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::Identifier("b".to_string())),
+        token!(TokenKind::Equal),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("a".to_string())),
+        token!(TokenKind::Plus),
+        token!(TokenKind::Numeric(1.0)),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Asterisk),
+        token!(TokenKind::Numeric(2.0)),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "Assignment": {
+                    "left": {
+                        "expression": {
+                            "Identifier": "b"
+                        },
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                    },
+                "right": {
+                    "span": {
+                        "column": 0,
+                        "line": 0
+                    },
+                    "expression": {
+                        "Binary": {
+                            "left": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Grouping": {
+                                        "span": {
+                                            "column": 0,
+                                            "line": 0
+                                        },
+                                        "expression": {
+                                            "Binary": {
+                                                "left": {
+                                                    "expression": {
+                                                        "Identifier": "a"
+                                                    },
+                                                    "span": {
+                                                        "column": 0,
+                                                        "line": 0
+                                                    },
+                                                },
+                                                "operator": "Plus",
+                                                "right": {
+                                                    "expression": {
+                                                        "Numeric": 1.0,
+                                                    },
+                                                    "span": {
+                                                        "column": 0,
+                                                        "line": 0
+                                                    },
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            "operator": "Asterisk",
+                            "right": {
+                                "span": {
+                                    "column": 0,
+                                    "line": 0
+                                },
+                                "expression": {
+                                    "Numeric": 2.0
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_parses_load_expression() {
+    let mut parser = Parser::new(&vec![
+        token!(TokenKind::Load),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::String(String::from("somelib.so"))),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "Load": "somelib.so"
+            }
+        }]),
+        json
+    )
+}
+
+#[test]
+fn it_parses_struct_declaration() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Struct),
+        token!(TokenKind::Identifier("Complex".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("re".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("number".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("next".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("Complex".to_string())),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "StructDecl": {
+                    "name": "Complex",
+                    "fields": [
+                        {
+                            "typ": "Numeric",
+                            "name": "re"
+                        },
+                        {
+                            "typ": {
+                                "Struct": "Complex"
+                            },
+                            "name": "next"
+                        }
+                    ]
+                }
+            }
+        }]),
         json
-    )
+    );
 }
 
 #[test]
-fn it_parses_func_call_with_one_arg() {
+fn it_parses_struct_literal_and_field_assignment() {
     let mut parser = Parser::new(&[
-        token!(TokenKind::Identifier("print".to_string())),
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::RightParen),
+        token!(TokenKind::Identifier("c".to_string())),
+        token!(TokenKind::Dot),
+        token!(TokenKind::Identifier("re".to_string())),
+        token!(TokenKind::Equal),
+        token!(TokenKind::Identifier("Complex".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("re".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("d".to_string())),
+        token!(TokenKind::Dot),
+        token!(TokenKind::Identifier("im".to_string())),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Dot),
+        token!(TokenKind::Identifier("re".to_string())),
         token!(TokenKind::Eof),
     ]);
 
@@ -1939,54 +3629,99 @@ fn it_parses_func_call_with_one_arg() {
     let json = serde_json::to_value(&ast).unwrap();
 
     assert_json_eq!(
-        json!(
-            [
-                {
-                    "span": {
-                        "column": 0,
-                        "line": 0
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "Assignment": {
+                    "left": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "FieldAccess": {
+                                "object": {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Identifier": "c"
+                                    }
+                                },
+                                "field": "re"
+                            }
+                        }
                     },
-                    "expression": {
-                        "FuncCall": {
-                            "args": [
-                                {
+                    "right": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "FieldAccess": {
+                                "object": {
                                     "span": {
                                         "column": 0,
                                         "line": 0
                                     },
                                     "expression": {
-                                        "String": "hello"
+                                        "StructLiteral": {
+                                            "name": "Complex",
+                                            "fields": [{
+                                                "name": "re",
+                                                "value": {
+                                                    "span": {
+                                                        "column": 0,
+                                                        "line": 0
+                                                    },
+                                                    "expression": {
+                                                        "FieldAccess": {
+                                                            "object": {
+                                                                "span": {
+                                                                    "column": 0,
+                                                                    "line": 0
+                                                                },
+                                                                "expression": {
+                                                                    "Identifier": "d"
+                                                                }
+                                                            },
+                                                            "field": "im"
+                                                        }
+                                                    }
+                                                }
+                                            }]
+                                        }
                                     }
-                                }
-                            ],
-                            "calee": {
-                                "span": {
-                                    "column": 0,
-                                    "line": 0
                                 },
-                                "expression": {
-                                    "Identifier": "print"
-                                }
+                                "field": "re"
                             }
-
                         }
                     }
                 }
-            ]
-        ),
+            }
+        }]),
         json
-    )
+    );
 }
 
 #[test]
-fn it_parses_func_call_with_two_args() {
-    let mut parser = Parser::new(&vec![
-        token!(TokenKind::Identifier("print".to_string())),
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::Comma),
-        token!(TokenKind::Numeric(10.0)),
-        token!(TokenKind::RightParen),
+fn it_parses_index_assignment() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Identifier("v".to_string())),
+        token!(TokenKind::LeftBracket),
+        token!(TokenKind::Identifier("i".to_string())),
+        token!(TokenKind::RightBracket),
+        token!(TokenKind::Equal),
+        token!(TokenKind::Identifier("w".to_string())),
+        token!(TokenKind::LeftBracket),
+        token!(TokenKind::Integer(0)),
+        token!(TokenKind::RightBracket),
+        token!(TokenKind::Dot),
+        token!(TokenKind::Identifier("re".to_string())),
         token!(TokenKind::Eof),
     ]);
 
@@ -1994,88 +3729,97 @@ fn it_parses_func_call_with_two_args() {
     let json = serde_json::to_value(&ast).unwrap();
 
     assert_json_eq!(
-        json!(
-            [
-                {
-                    "span": {
-                        "column": 0,
-                        "line": 0
-                    },
-                    "expression": {
-                        "FuncCall": {
-                            "args": [
-                                {
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "Assignment": {
+                    "left": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Index": {
+                                "object": {
                                     "span": {
                                         "column": 0,
                                         "line": 0
                                     },
                                     "expression": {
-                                        "String": "hello"
+                                        "Identifier": "v"
                                     }
                                 },
-                                {
+                                "index": {
                                     "span": {
                                         "column": 0,
                                         "line": 0
                                     },
                                     "expression": {
-                                        "Numeric": 10.0
+                                        "Identifier": "i"
                                     }
                                 }
-                            ],
-                            "calee": {
-                                "span": {
-                                    "column": 0,
-                                    "line": 0
+                            }
+                        }
+                    },
+                    "right": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "FieldAccess": {
+                                "object": {
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Index": {
+                                            "object": {
+                                                "span": {
+                                                    "column": 0,
+                                                    "line": 0
+                                                },
+                                                "expression": {
+                                                    "Identifier": "w"
+                                                }
+                                            },
+                                            "index": {
+                                                "span": {
+                                                    "column": 0,
+                                                    "line": 0
+                                                },
+                                                "expression": {
+                                                    "Integer": 0
+                                                }
+                                            }
+                                        }
+                                    }
                                 },
-                                "expression": {
-                                    "Identifier": "print"
-                                }
+                                "field": "re"
                             }
                         }
                     }
                 }
-            ]
-        ),
+            }
+        }]),
         json
-    )
-}
-
-#[test]
-fn it_returns_error_for_call_syntax_on_non_identifiers() {
-    let mut parser = Parser::new(&vec![
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::Comma),
-        token!(TokenKind::Numeric(10.0)),
-        token!(TokenKind::RightParen),
-        token!(TokenKind::Eof),
-    ]);
-
-    match parser.parse() {
-        Ok(_) => assert!(false, "should return an error"),
-        Err(e) => {
-            assert!(matches!(
-                e,
-                ParserError::SyntaxError {
-                    token: Token {
-                        kind: TokenKind::LeftParen,
-                        ..
-                    },
-                    ..
-                },
-            ));
-        }
-    };
+    );
 }
 
 #[test]
-fn it_parses_grouping_expression() {
+fn it_parses_interpolated_string() {
     let mut parser = Parser::new(&[
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
-        token!(TokenKind::RightParen),
+        token!(TokenKind::InterpolatedString(vec![
+            StringPart::Text("x = ".to_string()),
+            StringPart::Code(vec![
+                token!(TokenKind::Identifier("x".to_string())),
+                token!(TokenKind::Eof),
+            ]),
+        ])),
         token!(TokenKind::Eof),
     ]);
 
@@ -2083,137 +3827,247 @@ fn it_parses_grouping_expression() {
     let json = serde_json::to_value(&ast).unwrap();
 
     assert_json_eq!(
-        json!(
-            [
-                {
-                    "span": {
-                        "column": 0,
-                        "line": 0
-                    },
-                    "expression": {
-                        "Grouping": {
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "Interpolation": {
+                    "parts": [
+                        {
                             "span": {
                                 "column": 0,
                                 "line": 0
                             },
                             "expression": {
-                                "String": "hello"
+                                "String": "x = "
+                            }
+                        },
+                        {
+                            "span": {
+                                "column": 0,
+                                "line": 0
+                            },
+                            "expression": {
+                                "Identifier": "x"
                             }
                         }
-                    }
+                    ]
                 }
-            ]
-        ),
+            }
+        }]),
         json
-    )
+    );
 }
 
 #[test]
-fn it_returns_error_for_unterminated_grouping_expresions() {
+fn it_does_not_allow_empty_placeholder() {
     let mut parser = Parser::new(&[
-        token!(TokenKind::LeftParen),
-        token!(TokenKind::String("hello".to_string())),
+        token!(TokenKind::InterpolatedString(vec![StringPart::Code(vec![
+            token!(TokenKind::Eof)
+        ])])),
         token!(TokenKind::Eof),
     ]);
 
-    match parser.parse() {
-        Ok(_) => assert!(false, "should return an error"),
-        Err(e) => {
-            assert!(matches!(
-                e,
-                ParserError::SyntaxError {
-                    token: Token {
-                        kind: TokenKind::String(_),
-                        ..
-                    },
-                    ..
-                },
-            ));
-        }
-    };
+    assert!(parser.parse().is_err());
 }
 
 #[test]
-fn it_parses_true_bool_literal() {
-    let mut parser = Parser::new(&[token!(TokenKind::True), token!(TokenKind::Eof)]);
-
-    let ast = parser.parse().unwrap().body;
-    let json = serde_json::to_value(&ast).unwrap();
+fn it_parses_vec_literal() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::LeftBracket),
+        token!(TokenKind::Integer(1)),
+        token!(TokenKind::Comma),
+        token!(TokenKind::LeftBracket),
+        token!(TokenKind::RightBracket),
+        token!(TokenKind::Comma),
+        token!(TokenKind::RightBracket),
+        token!(TokenKind::Eof),
+    ]);
 
-    assert_json_eq!(
-        json!(
-            [
-                {
-                    "span": {
-                        "column": 0,
-                        "line": 0
-                    },
-                    "expression": {
-                        "Bool": true
-                    }
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "VecLiteral": {
+                    "elements": [
+                        {
+                            "span": {
+                                "column": 0,
+                                "line": 0
+                            },
+                            "expression": {
+                                "Integer": 1
+                            }
+                        },
+                        {
+                            "span": {
+                                "column": 0,
+                                "line": 0
+                            },
+                            "expression": {
+                                "VecLiteral": {
+                                    "elements": []
+                                }
+                            }
+                        }
+                    ]
                 }
-            ]
-        ),
+            }
+        }]),
         json
-    )
+    );
 }
 
 #[test]
-fn it_parses_false_bool_literal() {
-    let mut parser = Parser::new(&[token!(TokenKind::False), token!(TokenKind::Eof)]);
+fn it_parses_enum_declaration() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Enum),
+        token!(TokenKind::Identifier("List".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("Cons".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("number".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("List".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("Nil".to_string())),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
 
     let ast = parser.parse().unwrap().body;
     let json = serde_json::to_value(&ast).unwrap();
 
     assert_json_eq!(
-        json!(
-            [
-                {
-                    "span": {
-                        "column": 0,
-                        "line": 0
-                    },
-                    "expression": {
-                        "Bool": false
-                    }
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "EnumDecl": {
+                    "name": "List",
+                    "variants": [
+                        {
+                            "name": "Cons",
+                            "fields": ["Numeric", { "Enum": "List" }]
+                        },
+                        {
+                            "name": "Nil",
+                            "fields": []
+                        }
+                    ]
                 }
-            ]
-        ),
+            }
+        }]),
         json
-    )
+    );
 }
 
 #[test]
-fn it_parses_break_expression() {
-    let mut parser = Parser::new(&[token!(TokenKind::Break), token!(TokenKind::Eof)]);
+fn it_parses_enum_used_before_its_declaration() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("s".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("Shape".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("void".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Enum),
+        token!(TokenKind::Identifier("Shape".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("Dot".to_string())),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
 
     let ast = parser.parse().unwrap().body;
-    let json = serde_json::to_value(&ast).unwrap();
+    let json = serde_json::to_value(&ast[0]).unwrap();
 
     assert_json_eq!(
-        json!([{
-            "expression": "Break",
+        json!({
             "span": {
                 "column": 0,
                 "line": 0
+            },
+            "expression": {
+                "FuncDecl": {
+                    "params": [{ "name": "s", "typ": { "Enum": "Shape" } }],
+                    "return_type": "Void",
+                    "body": []
+                }
             }
-        }]),
+        }),
         json
-    )
+    );
 }
 
 #[test]
-fn it_parses_grouping_expression_with_identifiers() {
-    let mut parser = Parser::new(&vec![
-        token!(TokenKind::Identifier("b".to_string())),
+fn it_returns_an_error_when_type_is_not_declared() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("s".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("Shape".to_string())),
+        token!(TokenKind::RightParen),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("void".to_string())),
+        token!(TokenKind::Arrow),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    assert!(matches!(
+        parser.parse(),
+        Err(ParserError::UndefinedType {
+            token: Token {
+                kind: TokenKind::Identifier(ref name),
+                ..
+            },
+            ..
+        }) if name == "Shape"
+    ));
+}
+
+#[test]
+fn it_parses_if_let_as_match() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::If),
+        token!(TokenKind::Let),
+        token!(TokenKind::Identifier("Opt".to_string())),
+        token!(TokenKind::DoubleColon),
+        token!(TokenKind::Identifier("Some".to_string())),
+        token!(TokenKind::LeftParen),
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::RightParen),
         token!(TokenKind::Equal),
+        token!(TokenKind::Identifier("Opt".to_string())),
+        token!(TokenKind::DoubleColon),
+        token!(TokenKind::Identifier("Some".to_string())),
         token!(TokenKind::LeftParen),
-        token!(TokenKind::Identifier("a".to_string())),
-        token!(TokenKind::Plus),
-        token!(TokenKind::Numeric(1.0)),
+        token!(TokenKind::Integer(1)),
         token!(TokenKind::RightParen),
-        token!(TokenKind::Asterisk),
-        token!(TokenKind::Numeric(2.0)),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("x".to_string())),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Else),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Integer(0)),
+        token!(TokenKind::RCurly),
         token!(TokenKind::Eof),
     ]);
 
@@ -2227,86 +4081,135 @@ fn it_parses_grouping_expression_with_identifiers() {
                 "line": 0
             },
             "expression": {
-                "Assignment": {
-                    "left": {
-                        "expression": {
-                            "Identifier": "b"
-                        },
+                "Match": {
+                    "subject": {
                         "span": {
                             "column": 0,
                             "line": 0
                         },
+                        "expression": {
+                            "EnumVariant": {
+                                "name": "Opt",
+                                "variant": "Some",
+                                "args": [{
+                                    "span": {
+                                        "column": 0,
+                                        "line": 0
+                                    },
+                                    "expression": {
+                                        "Integer": 1
+                                    }
+                                }]
+                            }
+                        }
                     },
-                "right": {
-                    "span": {
-                        "column": 0,
-                        "line": 0
-                    },
-                    "expression": {
-                        "Binary": {
-                            "left": {
+                    "arms": [
+                        {
+                            "pattern": {
+                                "Variant": {
+                                    "name": "Opt",
+                                    "variant": "Some",
+                                    "bindings": ["x"]
+                                }
+                            },
+                            "guard": null,
+                            "body": [{
                                 "span": {
                                     "column": 0,
                                     "line": 0
                                 },
                                 "expression": {
-                                    "Grouping": {
-                                        "span": {
-                                            "column": 0,
-                                            "line": 0
-                                        },
-                                        "expression": {
-                                            "Binary": {
-                                                "left": {
-                                                    "expression": {
-                                                        "Identifier": "a"
-                                                    },
-                                                    "span": {
-                                                        "column": 0,
-                                                        "line": 0
-                                                    },
-                                                },
-                                                "operator": "Plus",
-                                                "right": {
-                                                    "expression": {
-                                                        "Numeric": 1.0,
-                                                    },
-                                                    "span": {
-                                                        "column": 0,
-                                                        "line": 0
-                                                    },
-                                                }
-                                            }
-                                        }
-                                    }
+                                    "Identifier": "x"
                                 }
-                            },
-                            "operator": "Asterisk",
-                            "right": {
+                            }]
+                        },
+                        {
+                            "pattern": "Wildcard",
+                            "guard": null,
+                            "body": [{
                                 "span": {
                                     "column": 0,
                                     "line": 0
                                 },
                                 "expression": {
-                                    "Numeric": 2.0
+                                    "Integer": 0
                                 }
-                            }
+                            }]
                         }
-                    }
+                    ]
                 }
             }
+        }]),
+        json
+    );
+}
+
+#[test]
+fn it_parses_nested_vec_types() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Struct),
+        token!(TokenKind::Identifier("Table".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("ids".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("vec".to_string())),
+        token!(TokenKind::Comma),
+        token!(TokenKind::Identifier("rows".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("vec".to_string())),
+        token!(TokenKind::Less),
+        token!(TokenKind::Identifier("vec".to_string())),
+        token!(TokenKind::Less),
+        token!(TokenKind::Identifier("string".to_string())),
+        token!(TokenKind::ShiftRight),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    let ast = parser.parse().unwrap().body;
+    let json = serde_json::to_value(&ast).unwrap();
+
+    assert_json_eq!(
+        json!([{
+            "span": {
+                "column": 0,
+                "line": 0
+            },
+            "expression": {
+                "StructDecl": {
+                    "name": "Table",
+                    "fields": [
+                        {
+                            "typ": {
+                                "Vector": "Numeric"
+                            },
+                            "name": "ids"
+                        },
+                        {
+                            "typ": {
+                                "Vector": {
+                                    "Vector": "String"
+                                }
+                            },
+                            "name": "rows"
+                        }
+                    ]
+                }
             }
         }]),
         json
-    )
+    );
 }
 
 #[test]
-fn it_parses_load_expression() {
-    let mut parser = Parser::new(&vec![
-        token!(TokenKind::Load),
+fn it_parses_type_arguments_of_call() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Identifier("vec_new".to_string())),
+        token!(TokenKind::DoubleColon),
+        token!(TokenKind::Less),
+        token!(TokenKind::Identifier("string".to_string())),
+        token!(TokenKind::Greater),
         token!(TokenKind::LeftParen),
-        token!(TokenKind::String(String::from("somelib.so"))),
         token!(TokenKind::RightParen),
         token!(TokenKind::Eof),
     ]);
@@ -2321,9 +4224,40 @@ fn it_parses_load_expression() {
                 "line": 0
             },
             "expression": {
-                "Load": "somelib.so"
+                "FuncCall": {
+                    "calee": {
+                        "span": {
+                            "column": 0,
+                            "line": 0
+                        },
+                        "expression": {
+                            "Identifier": "vec_new"
+                        }
+                    },
+                    "type_args": ["String"],
+                    "args": []
+                }
             }
         }]),
         json
-    )
+    );
+}
+
+#[test]
+fn it_does_not_allow_vec_of_void() {
+    let mut parser = Parser::new(&[
+        token!(TokenKind::Struct),
+        token!(TokenKind::Identifier("Table".to_string())),
+        token!(TokenKind::LCurly),
+        token!(TokenKind::Identifier("rows".to_string())),
+        token!(TokenKind::Colon),
+        token!(TokenKind::Identifier("vec".to_string())),
+        token!(TokenKind::Less),
+        token!(TokenKind::Identifier("void".to_string())),
+        token!(TokenKind::Greater),
+        token!(TokenKind::RCurly),
+        token!(TokenKind::Eof),
+    ]);
+
+    assert!(parser.parse().is_err());
 }
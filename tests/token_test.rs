@@ -22,11 +22,15 @@ macro_rules! assert_angle_brackets_format_string {
 fn it_assert_token_types_formatng() {
     assert_angle_brackets_format_string!(Identifier, "ident".to_string(), "<Identifier(ident)>");
     assert_angle_brackets_format_string!(Numeric, 10.0, "<Numeric(10)>");
+    assert_angle_brackets_format_string!(Integer, 10, "<Integer(10)>");
     assert_angle_brackets_format_string!(String, "string".to_string(), "<String(string)>");
+    assert_angle_brackets_format_string!(Label, "outer".to_string(), "<Label(outer)>");
     assert_format_string!(LeftParen, "<LeftParen>");
     assert_format_string!(NotEqual, "<NotEqual>");
     assert_format_string!(LeftParen, "<LeftParen>");
     assert_format_string!(RightParen, "<RightParen>");
+    assert_format_string!(LeftBracket, "<LeftBracket>");
+    assert_format_string!(RightBracket, "<RightBracket>");
     assert_format_string!(Slash, "<Slash>");
     assert_format_string!(Plus, "<Plus>");
     assert_format_string!(Minus, "<Minus>");
@@ -36,12 +40,22 @@ fn it_assert_token_types_formatng() {
     assert_format_string!(RCurly, "<RCurly>");
     assert_format_string!(If, "<If>");
     assert_format_string!(While, "<While>");
+    assert_format_string!(For, "<For>");
+    assert_format_string!(In, "<In>");
+    assert_format_string!(DotDot, "<DotDot>");
+    assert_format_string!(Dot, "<Dot>");
     assert_format_string!(True, "<True>");
     assert_format_string!(False, "<False>");
     assert_format_string!(DoubleEqual, "<DoubleEqual>");
     assert_format_string!(Percent, "<Percent>");
     assert_format_string!(Exclamation, "<Exclamation>");
     assert_format_string!(Break, "<Break>");
+    assert_format_string!(Continue, "<Continue>");
+    assert_format_string!(Match, "<Match>");
+    assert_format_string!(Struct, "<Struct>");
+    assert_format_string!(Enum, "<Enum>");
+    assert_format_string!(Let, "<Let>");
+    assert_format_string!(Return, "<Return>");
     assert_format_string!(Eof, "<Eof>");
     assert_format_string!(Comma, "<Comma>");
     assert_format_string!(Arrow, "<Arrow>");
@@ -51,6 +65,13 @@ fn it_assert_token_types_formatng() {
     assert_format_string!(GreaterOrEqual, "<GreaterOrEqual>");
     assert_format_string!(Or, "<Or>");
     assert_format_string!(And, "<And>");
+    assert_format_string!(Pipe, "<Pipe>");
+    assert_format_string!(Ampersand, "<Ampersand>");
+    assert_format_string!(Caret, "<Caret>");
+    assert_format_string!(Tilde, "<Tilde>");
+    assert_format_string!(ShiftLeft, "<ShiftLeft>");
+    assert_format_string!(ShiftRight, "<ShiftRight>");
     assert_format_string!(Else, "<Else>");
     assert_format_string!(Colon, "<Colon>");
+    assert_format_string!(DoubleColon, "<DoubleColon>");
 }
@@ -1,4 +1,4 @@
-use rocklang::token::TokenKind;
+use rocklang::token::{StringPart, TokenKind};
 use rocklang::tokenizer::TokenizerError;
 use rocklang::tokenizer::{Tokenize, Tokenizer};
 
@@ -39,7 +39,7 @@ fn it_tokenizes_all_tokens() {
     assert_token_kind_of!(TokenKind::Identifier(_), tokens[20]);
     assert_token_kind_of!(TokenKind::Identifier(_), tokens[21]);
     assert_token_kind_of!(TokenKind::Numeric(_), tokens[22]);
-    assert_token_kind_of!(TokenKind::Numeric(_), tokens[23]);
+    assert_token_kind_of!(TokenKind::Integer(_), tokens[23]);
     assert_token_kind_of!(TokenKind::LessOrEqual, tokens[24]);
     assert_token_kind_of!(TokenKind::GreaterOrEqual, tokens[25]);
     assert_token_kind_of!(TokenKind::Colon, tokens[26]);
@@ -47,17 +47,273 @@ fn it_tokenizes_all_tokens() {
     assert_eq!(28, tokens.len());
 }
 
+#[test]
+fn it_tokenizes_keywords() {
+    let mut tokenizer = Tokenizer::new(String::from(
+        "if else while break return true false load extern continue match struct enum let\n",
+    ));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_token_kind_of!(TokenKind::If, tokens[0]);
+    assert_token_kind_of!(TokenKind::Else, tokens[1]);
+    assert_token_kind_of!(TokenKind::While, tokens[2]);
+    assert_token_kind_of!(TokenKind::Break, tokens[3]);
+    assert_token_kind_of!(TokenKind::Return, tokens[4]);
+    assert_token_kind_of!(TokenKind::True, tokens[5]);
+    assert_token_kind_of!(TokenKind::False, tokens[6]);
+    assert_token_kind_of!(TokenKind::Load, tokens[7]);
+    assert_token_kind_of!(TokenKind::Extern, tokens[8]);
+    assert_token_kind_of!(TokenKind::Continue, tokens[9]);
+    assert_token_kind_of!(TokenKind::Match, tokens[10]);
+    assert_token_kind_of!(TokenKind::Struct, tokens[11]);
+    assert_token_kind_of!(TokenKind::Enum, tokens[12]);
+    assert_token_kind_of!(TokenKind::Let, tokens[13]);
+    assert_token_kind_of!(TokenKind::Eof, tokens[14]);
+    assert_eq!(15, tokens.len());
+}
+
+#[test]
+fn it_tokenizes_bitwise_operators() {
+    let mut tokenizer = Tokenizer::new(String::from("& | ^ ~ << >> && ||\n"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_token_kind_of!(TokenKind::Ampersand, tokens[0]);
+    assert_token_kind_of!(TokenKind::Pipe, tokens[1]);
+    assert_token_kind_of!(TokenKind::Caret, tokens[2]);
+    assert_token_kind_of!(TokenKind::Tilde, tokens[3]);
+    assert_token_kind_of!(TokenKind::ShiftLeft, tokens[4]);
+    assert_token_kind_of!(TokenKind::ShiftRight, tokens[5]);
+    assert_token_kind_of!(TokenKind::And, tokens[6]);
+    assert_token_kind_of!(TokenKind::Or, tokens[7]);
+    assert_token_kind_of!(TokenKind::Eof, tokens[8]);
+    assert_eq!(9, tokens.len());
+}
+
+#[test]
+fn it_tokenizes_for_loop_over_range() {
+    let mut tokenizer = Tokenizer::new(String::from("for i in 0..10 {}\n"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_token_kind_of!(TokenKind::For, tokens[0]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[1]);
+    assert_token_kind_of!(TokenKind::In, tokens[2]);
+    assert_token_kind_of!(TokenKind::Integer(0), tokens[3]);
+    assert_token_kind_of!(TokenKind::DotDot, tokens[4]);
+    assert_token_kind_of!(TokenKind::Integer(10), tokens[5]);
+    assert_token_kind_of!(TokenKind::LCurly, tokens[6]);
+    assert_token_kind_of!(TokenKind::RCurly, tokens[7]);
+    assert_token_kind_of!(TokenKind::Eof, tokens[8]);
+    assert_eq!(9, tokens.len());
+}
+
+#[test]
+fn it_tokenizes_field_access() {
+    let mut tokenizer = Tokenizer::new(String::from("c.re = 0..n.len\n"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[0]);
+    assert_token_kind_of!(TokenKind::Dot, tokens[1]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[2]);
+    assert_token_kind_of!(TokenKind::Equal, tokens[3]);
+    assert_token_kind_of!(TokenKind::Integer(0), tokens[4]);
+    assert_token_kind_of!(TokenKind::DotDot, tokens[5]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[6]);
+    assert_token_kind_of!(TokenKind::Dot, tokens[7]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[8]);
+    assert_token_kind_of!(TokenKind::Eof, tokens[9]);
+    assert_eq!(10, tokens.len());
+}
+
+#[test]
+fn it_tokenizes_index() {
+    let mut tokenizer = Tokenizer::new(String::from("v[i] = w[0][1]\n"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[0]);
+    assert_token_kind_of!(TokenKind::LeftBracket, tokens[1]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[2]);
+    assert_token_kind_of!(TokenKind::RightBracket, tokens[3]);
+    assert_token_kind_of!(TokenKind::Equal, tokens[4]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[5]);
+    assert_token_kind_of!(TokenKind::LeftBracket, tokens[6]);
+    assert_token_kind_of!(TokenKind::Integer(0), tokens[7]);
+    assert_token_kind_of!(TokenKind::RightBracket, tokens[8]);
+    assert_token_kind_of!(TokenKind::LeftBracket, tokens[9]);
+    assert_token_kind_of!(TokenKind::Integer(1), tokens[10]);
+    assert_token_kind_of!(TokenKind::RightBracket, tokens[11]);
+    assert_token_kind_of!(TokenKind::Eof, tokens[12]);
+    assert_eq!(13, tokens.len());
+}
+
+#[test]
+fn it_tokenizes_enum_variants() {
+    let mut tokenizer = Tokenizer::new(String::from("Shape::Rect(w: number)\n"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[0]);
+    assert_token_kind_of!(TokenKind::DoubleColon, tokens[1]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[2]);
+    assert_token_kind_of!(TokenKind::LeftParen, tokens[3]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[4]);
+    assert_token_kind_of!(TokenKind::Colon, tokens[5]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[6]);
+    assert_token_kind_of!(TokenKind::RightParen, tokens[7]);
+    assert_token_kind_of!(TokenKind::Eof, tokens[8]);
+    assert_eq!(9, tokens.len());
+}
+
+#[test]
+fn it_tokenizes_match_arms() {
+    let mut tokenizer = Tokenizer::new(String::from("match x { 1..5 => a, _ => b }\n"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_token_kind_of!(TokenKind::Match, tokens[0]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[1]);
+    assert_token_kind_of!(TokenKind::LCurly, tokens[2]);
+    assert_token_kind_of!(TokenKind::Integer(1), tokens[3]);
+    assert_token_kind_of!(TokenKind::DotDot, tokens[4]);
+    assert_token_kind_of!(TokenKind::Integer(5), tokens[5]);
+    assert_token_kind_of!(TokenKind::Arrow, tokens[6]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[7]);
+    assert_token_kind_of!(TokenKind::Comma, tokens[8]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[9]);
+    assert_token_kind_of!(TokenKind::Arrow, tokens[10]);
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[11]);
+    assert_token_kind_of!(TokenKind::RCurly, tokens[12]);
+    assert_token_kind_of!(TokenKind::Eof, tokens[13]);
+    assert_eq!(14, tokens.len());
+    assert!(matches!(&tokens[9].kind, TokenKind::Identifier(name) if name == "_"));
+}
+
+#[test]
+fn it_tokenizes_loop_labels() {
+    let mut tokenizer = Tokenizer::new(String::from("'outer: while true { break 'outer }\n"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_token_kind_of!(TokenKind::Label(_), tokens[0]);
+    assert_token_kind_of!(TokenKind::Colon, tokens[1]);
+    assert_token_kind_of!(TokenKind::While, tokens[2]);
+    assert_token_kind_of!(TokenKind::True, tokens[3]);
+    assert_token_kind_of!(TokenKind::LCurly, tokens[4]);
+    assert_token_kind_of!(TokenKind::Break, tokens[5]);
+    assert_token_kind_of!(TokenKind::Label(_), tokens[6]);
+    assert_token_kind_of!(TokenKind::RCurly, tokens[7]);
+    assert_token_kind_of!(TokenKind::Eof, tokens[8]);
+    assert_eq!(9, tokens.len());
+    assert!(matches!(&tokens[6].kind, TokenKind::Label(label) if label == "outer"));
+}
+
+#[test]
+fn it_tokenizes_integer_too_large_for_int_as_numeric() {
+    let mut tokenizer = Tokenizer::new(String::from("99999999999999999999 9223372036854775807"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert!(matches!(tokens[0].kind, TokenKind::Numeric(n) if n == 1e20));
+    assert!(matches!(tokens[1].kind, TokenKind::Integer(i64::MAX)));
+}
+
 #[test]
 fn it_returns_error_for_unexpected_character() {
-    let mut tokenizer = Tokenizer::new(String::from("~"));
+    let mut tokenizer = Tokenizer::new(String::from("$"));
     assert!(matches!(
         tokenizer.tokenize(),
-        Err(TokenizerError { chr: '~', line: 1 }),
+        Err(TokenizerError::UnexpectedCharacter { chr: '$', line: 1 }),
     ));
 }
 
 #[test]
 fn tokenizer_error_display() {
-    let error = TokenizerError { chr: 'a', line: 55 };
+    let error = TokenizerError::UnexpectedCharacter { chr: 'a', line: 55 };
     assert_eq!("unexpected character 'a'", format!("{}", error));
 }
+
+#[test]
+fn it_tokenizes_escape_sequences() {
+    let mut tokenizer = Tokenizer::new(String::from(r#""a\tb\n\\ \"q\" \u{48}\u{1F600}""#));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert!(
+        matches!(&tokens[0].kind, TokenKind::String(literal) if literal == "a\tb\n\\ \"q\" H\u{1F600}")
+    );
+    assert_token_kind_of!(TokenKind::Eof, tokens[1]);
+    assert_eq!(2, tokens.len());
+}
+
+#[test]
+fn it_tokenizes_multi_line_raw_strings() {
+    let mut tokenizer = Tokenizer::new(String::from("r\"a\\n\nb\" r#\"say \"hi\"\"# x"));
+    let tokens = tokenizer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].kind, TokenKind::String(literal) if literal == "a\\n\nb"));
+    assert!(matches!(&tokens[1].kind, TokenKind::String(literal) if literal == "say \"hi\""));
+    assert_token_kind_of!(TokenKind::Identifier(_), tokens[2]);
+    assert_eq!(2, tokens[2].span.line);
+    assert_token_kind_of!(TokenKind::Eof, tokens[3]);
+    assert_eq!(4, tokens.len());
+}
+
+#[test]
+fn it_tokenizes_interpolated_strings() {
+    let mut tokenizer = Tokenizer::new(String::from(r#""x = {x * 2}, {f("}")}\{""#));
+    let tokens = tokenizer.tokenize().unwrap();
+    let parts = match &tokens[0].kind {
+        TokenKind::InterpolatedString(parts) => parts,
+        kind => panic!("unexpected token {}", kind),
+    };
+    assert_eq!(5, parts.len());
+    assert!(matches!(&parts[0], StringPart::Text(text) if text == "x = "));
+    assert!(matches!(&parts[1], StringPart::Code(tokens) if tokens.len() == 4));
+    assert!(matches!(&parts[2], StringPart::Text(text) if text == ", "));
+    match &parts[3] {
+        StringPart::Code(tokens) => {
+            assert_token_kind_of!(TokenKind::Identifier(_), tokens[0]);
+            assert_token_kind_of!(TokenKind::LeftParen, tokens[1]);
+            assert!(matches!(&tokens[2].kind, TokenKind::String(literal) if literal == "}"));
+            assert_token_kind_of!(TokenKind::RightParen, tokens[3]);
+            assert_token_kind_of!(TokenKind::Eof, tokens[4]);
+        }
+        part => panic!("unexpected part {:?}", part),
+    }
+    assert!(matches!(&parts[4], StringPart::Text(text) if text == "{"));
+    assert_token_kind_of!(TokenKind::Eof, tokens[1]);
+}
+
+#[test]
+fn it_returns_error_for_unterminated_string() {
+    let mut tokenizer = Tokenizer::new(String::from("x = 1\ny = \"abc\n"));
+    assert!(matches!(
+        tokenizer.tokenize(),
+        Err(TokenizerError::UnterminatedString { line: 2, column: 4 }),
+    ));
+}
+
+#[test]
+fn it_returns_error_for_unterminated_interpolation() {
+    let mut tokenizer = Tokenizer::new(String::from("x = 1\ny = \"ab{y\"\n"));
+    assert!(matches!(
+        tokenizer.tokenize(),
+        Err(TokenizerError::UnterminatedInterpolation { line: 2, column: 7 }),
+    ));
+
+    let mut tokenizer = Tokenizer::new(String::from("\"{f(\"}"));
+    assert!(matches!(
+        tokenizer.tokenize(),
+        Err(TokenizerError::UnterminatedInterpolation { line: 1, column: 2 }),
+    ));
+}
+
+#[test]
+fn it_returns_error_for_invalid_escape() {
+    let mut tokenizer = Tokenizer::new(String::from(r#""\u{zz}""#));
+    assert!(matches!(
+        tokenizer.tokenize(),
+        Err(TokenizerError::InvalidEscape { sequence, line: 1, .. }) if sequence == "\\u{",
+    ));
+
+    let mut tokenizer = Tokenizer::new(String::from(r#""\q""#));
+    assert!(matches!(
+        tokenizer.tokenize(),
+        Err(TokenizerError::InvalidEscape { sequence, line: 1, column: 2 }) if sequence == "\\q",
+    ));
+}
+
+#[test]
+fn unterminated_string_error_display() {
+    let error = TokenizerError::UnterminatedString { line: 3, column: 7 };
+    assert_eq!("unterminated string at 3:7", format!("{}", error));
+}
+
+#[test]
+fn unterminated_interpolation_error_display() {
+    let error = TokenizerError::UnterminatedInterpolation { line: 3, column: 7 };
+    assert_eq!("unterminated interpolation at 3:7", format!("{}", error));
+}